@@ -24,6 +24,7 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     convert::TryFrom,
     rc::Rc,
+    time::Instant,
 };
 
 use num::Zero;
@@ -66,10 +67,10 @@ pub use self::{
     get_bids::{GetBidsRequest, GetBidsResult},
     query::{QueryRequest, QueryResult},
     run_genesis_request::RunGenesisRequest,
-    step::{RewardItem, SlashItem, StepError, StepRequest, StepSuccess},
+    step::{RewardItem, SlashItem, StepError, StepRequest, StepSuccess, StepTimings},
     system_contract_registry::SystemContractRegistry,
     transfer::{TransferArgs, TransferRuntimeArgsBuilder, TransferTargetMode},
-    upgrade::{UpgradeConfig, UpgradeSuccess},
+    upgrade::{StateMigration, UpgradeConfig, UpgradeSuccess},
 };
 use crate::{
     core::{
@@ -428,6 +429,9 @@ where
             tracking_copy.borrow_mut().write(*key, value.clone());
         }
 
+        // run the state migrations against the upgraded state
+        system_upgrader.run_state_migrations(correlation_id, upgrade_config.state_migrations())?;
+
         let execution_effect = tracking_copy.borrow().effect();
 
         // commit
@@ -1855,6 +1859,9 @@ where
             Ok(())
         })?;
 
+        let mut timings = StepTimings::default();
+
+        let start = Instant::now();
         let distribute_rewards_stack = self.get_new_system_call_stack();
         let (_, execution_result): (Option<()>, ExecutionResult) = executor.call_system_contract(
             DirectSystemContractCall::DistributeRewards,
@@ -1873,6 +1880,8 @@ where
             U512::zero(),
         );
 
+        timings.distribute_rewards = start.elapsed();
+
         if let Some(exec_error) = execution_result.take_error() {
             return Err(StepError::DistributeError(exec_error));
         }
@@ -1888,6 +1897,7 @@ where
                 runtime_args
             };
 
+            let start = Instant::now();
             let slash_stack = self.get_new_system_call_stack();
             let (_, execution_result): (Option<()>, ExecutionResult) = executor
                 .call_system_contract(
@@ -1907,6 +1917,8 @@ where
                     U512::zero(),
                 );
 
+            timings.slash = start.elapsed();
+
             if let Some(exec_error) = execution_result.take_error() {
                 return Err(StepError::SlashingError(exec_error));
             }
@@ -1928,6 +1940,7 @@ where
             Ok(())
         })?;
 
+        let start = Instant::now();
         let run_auction_stack = self.get_new_system_call_stack();
         let (_, execution_result): (Option<()>, ExecutionResult) = executor.call_system_contract(
             DirectSystemContractCall::RunAuction,
@@ -1946,6 +1959,8 @@ where
             U512::zero(),
         );
 
+        timings.run_auction = start.elapsed();
+
         if let Some(exec_error) = execution_result.take_error() {
             return Err(StepError::AuctionError(exec_error));
         }
@@ -1954,6 +1969,7 @@ where
         let execution_journal = tracking_copy.borrow().execution_journal();
 
         // commit
+        let start = Instant::now();
         let post_state_hash = self
             .state
             .commit(
@@ -1962,10 +1978,12 @@ where
                 execution_effect.transforms,
             )
             .map_err(Into::into)?;
+        timings.commit = start.elapsed();
 
         Ok(StepSuccess {
             post_state_hash,
             execution_journal,
+            timings,
         })
     }
 
@@ -2,7 +2,7 @@
 //!
 //! A step request executes auction code, slashes validators, evicts validators and distributes
 //! rewards.
-use std::{collections::BTreeMap, vec::Vec};
+use std::{collections::BTreeMap, time::Duration, vec::Vec};
 
 use casper_hashing::Digest;
 use casper_types::{bytesrepr, CLValueError, EraId, ProtocolVersion, PublicKey};
@@ -186,6 +186,19 @@ impl From<RuntimeStackOverflow> for StepError {
     }
 }
 
+/// The time taken by each phase of a step.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct StepTimings {
+    /// Time taken to distribute rewards via the auction contract.
+    pub distribute_rewards: Duration,
+    /// Time taken to slash validators via the auction contract.
+    pub slash: Duration,
+    /// Time taken to run the auction.
+    pub run_auction: Duration,
+    /// Time taken to commit the effects of the step to global state.
+    pub commit: Duration,
+}
+
 /// Represents a successfully executed step request.
 #[derive(Debug)]
 pub struct StepSuccess {
@@ -193,4 +206,6 @@ pub struct StepSuccess {
     pub post_state_hash: Digest,
     /// Effects of executing a step request.
     pub execution_journal: ExecutionJournal,
+    /// Time taken by each phase of the step.
+    pub timings: StepTimings,
 }
@@ -1,19 +1,22 @@
 //! Support for applying upgrades on the execution engine.
 use std::{cell::RefCell, collections::BTreeMap, fmt, rc::Rc};
 
+use datasize::DataSize;
 use num_rational::Ratio;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tracing::{debug, error};
 
 use casper_hashing::Digest;
 use casper_types::{
-    bytesrepr::{self},
-    system::SystemContractType,
-    Contract, ContractHash, EraId, Key, ProtocolVersion, StoredValue,
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    system::{auction::UnbondingPurse, SystemContractType},
+    Contract, ContractHash, EraId, Key, KeyTag, ProtocolVersion, StoredValue,
 };
 
 use crate::{
     core::{
-        engine_state::{execution_effect::ExecutionEffect, ChainspecRegistry},
+        engine_state::{execution_effect::ExecutionEffect, ChainspecRegistry, Error},
         tracking_copy::TrackingCopy,
     },
     shared::newtypes::CorrelationId,
@@ -53,6 +56,7 @@ pub struct UpgradeConfig {
     new_unbonding_delay: Option<u64>,
     global_state_update: BTreeMap<Key, StoredValue>,
     chainspec_registry: ChainspecRegistry,
+    state_migrations: Vec<StateMigration>,
 }
 
 impl UpgradeConfig {
@@ -70,6 +74,7 @@ impl UpgradeConfig {
         new_unbonding_delay: Option<u64>,
         global_state_update: BTreeMap<Key, StoredValue>,
         chainspec_registry: ChainspecRegistry,
+        state_migrations: Vec<StateMigration>,
     ) -> Self {
         UpgradeConfig {
             pre_state_hash,
@@ -83,6 +88,7 @@ impl UpgradeConfig {
             new_unbonding_delay,
             global_state_update,
             chainspec_registry,
+            state_migrations,
         }
     }
 
@@ -141,12 +147,63 @@ impl UpgradeConfig {
         &self.chainspec_registry
     }
 
+    /// Returns the state migrations to run, in order.
+    pub fn state_migrations(&self) -> &[StateMigration] {
+        &self.state_migrations
+    }
+
     /// Sets new pre state hash.
     pub fn with_pre_state_hash(&mut self, pre_state_hash: Digest) {
         self.pre_state_hash = pre_state_hash;
     }
 }
 
+const WITHDRAWS_TO_UNBONDS_TAG: u8 = 0;
+
+/// A migration of global state which can be run as part of a protocol upgrade.
+///
+/// Migrations are run after the system contracts have been refreshed and the global state update
+/// has been applied.
+#[derive(
+    Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, DataSize, Debug,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum StateMigration {
+    /// Moves the purses held under every `Key::Withdraw` to the corresponding `Key::Unbond`, so
+    /// that they are paid out by the auction.
+    WithdrawsToUnbonds,
+}
+
+impl fmt::Display for StateMigration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StateMigration::WithdrawsToUnbonds => write!(f, "withdraws_to_unbonds"),
+        }
+    }
+}
+
+impl ToBytes for StateMigration {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        match self {
+            StateMigration::WithdrawsToUnbonds => WITHDRAWS_TO_UNBONDS_TAG.to_bytes(),
+        }
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for StateMigration {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, remainder) = u8::from_bytes(bytes)?;
+        match tag {
+            WITHDRAWS_TO_UNBONDS_TAG => Ok((StateMigration::WithdrawsToUnbonds, remainder)),
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
 /// Represents outcomes of a failed protocol upgrade.
 #[derive(Clone, Error, Debug)]
 pub enum ProtocolUpgradeError {
@@ -168,6 +225,9 @@ pub enum ProtocolUpgradeError {
     /// Failed to create system contract registry.
     #[error("Failed to insert system contract registry")]
     FailedToCreateSystemRegistry,
+    /// Failed to read the purses stored under a `Key::Unbond`.
+    #[error("Failed to get stored values under unbonds")]
+    FailedToGetStoredUnbonds,
 }
 
 impl From<bytesrepr::Error> for ProtocolUpgradeError {
@@ -327,4 +387,73 @@ where
 
         Ok(())
     }
+
+    /// Runs the given state migrations in order.
+    pub(crate) fn run_state_migrations(
+        &self,
+        correlation_id: CorrelationId,
+        state_migrations: &[StateMigration],
+    ) -> Result<(), Error> {
+        for state_migration in state_migrations {
+            debug!(%state_migration, "running state migration");
+            match state_migration {
+                StateMigration::WithdrawsToUnbonds => {
+                    self.migrate_withdraws_to_unbonds(correlation_id)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves the purses held under every `Key::Withdraw` to the corresponding `Key::Unbond`,
+    /// leaving an empty list under the `Key::Withdraw`.
+    fn migrate_withdraws_to_unbonds(&self, correlation_id: CorrelationId) -> Result<(), Error> {
+        let withdraw_keys = self
+            .tracking_copy
+            .borrow_mut()
+            .get_keys(correlation_id, &KeyTag::Withdraw)
+            .map_err(|_| Error::FailedToGetWithdrawKeys)?;
+
+        for withdraw_key in withdraw_keys {
+            let withdraw_purses = self
+                .tracking_copy
+                .borrow_mut()
+                .read(correlation_id, &withdraw_key)
+                .map_err(|_| Error::FailedToGetStoredWithdraws)?
+                .ok_or(Error::FailedToGetStoredWithdraws)?
+                .as_withdraw()
+                .ok_or(Error::FailedToGetWithdrawPurses)?
+                .to_owned();
+            if withdraw_purses.is_empty() {
+                continue;
+            }
+
+            let unbond_key = withdraw_key
+                .withdraw_to_unbond()
+                .ok_or(Error::InvalidKeyVariant)?;
+            let mut unbonding_purses = match self
+                .tracking_copy
+                .borrow_mut()
+                .read(correlation_id, &unbond_key)
+                .map_err(|_| {
+                    Error::ProtocolUpgrade(ProtocolUpgradeError::FailedToGetStoredUnbonds)
+                })? {
+                Some(stored_value) => stored_value
+                    .as_unbonding()
+                    .ok_or_else(|| {
+                        error!(%unbond_key, "stored value is not a list of unbonding purses");
+                        Error::ProtocolUpgrade(ProtocolUpgradeError::FailedToGetStoredUnbonds)
+                    })?
+                    .to_owned(),
+                None => Vec::new(),
+            };
+            unbonding_purses.extend(withdraw_purses.into_iter().map(UnbondingPurse::from));
+
+            let mut tracking_copy = self.tracking_copy.borrow_mut();
+            tracking_copy.write(unbond_key, StoredValue::Unbonding(unbonding_purses));
+            tracking_copy.write(withdraw_key, StoredValue::Withdraw(Vec::new()));
+        }
+
+        Ok(())
+    }
 }
@@ -1,12 +1,15 @@
 use std::{
     collections::{HashMap, HashSet},
+    convert::TryFrom,
     ops::Deref,
     sync::{Arc, RwLock},
 };
 
 use casper_hashing::{ChunkWithProof, Digest};
 use casper_types::{bytesrepr::Bytes, Key, StoredValue};
-use tracing::trace;
+use lmdb::{Cursor, DatabaseFlags, WriteFlags};
+use once_cell::sync::OnceCell;
+use tracing::{error, trace};
 
 use crate::{
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
@@ -25,13 +28,16 @@ use crate::{
         trie_store::{
             lmdb::{LmdbTrieStore, ScratchTrieStore},
             operations::{
-                descendant_trie_keys, keys_with_prefix, missing_trie_keys, put_trie, read,
-                read_with_proof, ReadResult,
+                descendant_trie_keys, keys_with_prefix, missing_trie_keys, push_child_trie_keys,
+                put_trie, read, read_with_proof, ReadResult,
             },
         },
     },
 };
 
+/// Name of the database in which trie pruning marks the tries found to be reachable.
+const TRIE_PRUNING_MARKS_DB_NAME: &str = "TRIE_PRUNING_MARKS";
+
 /// Global state implemented against LMDB as a backing data store.
 pub struct LmdbGlobalState {
     /// Environment for LMDB.
@@ -42,6 +48,8 @@ pub struct LmdbGlobalState {
     /// Empty root hash used for a new trie.
     pub(crate) empty_root_hash: Digest,
     digests_without_missing_descendants: RwLock<HashSet<Digest>>,
+    /// Maps the keys of the tries found to be reachable by trie pruning to the run marking them.
+    trie_pruning_marks_db: OnceCell<lmdb::Database>,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -83,6 +91,7 @@ impl LmdbGlobalState {
             trie_store,
             empty_root_hash,
             digests_without_missing_descendants: Default::default(),
+            trie_pruning_marks_db: OnceCell::new(),
         }
     }
 
@@ -130,6 +139,193 @@ impl LmdbGlobalState {
     pub fn trie_store(&self) -> &LmdbTrieStore {
         &self.trie_store
     }
+
+    /// Returns the database in which trie pruning marks reachable tries, creating it if needed.
+    fn trie_pruning_marks_db(&self) -> Result<lmdb::Database, error::Error> {
+        let marks_db = self.trie_pruning_marks_db.get_or_try_init(|| {
+            self.environment
+                .env()
+                .create_db(Some(TRIE_PRUNING_MARKS_DB_NAME), DatabaseFlags::empty())
+        })?;
+        Ok(*marks_db)
+    }
+
+    /// Marks the tries under `trie_keys` and all their descendants as reachable in the trie
+    /// pruning run identified by `generation`, in order and marking at most `max_tries` tries.
+    ///
+    /// Returns the number of leading `trie_keys` whose tries are all marked.  A trie is only marked
+    /// once all its descendants are, so marked tries are skipped along with their descendants, and
+    /// calling this again with the remaining `trie_keys` continues where the previous call stopped.
+    /// The marks are persisted in a single short-lived read-write transaction, so this holds
+    /// across restarts as well.
+    pub fn mark_reachable_tries(
+        &self,
+        generation: u64,
+        trie_keys: &[Digest],
+        max_tries: usize,
+    ) -> Result<usize, error::Error> {
+        let marks_db = self.trie_pruning_marks_db()?;
+        let mut txn = self.environment.create_read_write_txn()?;
+        let mut remaining_marks = max_tries;
+        let mut marked_count = 0;
+        for trie_key in trie_keys {
+            if !mark_descendant_tries(
+                &mut txn,
+                &self.trie_store,
+                marks_db,
+                generation,
+                *trie_key,
+                &mut remaining_marks,
+            )? {
+                break;
+            }
+            marked_count += 1;
+        }
+        txn.commit()?;
+        Ok(marked_count)
+    }
+
+    /// Scans up to `max_keys` keys of the trie store from `start_at` onwards, or from the first
+    /// key if it is `None`, and deletes those tries which are not marked as reachable in the trie
+    /// pruning run identified by `generation`.
+    ///
+    /// Prior to deleting, all tries reachable from `new_trie_keys`, i.e. the state roots committed
+    /// and tries written since marking began, are marked.  This happens inside the same read-write
+    /// transaction as the deletion, so none of them can be removed.  Along with the number of
+    /// deleted tries, the first key not scanned yet is returned to continue the scan from, or
+    /// `None` if the end of the trie store has been reached.  As only sweeping deletes tries, that
+    /// key still exists when continuing.
+    pub fn sweep_unmarked_tries(
+        &self,
+        generation: u64,
+        new_trie_keys: &[Digest],
+        start_at: Option<Digest>,
+        max_keys: usize,
+    ) -> Result<(usize, Option<Digest>), error::Error> {
+        let marks_db = self.trie_pruning_marks_db()?;
+        let mut txn = self.environment.create_read_write_txn()?;
+        for trie_key in new_trie_keys {
+            mark_descendant_tries(
+                &mut txn,
+                &self.trie_store,
+                marks_db,
+                generation,
+                *trie_key,
+                &mut usize::MAX,
+            )?;
+        }
+
+        let mut unmarked = Vec::new();
+        let mut next_key = None;
+        {
+            let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, self.trie_store.get_db())?;
+            // Note: `iter_start` and `iter_from` panic if there is no key to start at, whereas a
+            //       new cursor's `iter` starts at the first key, if any.
+            let iter = match start_at {
+                Some(start_at) => cursor.iter_from(start_at),
+                None => cursor.iter(),
+            };
+            let mut scanned_count = 0;
+            for (raw_key, _) in iter {
+                let trie_key = match Digest::try_from(raw_key) {
+                    Ok(trie_key) => trie_key,
+                    Err(_) => {
+                        error!(?raw_key, "invalid trie key in trie store");
+                        continue;
+                    }
+                };
+                if scanned_count == max_keys {
+                    next_key = Some(trie_key);
+                    break;
+                }
+                scanned_count += 1;
+                if !is_marked(&txn, marks_db, &trie_key, generation)? {
+                    unmarked.push(trie_key);
+                }
+            }
+        }
+
+        for trie_key in &unmarked {
+            txn.del(self.trie_store.get_db(), trie_key, None)?;
+            match txn.del(marks_db, trie_key, None) {
+                Ok(()) | Err(lmdb::Error::NotFound) => (),
+                Err(error) => return Err(error.into()),
+            }
+        }
+        txn.commit()?;
+
+        if !unmarked.is_empty() {
+            // Any cached digest may have had one of its descendants removed, so the cache can no
+            // longer be trusted.
+            self.digests_without_missing_descendants
+                .write()
+                .expect("digest cache write lock")
+                .clear();
+        }
+
+        Ok((unmarked.len(), next_key))
+    }
+}
+
+/// Returns whether the trie under `trie_key` is marked as reachable in `generation`.
+fn is_marked<T: lmdb::Transaction>(
+    txn: &T,
+    marks_db: lmdb::Database,
+    trie_key: &Digest,
+    generation: u64,
+) -> Result<bool, lmdb::Error> {
+    match txn.get(marks_db, trie_key) {
+        Ok(mark) => Ok(mark == generation.to_le_bytes()),
+        Err(lmdb::Error::NotFound) => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+/// Marks the trie under `trie_key` and all its descendants with `generation`, marking at most
+/// `remaining_marks` tries and deducting those marked.  Returns whether all of them are marked.
+///
+/// Tries are marked in post-order, i.e. a trie is only marked once all of its descendants are.
+/// Besides the marked tries, only the ancestors of the first one are read, so every call with
+/// `remaining_marks` greater than zero makes progress.
+fn mark_descendant_tries(
+    txn: &mut lmdb::RwTransaction,
+    trie_store: &LmdbTrieStore,
+    marks_db: lmdb::Database,
+    generation: u64,
+    trie_key: Digest,
+    remaining_marks: &mut usize,
+) -> Result<bool, error::Error> {
+    // Each trie key is visited twice: first to push its children, then to mark it.
+    let mut to_visit = vec![(trie_key, false)];
+    while let Some((trie_key, children_pushed)) = to_visit.pop() {
+        if children_pushed {
+            txn.put(
+                marks_db,
+                &trie_key,
+                &generation.to_le_bytes(),
+                WriteFlags::empty(),
+            )?;
+            *remaining_marks = remaining_marks.saturating_sub(1);
+            continue;
+        }
+        if is_marked(txn, marks_db, &trie_key, generation)? {
+            continue;
+        }
+        if *remaining_marks == 0 {
+            return Ok(false);
+        }
+
+        let trie_bytes =
+            match Store::<Digest, Trie<Key, StoredValue>>::get_raw(trie_store, txn, &trie_key)? {
+                Some(trie_bytes) => trie_bytes,
+                None => continue,
+            };
+        to_visit.push((trie_key, true));
+        let mut child_trie_keys = Vec::new();
+        push_child_trie_keys::<Key, StoredValue>(&trie_bytes, &mut child_trie_keys);
+        to_visit.extend(child_trie_keys.into_iter().map(|child| (child, false)));
+    }
+    Ok(true)
 }
 
 impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
@@ -570,6 +766,129 @@ mod tests {
         );
     }
 
+    /// Returns the keys of all tries in the trie store.
+    fn all_trie_keys(state: &LmdbGlobalState) -> Vec<Digest> {
+        let txn = state.environment.create_read_txn().unwrap();
+        let mut cursor =
+            lmdb::Transaction::open_ro_cursor(&txn, state.trie_store.get_db()).unwrap();
+        let trie_keys = cursor
+            .iter()
+            .map(|(raw_key, _)| Digest::try_from(raw_key).unwrap())
+            .collect();
+        drop(cursor);
+        txn.commit().unwrap();
+        trie_keys
+    }
+
+    /// Marks the tries reachable from `retained_roots` and sweeps the whole trie store, both in
+    /// batches of `batch_size`, returning the number of deleted tries.
+    fn mark_and_sweep(
+        state: &LmdbGlobalState,
+        generation: u64,
+        retained_roots: &[Digest],
+        batch_size: usize,
+    ) -> usize {
+        let mut marked_count = 0;
+        while marked_count < retained_roots.len() {
+            marked_count += state
+                .mark_reachable_tries(generation, &retained_roots[marked_count..], batch_size)
+                .unwrap();
+        }
+
+        let mut deleted_count = 0;
+        let mut start_at = None;
+        loop {
+            let (count, next_key) = state
+                .sweep_unmarked_tries(generation, &[], start_at, batch_size)
+                .unwrap();
+            assert!(count <= batch_size);
+            deleted_count += count;
+            match next_key {
+                Some(next_key) => start_at = Some(next_key),
+                None => break,
+            }
+        }
+        deleted_count
+    }
+
+    /// Creates a test state along with a second state root updating it.
+    fn create_updated_test_state() -> (LmdbGlobalState, Digest, Digest) {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let effects: AdditiveMap<Key, Transform> = create_test_pairs_updated()
+            .iter()
+            .map(|TestPair { key, value }| (*key, Transform::Write(value.to_owned())))
+            .collect();
+        let updated_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+        (state, root_hash, updated_hash)
+    }
+
+    #[test]
+    fn prunes_same_tries_regardless_of_batch_size() {
+        let (state, _, updated_hash) = create_updated_test_state();
+        let trie_count = all_trie_keys(&state).len();
+        let deleted_count = mark_and_sweep(&state, 1, &[updated_hash], usize::MAX);
+        assert!(deleted_count > 0);
+        let remaining = all_trie_keys(&state);
+
+        for batch_size in 1..=trie_count {
+            let (state, _, updated_hash) = create_updated_test_state();
+            assert_eq!(
+                mark_and_sweep(&state, 1, &[updated_hash], batch_size),
+                deleted_count
+            );
+            assert_eq!(all_trie_keys(&state), remaining);
+        }
+    }
+
+    #[test]
+    fn prunes_tries_unreachable_from_retained_roots() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+        let (state, root_hash, updated_hash) = create_updated_test_state();
+
+        assert!(mark_and_sweep(&state, 1, &[updated_hash], 1) > 0);
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert!(state
+            .missing_trie_keys(correlation_id, vec![updated_hash])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn prunes_tries_marked_in_previous_generation_only() {
+        let (state, root_hash, updated_hash) = create_updated_test_state();
+
+        mark_and_sweep(&state, 1, &[root_hash, updated_hash], 1);
+        assert!(state.checkout(root_hash).unwrap().is_some());
+        assert!(mark_and_sweep(&state, 2, &[updated_hash], 1) > 0);
+
+        assert!(state.checkout(root_hash).unwrap().is_none());
+        assert!(state.checkout(updated_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn does_not_prune_tries_reachable_from_new_trie_keys() {
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        let trie_keys = all_trie_keys(&state);
+
+        let (deleted_count, next_key) = state
+            .sweep_unmarked_tries(1, &[root_hash], None, usize::MAX)
+            .unwrap();
+
+        assert!(next_key.is_none());
+        assert!(state.checkout(root_hash).unwrap().is_some());
+        assert!(deleted_count < trie_keys.len());
+    }
+
     #[test]
     fn returns_trie_or_chunk() {
         let correlation_id = CorrelationId::new();
@@ -602,7 +602,9 @@ impl<'a> Iterator for DescendantsIterator<'a> {
 }
 
 /// Hash bytes into chunks if necessary.
-pub(crate) fn hash_bytes_into_chunks_if_necessary(bytes: &[u8]) -> Digest {
+///
+/// This is the key under which a serialized trie is held in the trie store.
+pub fn hash_bytes_into_chunks_if_necessary(bytes: &[u8]) -> Digest {
     if bytes.len() <= ChunkWithProof::CHUNK_SIZE_BYTES {
         Digest::hash(bytes)
     } else {
@@ -348,46 +348,55 @@ where
             }
         };
 
-        // Optimization: Don't deserialize leaves as they have no descendants.
-        if let Some(&Trie::<K, V>::LEAF_TAG) = retrieved_trie_bytes.first() {
-            continue;
-        }
+        push_child_trie_keys::<K, V>(&retrieved_trie_bytes, &mut trie_keys_to_visit);
+    }
+    let elapsed = start.elapsed().as_millis();
+    trace!(%elapsed, "descendant_trie_keys took ms");
+    Ok(visited)
+}
 
-        // Parse the trie, handling errors gracefully.
-        let retrieved_trie = match bytesrepr::deserialize_from_slice(retrieved_trie_bytes) {
-            Ok(retrieved_trie) => retrieved_trie,
-            // Couldn't parse; treat as missing and continue.
-            Err(err) => {
-                error!(?err, "unable to parse trie");
-                continue;
-            }
-        };
+/// Pushes the keys of the children of the given serialized trie onto `trie_keys_to_visit`.
+pub(crate) fn push_child_trie_keys<K, V>(trie_bytes: &[u8], trie_keys_to_visit: &mut Vec<Digest>)
+where
+    K: FromBytes,
+    V: FromBytes,
+{
+    // Optimization: Don't deserialize leaves as they have no descendants.
+    if let Some(&Trie::<K, V>::LEAF_TAG) = trie_bytes.first() {
+        return;
+    }
 
-        match retrieved_trie {
-            // Should be unreachable due to checking the first byte as a shortcut above.
-            Trie::<K, V>::Leaf { .. } => {
-                error!("did not expect to see a trie leaf in `missing_trie_keys` after shortcut");
-            }
-            // If we hit a pointer block, queue up all of the nodes it points to
-            Trie::Node { pointer_block } => {
-                for (_, pointer) in pointer_block.as_indexed_pointers() {
-                    match pointer {
-                        Pointer::LeafPointer(descendant_leaf_trie_key) => {
-                            trie_keys_to_visit.push(descendant_leaf_trie_key)
-                        }
-                        Pointer::NodePointer(descendant_node_trie_key) => {
-                            trie_keys_to_visit.push(descendant_node_trie_key)
-                        }
+    // Parse the trie, handling errors gracefully.
+    let retrieved_trie: Trie<K, V> = match bytesrepr::deserialize_from_slice(trie_bytes) {
+        Ok(retrieved_trie) => retrieved_trie,
+        // Couldn't parse; treat as missing and continue.
+        Err(err) => {
+            error!(?err, "unable to parse trie");
+            return;
+        }
+    };
+
+    match retrieved_trie {
+        // Should be unreachable due to checking the first byte as a shortcut above.
+        Trie::Leaf { .. } => {
+            error!("did not expect to see a trie leaf in `descendant_trie_keys` after shortcut");
+        }
+        // If we hit a pointer block, queue up all of the nodes it points to
+        Trie::Node { pointer_block } => {
+            for (_, pointer) in pointer_block.as_indexed_pointers() {
+                match pointer {
+                    Pointer::LeafPointer(descendant_leaf_trie_key) => {
+                        trie_keys_to_visit.push(descendant_leaf_trie_key)
+                    }
+                    Pointer::NodePointer(descendant_node_trie_key) => {
+                        trie_keys_to_visit.push(descendant_node_trie_key)
                     }
                 }
             }
-            // If we hit an extension block, add its pointer to the queue
-            Trie::Extension { pointer, .. } => trie_keys_to_visit.push(pointer.into_hash()),
         }
+        // If we hit an extension block, add its pointer to the queue
+        Trie::Extension { pointer, .. } => trie_keys_to_visit.push(pointer.into_hash()),
     }
-    let elapsed = start.elapsed().as_millis();
-    trace!(%elapsed, "descendant_trie_keys took ms");
-    Ok(visited)
 }
 
 struct TrieScan<K, V> {
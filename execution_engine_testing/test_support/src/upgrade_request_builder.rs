@@ -2,7 +2,9 @@ use std::collections::BTreeMap;
 
 use num_rational::Ratio;
 
-use casper_execution_engine::core::engine_state::{ChainspecRegistry, UpgradeConfig};
+use casper_execution_engine::core::engine_state::{
+    ChainspecRegistry, StateMigration, UpgradeConfig,
+};
 use casper_hashing::Digest;
 use casper_types::{EraId, Key, ProtocolVersion, StoredValue};
 
@@ -19,6 +21,7 @@ pub struct UpgradeRequestBuilder {
     new_unbonding_delay: Option<u64>,
     global_state_update: BTreeMap<Key, StoredValue>,
     chainspec_registry: ChainspecRegistry,
+    state_migrations: Vec<StateMigration>,
 }
 
 impl UpgradeRequestBuilder {
@@ -99,6 +102,12 @@ impl UpgradeRequestBuilder {
         self
     }
 
+    /// Sets the state migrations to run.
+    pub fn with_state_migrations(mut self, state_migrations: Vec<StateMigration>) -> Self {
+        self.state_migrations = state_migrations;
+        self
+    }
+
     /// Consumes the `UpgradeRequestBuilder` and returns an [`UpgradeConfig`].
     pub fn build(self) -> UpgradeConfig {
         UpgradeConfig::new(
@@ -113,6 +122,7 @@ impl UpgradeRequestBuilder {
             self.new_unbonding_delay,
             self.global_state_update,
             self.chainspec_registry,
+            self.state_migrations,
         )
     }
 }
@@ -131,6 +141,7 @@ impl Default for UpgradeRequestBuilder {
             new_unbonding_delay: None,
             global_state_update: Default::default(),
             chainspec_registry: ChainspecRegistry::new_with_optional_global_state(&[], None),
+            state_migrations: Vec::new(),
         }
     }
 }
@@ -4,14 +4,15 @@ use num_rational::Ratio;
 
 use casper_engine_test_support::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNT_ADDR,
-    DEFAULT_MAX_ASSOCIATED_KEYS, DEFAULT_UNBONDING_DELAY, DEFAULT_WASM_CONFIG,
-    PRODUCTION_RUN_GENESIS_REQUEST,
+    DEFAULT_ACCOUNT_PUBLIC_KEY, DEFAULT_MAX_ASSOCIATED_KEYS, DEFAULT_UNBONDING_DELAY,
+    DEFAULT_WASM_CONFIG, PRODUCTION_RUN_GENESIS_REQUEST,
 };
 
 use casper_execution_engine::{
     core::engine_state::{
         engine_config::{DEFAULT_MINIMUM_DELEGATION_AMOUNT, DEFAULT_STRICT_ARGUMENT_CHECKING},
-        EngineConfig, DEFAULT_MAX_QUERY_DEPTH, DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
+        EngineConfig, StateMigration, DEFAULT_MAX_QUERY_DEPTH,
+        DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
     },
     shared::{
         host_function_costs::HostFunctionCosts,
@@ -37,11 +38,12 @@ use casper_types::{
     runtime_args,
     system::{
         auction::{
-            AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
+            UnbondingPurse, WithdrawPurse, AUCTION_DELAY_KEY, LOCKED_FUNDS_PERIOD_KEY,
+            UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         mint::ROUND_SEIGNIORAGE_RATE_KEY,
     },
-    CLValue, EraId, ProtocolVersion, RuntimeArgs, StoredValue, U256, U512,
+    AccessRights, CLValue, EraId, Key, ProtocolVersion, RuntimeArgs, StoredValue, URef, U256, U512,
 };
 
 const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
@@ -657,6 +659,63 @@ fn should_apply_global_state_upgrade() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_migrate_withdraws_to_unbonds() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let withdraw_purse = WithdrawPurse::new(
+        URef::new([1; 32], AccessRights::READ_ADD_WRITE),
+        DEFAULT_ACCOUNT_PUBLIC_KEY.clone(),
+        DEFAULT_ACCOUNT_PUBLIC_KEY.clone(),
+        EraId::new(1),
+        U512::from(1_000),
+    );
+
+    // Seed a legacy withdraw purse via the global state update, which is applied before migrating.
+    let mut update_map = BTreeMap::new();
+    update_map.insert(
+        Key::Withdraw(*DEFAULT_ACCOUNT_ADDR),
+        StoredValue::Withdraw(vec![withdraw_purse.clone()]),
+    );
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_global_state_update(update_map)
+            .with_state_migrations(vec![StateMigration::WithdrawsToUnbonds])
+            .build()
+    };
+
+    builder
+        .upgrade_with_upgrade_request(*builder.get_engine_state().config(), &mut upgrade_request)
+        .expect_upgrade_success();
+
+    let unbonding_purses = builder
+        .query(None, Key::Unbond(*DEFAULT_ACCOUNT_ADDR), &[])
+        .expect("should have unbonding purses")
+        .as_unbonding()
+        .expect("should be unbonding purses")
+        .clone();
+    assert_eq!(unbonding_purses, vec![UnbondingPurse::from(withdraw_purse)]);
+
+    let withdraw_purses = builder
+        .query(None, Key::Withdraw(*DEFAULT_ACCOUNT_ADDR), &[])
+        .expect("should have withdraw purses")
+        .as_withdraw()
+        .expect("should be withdraw purses")
+        .clone();
+    assert!(withdraw_purses.is_empty());
+}
+
 #[ignore]
 #[test]
 fn should_increase_max_associated_keys_after_upgrade() {
@@ -3,12 +3,16 @@
 //! Most configuration is done via config files (see [`config`](../config/index.html) for details).
 
 pub mod arglang;
+mod inspect;
+mod preset;
+mod validate;
 
 use std::{
     alloc::System,
     env, fs,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 
 use anyhow::{self, Context};
@@ -19,14 +23,23 @@ use structopt::StructOpt;
 use toml::{value::Table, Value};
 use tracing::{error, info};
 
+pub use self::{inspect::StorageQuery, preset::Preset};
 use crate::{
     logging,
-    reactor::{initializer, joiner, participating, ReactorExit, Runner},
+    reactor::{
+        crash_dump, initializer, joiner, participating, watchdog, ConfigLoader, ReactorExit, Runner,
+    },
     setup_signal_hooks,
     types::ExitCode,
     utils::WithDir,
 };
 
+/// Prefix of environment variables overriding config file entries.
+const ENV_OVERRIDE_PREFIX: &str = "NODE_";
+
+/// Separator between the section and key names in environment variable overrides.
+const ENV_OVERRIDE_SEPARATOR: &str = "__";
+
 // We override the standard allocator to gather metrics and tune the allocator via th MALLOC_CONF
 // env var.
 #[global_allocator]
@@ -39,11 +52,17 @@ static ALLOC: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
 pub enum Cli {
     /// Run the validator node.
     ///
-    /// Loads the configuration values from the given configuration file or uses defaults if not
-    /// given, then runs the reactor.
+    /// Loads the configuration values from the given configuration file, on top of the defaults of
+    /// the given preset if any, then runs the reactor.
     Validator {
-        /// Path to configuration file.
-        config: PathBuf,
+        /// Path to configuration file.  May only be omitted if a preset is given.
+        #[structopt(required_unless = "preset")]
+        config: Option<PathBuf>,
+
+        /// Built-in defaults to use for any values not set in the config file: one of 'mainnet',
+        /// 'testnet' or 'local'.
+        #[structopt(long)]
+        preset: Option<Preset>,
 
         #[structopt(
             short = "C",
@@ -53,7 +72,9 @@ pub enum Cli {
             value_delimiter(";")
         )]
         /// Overrides and extensions for configuration file entries in the form
-        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=node.chainspec_config_path=chainspec.toml'
+        /// <SECTION>.<KEY>=<VALUE>.  For example, '-C=node.chainspec_config_path=chainspec.toml'.
+        /// May be repeated, and takes precedence over environment variables of the form
+        /// NODE_<SECTION>__<KEY>.
         config_ext: Vec<ConfigExt>,
     },
     /// Migrate modified values from the old config as required after an upgrade.
@@ -74,12 +95,75 @@ pub enum Cli {
         #[structopt(long)]
         new_config: PathBuf,
     },
+    /// Check the configuration file and the chainspec alongside it without starting the node.
+    ///
+    /// Prints a JSON report of all problems found, and exits with a non-zero code if any of them
+    /// would prevent the node from running correctly.
+    ValidateConfig {
+        /// Path to configuration file.  May only be omitted if a preset is given.
+        #[structopt(required_unless = "preset")]
+        config: Option<PathBuf>,
+
+        /// Built-in defaults to use for any values not set in the config file: one of 'mainnet',
+        /// 'testnet' or 'local'.
+        #[structopt(long)]
+        preset: Option<Preset>,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.
+        config_ext: Vec<ConfigExt>,
+    },
+    /// Check a chainspec without starting the node.
+    ///
+    /// Prints a JSON report of all problems found, and exits with a non-zero code if any of them
+    /// would prevent the node from running correctly.
+    ValidateChainspec {
+        /// Path to the chainspec file, or the directory containing it.
+        chainspec: PathBuf,
+    },
+    /// Query the storage of a stopped node without starting it.
+    ///
+    /// Opens the storage referred to by the configuration file read-only, and prints the result of
+    /// the query as JSON.
+    InspectStorage {
+        /// Path to configuration file.  May only be omitted if a preset is given.
+        #[structopt(required_unless = "preset")]
+        config: Option<PathBuf>,
+
+        /// Built-in defaults to use for any values not set in the config file: one of 'mainnet',
+        /// 'testnet' or 'local'.
+        #[structopt(long)]
+        preset: Option<Preset>,
+
+        #[structopt(
+            short = "C",
+            long,
+            env = "NODE_CONFIG",
+            use_delimiter(true),
+            value_delimiter(";")
+        )]
+        /// Overrides and extensions for configuration file entries in the form
+        /// <SECTION>.<KEY>=<VALUE>.
+        config_ext: Vec<ConfigExt>,
+
+        /// The item to look up in the storage.
+        #[structopt(subcommand)]
+        query: StorageQuery,
+    },
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 /// Command line extension to be applied to TOML-based config file values.
 pub struct ConfigExt {
     section: String,
+    /// The key within the section.  Dots separate the names of nested tables.
     key: String,
     value: String,
 }
@@ -90,22 +174,56 @@ impl ConfigExt {
     /// Returns errors if the respective sections to be updated are not TOML tables or if parsing
     /// the command line options failed.
     fn update_toml_table(&self, toml_value: &mut Value) -> anyhow::Result<()> {
-        let table = toml_value
+        let mut table = toml_value
             .as_table_mut()
             .ok_or_else(|| anyhow::anyhow!("configuration table is not a table"))?;
 
-        if !table.contains_key(&self.section) {
-            table.insert(self.section.clone(), Value::Table(Table::new()));
+        let mut path = vec![self.section.as_str()];
+        path.extend(self.key.split('.'));
+        let (key, tables) = path.split_last().expect("path cannot be empty");
+        for name in tables {
+            table = table
+                .entry(name.to_string())
+                .or_insert(Value::Table(Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| anyhow::anyhow!("configuration section {} is not a table", name))?;
         }
         let val = arglang::parse(&self.value)?;
-        table[&self.section]
-            .as_table_mut()
-            .ok_or_else(|| {
-                anyhow::anyhow!("configuration section {} is not a table", self.section)
-            })?
-            .insert(self.key.clone(), val);
+        table.insert(key.to_string(), val);
         Ok(())
     }
+
+    /// Collects overrides from environment variables of the form `NODE_<SECTION>__<KEY>`, where
+    /// further `__` separators denote nested tables.  Names are converted to lowercase.
+    ///
+    /// Variables without a separator, such as `NODE_CONFIG`, are ignored.  The overrides are
+    /// returned sorted by variable name so that they are applied in a consistent order.
+    fn from_env_vars<I>(vars: I) -> Vec<ConfigExt>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut overrides: Vec<(String, ConfigExt)> = vars
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let path = name.strip_prefix(ENV_OVERRIDE_PREFIX)?.to_lowercase();
+                let (section, key) = path.split_once(ENV_OVERRIDE_SEPARATOR)?;
+                if section.is_empty() || key.is_empty() {
+                    return None;
+                }
+                let config_ext = ConfigExt {
+                    section: section.to_string(),
+                    key: key.replace(ENV_OVERRIDE_SEPARATOR, "."),
+                    value,
+                };
+                Some((name, config_ext))
+            })
+            .collect();
+        overrides.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
+        overrides
+            .into_iter()
+            .map(|(_, config_ext)| config_ext)
+            .collect()
+    }
 }
 
 impl FromStr for ConfigExt {
@@ -141,12 +259,28 @@ impl Cli {
     /// Executes selected CLI command.
     pub async fn run(self) -> anyhow::Result<i32> {
         match self {
-            Cli::Validator { config, config_ext } => {
+            Cli::Validator {
+                config,
+                preset,
+                config_ext,
+            } => {
                 // Setup UNIX signal hooks.
                 setup_signal_hooks();
 
-                let validator_config = Self::init(&config, config_ext)?;
+                let validator_config = Self::init(preset, config.as_deref(), &config_ext)?;
                 info!(version = %crate::VERSION_STRING.as_str(), "node starting up");
+                let slow_event_threshold =
+                    Duration::from(validator_config.value().node.slow_event_threshold);
+                let shutdown_config = validator_config.value().node.shutdown.clone();
+                if validator_config.value().node.watchdog.enabled {
+                    watchdog::start(validator_config.value().node.watchdog.clone());
+                }
+
+                // Reloading re-reads the config file and reapplies the command line overrides.
+                let config_path = config.clone();
+                let config_loader = ConfigLoader::new(move || {
+                    Self::load_config(preset, config_path.as_deref(), &config_ext)
+                });
 
                 // We use a `ChaCha20Rng` for the production node. For one, we want to completely
                 // eliminate any chance of runtime failures, regardless of how small (these
@@ -163,6 +297,9 @@ impl Cli {
                     &registry,
                 )
                 .await?;
+                initializer_runner.set_config_loader(config_loader.clone());
+                initializer_runner.set_slow_event_threshold(slow_event_threshold);
+                initializer_runner.set_shutdown_config(shutdown_config.clone());
 
                 match initializer_runner.run(&mut rng).await {
                     ReactorExit::ProcessShouldExit(exit_code) => return Ok(exit_code as i32),
@@ -170,16 +307,16 @@ impl Cli {
                 }
 
                 let initializer = initializer_runner.drain_into_inner().await;
-                let root = config
-                    .parent()
-                    .map(|path| path.to_owned())
-                    .unwrap_or_else(|| "/".into());
+                let root = Self::root_dir(config.as_deref());
                 let mut joiner_runner = Runner::<joiner::Reactor>::with_metrics(
                     WithDir::new(root, initializer),
                     &mut rng,
                     &registry,
                 )
                 .await?;
+                joiner_runner.set_config_loader(config_loader.clone());
+                joiner_runner.set_slow_event_threshold(slow_event_threshold);
+                joiner_runner.set_shutdown_config(shutdown_config.clone());
                 match joiner_runner.run(&mut rng).await {
                     ReactorExit::ProcessShouldExit(exit_code) => return Ok(exit_code as i32),
                     ReactorExit::ProcessShouldContinue => info!("finished joining"),
@@ -191,6 +328,9 @@ impl Cli {
                 let mut participating_runner =
                     Runner::<participating::Reactor>::with_metrics(config, &mut rng, &registry)
                         .await?;
+                participating_runner.set_config_loader(config_loader);
+                participating_runner.set_slow_event_threshold(slow_event_threshold);
+                participating_runner.set_shutdown_config(shutdown_config);
 
                 match participating_runner.run(&mut rng).await {
                     ReactorExit::ProcessShouldExit(exit_code) => Ok(exit_code as i32),
@@ -204,7 +344,7 @@ impl Cli {
                 old_config,
                 new_config,
             } => {
-                let new_config = Self::init(&new_config, vec![])?;
+                let new_config = Self::init(None, Some(&new_config), &[])?;
 
                 let old_root = old_config
                     .parent()
@@ -226,7 +366,7 @@ impl Cli {
                 old_config,
                 new_config,
             } => {
-                let new_config = Self::init(&new_config, vec![])?;
+                let new_config = Self::init(None, Some(&new_config), &[])?;
 
                 let old_root = old_config
                     .parent()
@@ -244,39 +384,179 @@ impl Cli {
                 )?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::ValidateConfig {
+                config,
+                preset,
+                config_ext,
+            } => {
+                // Logging is not initialized, so that stdout only contains the report.
+                let report = validate::validate_config(
+                    Self::root_dir(config.as_deref()),
+                    Self::load_config(preset, config.as_deref(), &config_ext),
+                );
+                println!("{}", report.to_json());
+                Ok(report.exit_code() as i32)
+            }
+            Cli::ValidateChainspec { chainspec } => {
+                let report = validate::validate_chainspec(&chainspec);
+                println!("{}", report.to_json());
+                Ok(report.exit_code() as i32)
+            }
+            Cli::InspectStorage {
+                config,
+                preset,
+                config_ext,
+                query,
+            } => {
+                // Logging is not initialized, so that stdout only contains the result.
+                let json = inspect::inspect_storage(
+                    Self::root_dir(config.as_deref()),
+                    Self::load_config(preset, config.as_deref(), &config_ext)?,
+                    query,
+                )?;
+                println!("{}", serde_json::to_string_pretty(&json)?);
+                Ok(ExitCode::Success as i32)
+            }
         }
     }
 
     /// Parses the config file for the current version of casper-node, and initializes logging.
     fn init(
-        config: &Path,
-        config_ext: Vec<ConfigExt>,
+        preset: Option<Preset>,
+        config: Option<&Path>,
+        config_ext: &[ConfigExt],
     ) -> anyhow::Result<WithDir<participating::Config>> {
-        // Determine the parent directory of the configuration file, if any.
-        // Otherwise, we default to `/`.
-        let root = config
-            .parent()
-            .map(|path| path.to_owned())
-            .unwrap_or_else(|| "/".into());
-
-        // The app supports running without a config file, using default values.
-        let encoded_config = fs::read_to_string(&config)
-            .context("could not read configuration file")
-            .with_context(|| config.display().to_string())?;
-
-        // Get the TOML table version of the config indicated from CLI args, or from a new
-        // defaulted config instance if one is not provided.
-        let mut config_table: Value = toml::from_str(&encoded_config)?;
-
-        // If any command line overrides to the config values are passed, apply them.
+        let root = Self::root_dir(config);
+        let mut participating_config = Self::load_config(preset, config, config_ext)?;
+        // Logging starts before the config is wrapped with its root dir, so resolve the log file
+        // path up front.
+        participating_config.logging.resolve_file_path(&root);
+        logging::init_with_config(&participating_config.logging)?;
+        if let Some(ref crash_dump_dir) = participating_config.node.crash_dump_dir {
+            crash_dump::enable(root.join(crash_dump_dir));
+        }
+
+        Ok(WithDir::new(root, participating_config))
+    }
+
+    /// Returns the directory against which relative paths in the config are resolved.
+    ///
+    /// This is the parent directory of the configuration file if there is one, or the current
+    /// directory if running from a preset only.
+    fn root_dir(config: Option<&Path>) -> PathBuf {
+        match config {
+            // If the configuration file has no parent directory, we default to `/`.
+            Some(config) => config
+                .parent()
+                .map(|path| path.to_owned())
+                .unwrap_or_else(|| "/".into()),
+            None => env::current_dir().unwrap_or_else(|_| ".".into()),
+        }
+    }
+
+    /// Parses the config file for the current version of casper-node on top of the given preset,
+    /// applying any overrides from environment variables and then from the command line.
+    fn load_config(
+        preset: Option<Preset>,
+        config: Option<&Path>,
+        config_ext: &[ConfigExt],
+    ) -> anyhow::Result<participating::Config> {
+        // Start from the preset, if any, or else an empty table, in which case the config file
+        // needs to provide all sections.
+        let mut config_table = match preset {
+            Some(preset) => preset.config_table()?,
+            None => Value::Table(Table::new()),
+        };
+
+        if let Some(config) = config {
+            let encoded_config = fs::read_to_string(&config)
+                .context("could not read configuration file")
+                .with_context(|| config.display().to_string())?;
+            preset::merge(&mut config_table, toml::from_str(&encoded_config)?);
+        }
+
+        // Apply any overrides from the environment, then from the command line, so that the latter
+        // take precedence.
+        for item in ConfigExt::from_env_vars(env::vars()) {
+            item.update_toml_table(&mut config_table).with_context(|| {
+                format!(
+                    "invalid environment override for {}.{}",
+                    item.section, item.key
+                )
+            })?;
+        }
         for item in config_ext {
             item.update_toml_table(&mut config_table)?;
         }
 
         // Create participating config, including any overridden values.
-        let participating_config: participating::Config = config_table.try_into()?;
-        logging::init_with_config(&participating_config.logging)?;
+        Ok(config_table.try_into()?)
+    }
+}
 
-        Ok(WithDir::new(root, participating_config))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_env_var_overrides() {
+        let vars = vec![
+            ("NODE_CONFIG", "network.bind_address=0.0.0.0:1"),
+            ("NODE_RPC_SERVER__QPS_LIMIT", "50"),
+            ("NODE_NETWORK__ESTIMATOR_WEIGHTS__GOSSIP", "2"),
+            ("NODE___KEY", "1"),
+            ("OTHER_NETWORK__BIND_ADDRESS", "0.0.0.0:2"),
+        ];
+        let overrides = ConfigExt::from_env_vars(
+            vars.into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        assert_eq!(
+            overrides,
+            vec![
+                ConfigExt::from_str("network.estimator_weights.gossip=2").unwrap(),
+                ConfigExt::from_str("rpc_server.qps_limit=50").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_apply_nested_overrides() {
+        let mut config: Value = toml::from_str(
+            r#"
+            [network]
+            bind_address = '0.0.0.0:34553'
+            [network.estimator_weights]
+            consensus = 0
+            "#,
+        )
+        .unwrap();
+
+        for ext in &[
+            "network.bind_address=0.0.0.0:1",
+            "network.estimator_weights.consensus=2",
+            "new_section.table.key=true",
+        ] {
+            ConfigExt::from_str(ext)
+                .unwrap()
+                .update_toml_table(&mut config)
+                .unwrap();
+        }
+
+        assert_eq!(
+            config["network"]["bind_address"].as_str(),
+            Some("0.0.0.0:1")
+        );
+        assert_eq!(
+            config["network"]["estimator_weights"]["consensus"].as_integer(),
+            Some(2)
+        );
+        assert_eq!(config["new_section"]["table"]["key"].as_bool(), Some(true));
+
+        // Overriding a value with a table is an error.
+        assert!(ConfigExt::from_str("network.bind_address.port=1")
+            .unwrap()
+            .update_toml_table(&mut config)
+            .is_err());
     }
 }
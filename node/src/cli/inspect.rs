@@ -0,0 +1,261 @@
+//! Offline inspection of the node's storage.
+//!
+//! Used by the `inspect-storage` subcommand, which opens the storage of a stopped node read-only
+//! and prints the result of a single query as JSON.
+
+use std::path::PathBuf;
+
+use anyhow::{self, bail, Context};
+use serde::Serialize;
+use serde_json::Value;
+use structopt::StructOpt;
+
+use casper_hashing::Digest;
+use casper_types::{EraId, Timestamp};
+
+use crate::{
+    components::storage::Storage,
+    reactor::participating,
+    types::{
+        chainspec::ChainspecRawBytes, BlockHash, BlockHashAndHeight, BlockHeader, Chainspec,
+        Deploy, DeployHash, JsonBlock,
+    },
+    utils::{Loadable, WithDir},
+};
+
+/// A query to run against the storage.
+#[derive(Debug, StructOpt)]
+pub enum StorageQuery {
+    /// Print a block along with its finality signatures.
+    Block {
+        /// The height of the block, or its hex-encoded hash.
+        id: String,
+    },
+    /// Print a deploy, along with the hash and height of the block containing it, if any.
+    Deploy {
+        /// The hex-encoded hash of the deploy.
+        hash: String,
+    },
+    /// Print the era, height and hash of every switch block, in order of era.
+    SwitchBlocks,
+    /// Print the era, height and hash of the highest block.
+    Highest,
+}
+
+/// A short summary of a stored block.
+#[derive(Serialize)]
+struct BlockSummary {
+    era_id: EraId,
+    height: u64,
+    block_hash: BlockHash,
+    timestamp: Timestamp,
+}
+
+impl BlockSummary {
+    fn new(header: &BlockHeader, verifiable_chunked_hash_activation: EraId) -> Self {
+        BlockSummary {
+            era_id: header.era_id(),
+            height: header.height(),
+            block_hash: header.hash(verifiable_chunked_hash_activation),
+            timestamp: header.timestamp(),
+        }
+    }
+}
+
+/// A stored deploy and the block it was executed in.
+#[derive(Serialize)]
+struct DeployAndBlock {
+    deploy: Deploy,
+    block: Option<BlockHashAndHeight>,
+}
+
+/// Opens the storage referred to by the given config read-only and runs the query against it.
+///
+/// `root` is the directory against which relative paths in the config are resolved.
+pub(super) fn inspect_storage(
+    root: PathBuf,
+    config: participating::Config,
+    query: StorageQuery,
+) -> anyhow::Result<Value> {
+    let config = WithDir::new(root, config);
+    let chainspec_dir = match config.value().node.chainspec_dir {
+        Some(ref chainspec_dir) => config.with_dir(chainspec_dir.clone()),
+        None => config.dir().to_path_buf(),
+    };
+    let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(&chainspec_dir)
+        .context("could not load chainspec")?;
+    let verifiable_chunked_hash_activation =
+        chainspec.protocol_config.verifiable_chunked_hash_activation;
+
+    let storage = Storage::open_read_only(
+        &config.map_ref(|cfg| cfg.storage.clone()),
+        &chainspec.network_config.name,
+        chainspec.highway_config.finality_threshold_fraction,
+        chainspec.protocol_config.last_emergency_restart,
+        verifiable_chunked_hash_activation,
+    )
+    .context("could not open storage")?;
+
+    run_query(&storage, query, verifiable_chunked_hash_activation)
+}
+
+/// Runs the query against the given storage, returning its result as JSON.
+fn run_query(
+    storage: &Storage,
+    query: StorageQuery,
+    verifiable_chunked_hash_activation: EraId,
+) -> anyhow::Result<Value> {
+    let json = match query {
+        StorageQuery::Block { id } => {
+            let maybe_block = match id.parse::<u64>() {
+                Ok(height) => storage.read_block_by_height(height)?,
+                Err(_) => storage.read_block(&BlockHash::new(parse_digest(&id)?))?,
+            };
+            let block = match maybe_block {
+                Some(block) => block,
+                None => bail!("block {} not found", id),
+            };
+            let maybe_signatures = storage.read_finality_signatures(block.hash())?;
+            serde_json::to_value(JsonBlock::new(block, maybe_signatures))?
+        }
+        StorageQuery::Deploy { hash } => {
+            let deploy_hash = DeployHash::new(parse_digest(&hash)?);
+            let deploy = match storage.read_deploy_by_hash(deploy_hash)? {
+                Some(deploy) => deploy,
+                None => bail!("deploy {} not found", hash),
+            };
+            let block = storage.get_block_hash_and_height_by_deploy_hash(deploy_hash)?;
+            serde_json::to_value(DeployAndBlock { deploy, block })?
+        }
+        StorageQuery::SwitchBlocks => {
+            let summaries: Vec<_> = storage
+                .read_switch_block_headers()?
+                .iter()
+                .map(|header| BlockSummary::new(header, verifiable_chunked_hash_activation))
+                .collect();
+            serde_json::to_value(summaries)?
+        }
+        StorageQuery::Highest => match storage.read_highest_block_header()? {
+            Some(header) => serde_json::to_value(BlockSummary::new(
+                &header,
+                verifiable_chunked_hash_activation,
+            ))?,
+            None => bail!("storage contains no blocks"),
+        },
+    };
+    Ok(json)
+}
+
+fn parse_digest(hex: &str) -> anyhow::Result<Digest> {
+    Digest::from_hex(hex).with_context(|| format!("{} is not a valid hex-encoded hash", hex))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter;
+
+    use num_rational::Ratio;
+    use serde_json::json;
+
+    use casper_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{components::storage::Config as StorageConfig, types::Block};
+
+    const NETWORK_NAME: &str = "test";
+
+    #[test]
+    fn should_run_queries_against_read_only_storage() {
+        let mut rng = crate::new_rng();
+        let verifiable_chunked_hash_activation = EraId::from(0);
+        let (storage_config, temp_dir) = StorageConfig::default_for_tests();
+        let storage_config = WithDir::new(temp_dir.path(), storage_config);
+
+        let deploy = Deploy::random(&mut rng);
+        let switch_block = Block::random_with_specifics(
+            &mut rng,
+            EraId::from(0),
+            0,
+            ProtocolVersion::V1_0_0,
+            true,
+            verifiable_chunked_hash_activation,
+            None,
+        );
+        let block = Block::random_with_specifics(
+            &mut rng,
+            EraId::from(1),
+            1,
+            ProtocolVersion::V1_0_0,
+            false,
+            verifiable_chunked_hash_activation,
+            iter::once(&deploy),
+        );
+
+        // Populate the storage, then close it again as a stopped node would.
+        {
+            let mut storage = Storage::new(
+                &storage_config,
+                None,
+                ProtocolVersion::V1_0_0,
+                NETWORK_NAME,
+                Ratio::new(1, 3),
+                None,
+                verifiable_chunked_hash_activation,
+            )
+            .expect("should create storage");
+            storage.write_block(&switch_block).unwrap();
+            storage.write_block(&block).unwrap();
+            storage.put_deploy(&deploy).unwrap();
+        }
+
+        let storage = Storage::open_read_only(
+            &storage_config,
+            NETWORK_NAME,
+            Ratio::new(1, 3),
+            None,
+            verifiable_chunked_hash_activation,
+        )
+        .expect("should open storage read-only");
+        let run = |query| {
+            run_query(&storage, query, verifiable_chunked_hash_activation).expect("query failed")
+        };
+        let summary = |block: &Block| {
+            serde_json::to_value(BlockSummary::new(
+                block.header(),
+                verifiable_chunked_hash_activation,
+            ))
+            .unwrap()
+        };
+
+        let by_height = run(StorageQuery::Block {
+            id: "1".to_string(),
+        });
+        let by_hash = run(StorageQuery::Block {
+            id: format!("{:x}", block.hash().inner()),
+        });
+        assert_eq!(by_height["hash"], json!(block.hash()));
+        assert_eq!(by_height, by_hash);
+
+        let deploy_and_block = run(StorageQuery::Deploy {
+            hash: format!("{:x}", deploy.id().inner()),
+        });
+        assert_eq!(deploy_and_block["deploy"], json!(deploy));
+        assert_eq!(deploy_and_block["block"]["block_hash"], json!(block.hash()));
+
+        assert_eq!(
+            run(StorageQuery::SwitchBlocks),
+            json!([summary(&switch_block)])
+        );
+        assert_eq!(run(StorageQuery::Highest), summary(&block));
+
+        // Unknown items are reported as errors.
+        assert!(run_query(
+            &storage,
+            StorageQuery::Block {
+                id: "2".to_string()
+            },
+            verifiable_chunked_hash_activation
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,159 @@
+//! Built-in defaults for known networks.
+//!
+//! A preset is a partial config which forms the base layer of the node's configuration.  Values
+//! from the config file, environment and command line are merged on top of it, so any of them
+//! take precedence over the preset.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context};
+use toml::{value::Table, Value};
+
+use casper_types::ProtocolVersion;
+
+/// The directory under which the casper-node-launcher installs each version's config and
+/// chainspec.
+const LAUNCHER_CONFIG_ROOT: &str = "/etc/casper";
+
+/// A named set of config defaults.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Preset {
+    /// The Casper mainnet.
+    Mainnet,
+    /// The Casper testnet.
+    Testnet,
+    /// A local test network, as set up by the `run-dev` scripts.
+    Local,
+}
+
+impl Preset {
+    /// Returns the preset's config as a TOML table.
+    pub(super) fn config_table(self) -> anyhow::Result<Value> {
+        let encoded = match self {
+            Preset::Mainnet => include_str!("presets/mainnet.toml"),
+            Preset::Testnet => include_str!("presets/testnet.toml"),
+            Preset::Local => include_str!("presets/local.toml"),
+        };
+        let mut table: Value = toml::from_str(encoded)
+            .with_context(|| format!("failed to parse built-in {} preset", self))?;
+
+        if matches!(self, Preset::Mainnet | Preset::Testnet) {
+            let mut node = Table::new();
+            node.insert(
+                "chainspec_dir".to_string(),
+                Value::String(launcher_chainspec_dir()?),
+            );
+            let mut overrides = Table::new();
+            overrides.insert("node".to_string(), Value::Table(node));
+            merge(&mut table, Value::Table(overrides));
+        }
+        Ok(table)
+    }
+}
+
+impl Display for Preset {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Preset::Mainnet => write!(formatter, "mainnet"),
+            Preset::Testnet => write!(formatter, "testnet"),
+            Preset::Local => write!(formatter, "local"),
+        }
+    }
+}
+
+impl FromStr for Preset {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "mainnet" => Ok(Preset::Mainnet),
+            "testnet" => Ok(Preset::Testnet),
+            "local" => Ok(Preset::Local),
+            _ => Err(anyhow!(
+                "unknown preset '{}', expected one of mainnet, testnet or local",
+                input
+            )),
+        }
+    }
+}
+
+/// Returns the chainspec dir for this version of the node as installed by the
+/// casper-node-launcher, e.g. `/etc/casper/1_4_6`.
+fn launcher_chainspec_dir() -> anyhow::Result<String> {
+    let version = ProtocolVersion::from_str(env!("CARGO_PKG_VERSION"))
+        .map_err(|error| anyhow!("invalid node version: {}", error))?;
+    Ok(format!(
+        "{}/{}",
+        LAUNCHER_CONFIG_ROOT,
+        version.to_string().replace('.', "_")
+    ))
+}
+
+/// Recursively merges `overrides` into `base`.  Tables are merged key by key, while any other
+/// value in `overrides` replaces the corresponding one in `base`.
+pub(super) fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Table(base_table), Value::Table(override_table)) => {
+            for (key, override_value) in override_table {
+                match base_table.get_mut(&key) {
+                    Some(base_value) => merge(base_value, override_value),
+                    None => {
+                        base_table.insert(key, override_value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reactor::participating;
+
+    #[test]
+    fn should_parse_all_presets() {
+        for preset in &[Preset::Mainnet, Preset::Testnet, Preset::Local] {
+            assert_eq!(&Preset::from_str(&preset.to_string()).unwrap(), preset);
+            let table = preset.config_table().unwrap();
+            let _config: participating::Config = table
+                .try_into()
+                .unwrap_or_else(|error| panic!("{} preset should be valid: {}", preset, error));
+        }
+        assert!(Preset::from_str("devnet").is_err());
+    }
+
+    #[test]
+    fn should_require_all_sections_without_preset() {
+        let file: Value = toml::from_str(
+            r#"
+            [network]
+            bind_address = '0.0.0.0:1'
+            "#,
+        )
+        .unwrap();
+        assert!(file.try_into::<participating::Config>().is_err());
+    }
+
+    #[test]
+    fn should_prefer_explicit_values_over_preset() {
+        let mut table = Preset::Local.config_table().unwrap();
+        let file: Value = toml::from_str(
+            r#"
+            [network]
+            bind_address = '0.0.0.0:1'
+            "#,
+        )
+        .unwrap();
+        merge(&mut table, file);
+
+        let config: participating::Config = table.try_into().unwrap();
+        assert_eq!(config.network.bind_address, "0.0.0.0:1");
+        // Values not in the file are taken from the preset.
+        assert_eq!(config.network.known_addresses, vec!["127.0.0.1:34553"]);
+    }
+}
@@ -0,0 +1,542 @@
+//! Offline validation of the node config and chainspec.
+//!
+//! Used by the `validate-config` and `validate-chainspec` subcommands, which print a JSON report
+//! of all problems found rather than stopping at the first one.
+
+use std::{
+    collections::BTreeSet,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+use num::rational::Ratio;
+use serde::Serialize;
+
+use casper_types::{Motes, PublicKey, U512};
+
+use crate::{
+    reactor::participating,
+    types::{
+        chainspec::{ChainspecRawBytes, CHAINSPEC_FILENAME},
+        Chainspec, ExitCode,
+    },
+    utils::{External, Loadable},
+};
+
+/// How serious a finding is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum Severity {
+    /// The node will refuse to start, or will misbehave.
+    Error,
+    /// The value is allowed, but probably not what was intended.
+    Warning,
+}
+
+/// A single problem found during validation.
+#[derive(Debug, Serialize)]
+pub(super) struct Finding {
+    severity: Severity,
+    /// A stable identifier for the kind of check which failed.
+    check: &'static str,
+    message: String,
+}
+
+/// The outcome of validating a config and/or chainspec.
+#[derive(Debug, Default)]
+pub(super) struct Report {
+    findings: Vec<Finding>,
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    valid: bool,
+    findings: &'a [Finding],
+}
+
+impl Report {
+    fn error(&mut self, check: &'static str, message: String) {
+        self.findings.push(Finding {
+            severity: Severity::Error,
+            check,
+            message,
+        });
+    }
+
+    fn warning(&mut self, check: &'static str, message: String) {
+        self.findings.push(Finding {
+            severity: Severity::Warning,
+            check,
+            message,
+        });
+    }
+
+    fn error_count(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .count()
+    }
+
+    /// Returns `true` if no errors were found.  Warnings don't affect validity.
+    pub(super) fn is_valid(&self) -> bool {
+        self.error_count() == 0
+    }
+
+    /// Returns the report as a JSON string.
+    pub(super) fn to_json(&self) -> String {
+        let report = JsonReport {
+            valid: self.is_valid(),
+            findings: &self.findings,
+        };
+        serde_json::to_string_pretty(&report).expect("should serialize report")
+    }
+
+    /// Returns the exit code the process should use for this report.
+    pub(super) fn exit_code(&self) -> ExitCode {
+        if self.is_valid() {
+            ExitCode::Success
+        } else {
+            ExitCode::Abort
+        }
+    }
+
+    #[cfg(test)]
+    fn checks(&self, severity: Severity) -> Vec<&'static str> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.severity == severity)
+            .map(|finding| finding.check)
+            .collect()
+    }
+}
+
+/// Validates the given parsed config, along with the chainspec it refers to.
+///
+/// `root` is the directory against which relative paths in the config are resolved.
+pub(super) fn validate_config(
+    root: PathBuf,
+    maybe_config: anyhow::Result<participating::Config>,
+) -> Report {
+    let mut report = Report::default();
+    let config = match maybe_config {
+        Ok(config) => config,
+        Err(error) => {
+            report.error("config_parse", format!("{:#}", error));
+            return report;
+        }
+    };
+    check_ports(&config, &mut report);
+    check_paths(&config, &root, &mut report);
+
+    let chainspec_dir = match config.node.chainspec_dir {
+        Some(ref chainspec_dir) => resolve_path(&root, chainspec_dir),
+        None => root,
+    };
+    if !chainspec_dir.join(CHAINSPEC_FILENAME).exists() {
+        report.error(
+            "path_exists",
+            format!(
+                "{} does not exist",
+                chainspec_dir.join(CHAINSPEC_FILENAME).display()
+            ),
+        );
+        return report;
+    }
+    if let Some(chainspec) = load_chainspec(&chainspec_dir, &mut report) {
+        check_chainspec(&chainspec, &mut report);
+        check_config_against_chainspec(&config, &chainspec, &mut report);
+    }
+    report
+}
+
+/// Validates the chainspec at the given path, which may be the chainspec file itself or the
+/// directory containing it.
+pub(super) fn validate_chainspec(path: &Path) -> Report {
+    let mut report = Report::default();
+    let dir = if path.is_file() {
+        path.parent().unwrap_or_else(|| Path::new("/"))
+    } else {
+        path
+    };
+    if let Some(chainspec) = load_chainspec(dir, &mut report) {
+        check_chainspec(&chainspec, &mut report);
+    }
+    report
+}
+
+fn load_chainspec(dir: &Path, report: &mut Report) -> Option<Chainspec> {
+    match <(Chainspec, ChainspecRawBytes)>::from_path(dir) {
+        Ok((chainspec, _)) => Some(chainspec),
+        Err(error) => {
+            report.error("chainspec_parse", error.to_string());
+            None
+        }
+    }
+}
+
+/// Checks that no two enabled servers try to listen on the same port.
+fn check_ports(config: &participating::Config, report: &mut Report) {
+    let listeners = [
+        ("network.bind_address", &config.network.bind_address, true),
+        (
+            "rpc_server.address",
+            &config.rpc_server.address,
+            config.rpc_server.enable_server,
+        ),
+        (
+            "speculative_exec_server.address",
+            &config.speculative_exec_server.address,
+            config.speculative_exec_server.enable_server,
+        ),
+        (
+            "rest_server.address",
+            &config.rest_server.address,
+            config.rest_server.enable_server,
+        ),
+        (
+            "event_stream_server.address",
+            &config.event_stream_server.address,
+            config.event_stream_server.enable_server,
+        ),
+    ];
+
+    let mut bound: Vec<(&str, SocketAddr)> = vec![];
+    for (name, address, enabled) in listeners.iter().copied() {
+        if !enabled {
+            continue;
+        }
+        let address: SocketAddr = match address.parse() {
+            Ok(address) => address,
+            Err(error) => {
+                report.error(
+                    "invalid_address",
+                    format!("{} '{}' is not a valid address: {}", name, address, error),
+                );
+                continue;
+            }
+        };
+        // Port 0 picks a free port, so can't conflict.
+        if address.port() == 0 {
+            continue;
+        }
+        for (other_name, other_address) in &bound {
+            let overlapping_ips = address.ip() == other_address.ip()
+                || address.ip().is_unspecified()
+                || other_address.ip().is_unspecified();
+            if address.port() == other_address.port() && overlapping_ips {
+                report.error(
+                    "port_conflict",
+                    format!(
+                        "{} {} conflicts with {} {}",
+                        name, address, other_name, other_address
+                    ),
+                );
+            }
+        }
+        bound.push((name, address));
+    }
+}
+
+/// Checks that paths the node reads from or writes to at startup exist.
+fn check_paths(config: &participating::Config, root: &Path, report: &mut Report) {
+    match config.consensus.pkcs11 {
+        Some(ref pkcs11) => {
+            check_file_exists(
+                "consensus.pkcs11.module_path",
+                &resolve_path(root, &pkcs11.module_path),
+                report,
+            );
+            if let External::Path(ref path) = pkcs11.pin_path {
+                check_file_exists(
+                    "consensus.pkcs11.pin_path",
+                    &resolve_path(root, path),
+                    report,
+                );
+            }
+        }
+        None => {
+            if let External::Path(ref path) = config.consensus.secret_key_path {
+                check_file_exists(
+                    "consensus.secret_key_path",
+                    &resolve_path(root, path),
+                    report,
+                );
+            }
+        }
+    }
+
+    let storage_path = resolve_path(root, &config.storage.path);
+    if storage_path.exists() && !storage_path.is_dir() {
+        report.error(
+            "path_exists",
+            format!(
+                "storage.path {} exists but is not a directory",
+                storage_path.display()
+            ),
+        );
+    }
+
+    if let Some(log_file) = config.logging.file() {
+        let log_file = resolve_path(root, log_file);
+        let log_dir_exists = log_file
+            .parent()
+            .map_or(true, |dir| dir.as_os_str().is_empty() || dir.is_dir());
+        if !log_dir_exists {
+            report.error(
+                "path_exists",
+                format!(
+                    "logging.file {} is not in an existing directory",
+                    log_file.display()
+                ),
+            );
+        }
+    }
+}
+
+/// Reports an error if the file at `path`, given by the config option `option`, does not exist.
+fn check_file_exists(option: &str, path: &Path, report: &mut Report) {
+    if !path.is_file() {
+        report.error(
+            "path_exists",
+            format!("{} {} does not exist", option, path.display()),
+        );
+    }
+}
+
+/// Resolves `path` relative to `root` if it is not absolute.
+fn resolve_path(root: &Path, path: &Path) -> PathBuf {
+    if path.is_relative() {
+        root.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Checks the chainspec for values which are invalid or inconsistent with each other.
+fn check_chainspec(chainspec: &Chainspec, report: &mut Report) {
+    let errors_before = report.error_count();
+
+    let highway = &chainspec.highway_config;
+    if highway.minimum_round_exponent > highway.maximum_round_exponent {
+        report.error(
+            "round_exponents",
+            format!(
+                "highway.minimum_round_exponent {} is greater than \
+                highway.maximum_round_exponent {}",
+                highway.minimum_round_exponent, highway.maximum_round_exponent
+            ),
+        );
+    }
+    if highway.finality_threshold_fraction <= Ratio::new(0, 1)
+        || highway.finality_threshold_fraction >= Ratio::new(1, 1)
+    {
+        report.error(
+            "finality_threshold",
+            format!(
+                "highway.finality_threshold_fraction {} is not in the range (0, 1)",
+                highway.finality_threshold_fraction
+            ),
+        );
+    }
+
+    check_era_duration(chainspec, report);
+
+    if chainspec
+        .protocol_config
+        .activation_point
+        .genesis_timestamp()
+        .is_some()
+    {
+        check_genesis_weights(chainspec, report);
+    }
+
+    // Catch anything else the node itself would reject at startup.
+    if report.error_count() == errors_before && !chainspec.is_valid() {
+        report.error(
+            "chainspec_invalid",
+            "chainspec was rejected; start the node for detailed log output".to_string(),
+        );
+    }
+}
+
+/// Checks that eras are long enough to fit the configured rounds.
+fn check_era_duration(chainspec: &Chainspec, report: &mut Report) {
+    let core = &chainspec.core_config;
+    let highway = &chainspec.highway_config;
+    let era_duration_ms = core.era_duration.millis();
+    if era_duration_ms == 0 && core.minimum_era_height == 0 {
+        report.error(
+            "era_duration",
+            "core.era_duration and core.minimum_era_height are both zero, so eras never end"
+                .to_string(),
+        );
+        return;
+    }
+    if era_duration_ms == 0 {
+        return;
+    }
+
+    let min_round_ms = 1u64.checked_shl(highway.minimum_round_exponent.into());
+    let max_round_ms = 1u64.checked_shl(highway.maximum_round_exponent.into());
+    match min_round_ms.and_then(|ms| ms.checked_mul(core.minimum_era_height)) {
+        Some(min_era_ms) if era_duration_ms >= min_era_ms => (),
+        _ => report.warning(
+            "era_duration",
+            format!(
+                "core.era_duration {} is shorter than core.minimum_era_height {} rounds at \
+                highway.minimum_round_exponent {}, so eras will be longer than configured",
+                core.era_duration, core.minimum_era_height, highway.minimum_round_exponent
+            ),
+        ),
+    }
+    match max_round_ms {
+        Some(max_round_ms) if era_duration_ms >= max_round_ms => (),
+        _ => report.warning(
+            "era_duration",
+            format!(
+                "core.era_duration {} is shorter than a single round at \
+                highway.maximum_round_exponent {}",
+                core.era_duration, highway.maximum_round_exponent
+            ),
+        ),
+    }
+}
+
+/// Checks the genesis validators' and delegators' stakes.
+fn check_genesis_weights(chainspec: &Chainspec, report: &mut Report) {
+    let accounts_config = &chainspec.network_config.accounts_config;
+    let validators: BTreeSet<PublicKey> = accounts_config
+        .accounts()
+        .iter()
+        .filter(|account| account.is_genesis_validator())
+        .map(|account| account.public_key())
+        .collect();
+
+    if validators.is_empty() {
+        report.error(
+            "validator_weights",
+            "no genesis validators with a non-zero bonded amount".to_string(),
+        );
+    }
+    let validator_slots = chainspec.core_config.validator_slots as usize;
+    if validators.len() > validator_slots {
+        report.error(
+            "validator_weights",
+            format!(
+                "{} genesis validators exceed core.validator_slots {}",
+                validators.len(),
+                validator_slots
+            ),
+        );
+    }
+
+    for delegator in accounts_config.delegators() {
+        if !validators.contains(delegator.validator_public_key()) {
+            report.error(
+                "validator_weights",
+                format!(
+                    "delegator {} delegates to {}, which is not a genesis validator",
+                    delegator.delegator_public_key(),
+                    delegator.validator_public_key()
+                ),
+            );
+        }
+    }
+
+    let total_weight = accounts_config
+        .accounts()
+        .iter()
+        .map(|account| account.bonded_amount())
+        .chain(
+            accounts_config
+                .delegators()
+                .iter()
+                .map(|delegator| delegator.delegated_amount()),
+        )
+        .try_fold(Motes::new(U512::zero()), |total, amount| {
+            total.checked_add(amount)
+        });
+    if total_weight.is_none() {
+        report.error(
+            "validator_weights",
+            "total of bonded and delegated amounts overflows".to_string(),
+        );
+    }
+}
+
+/// Checks config values which depend on the chainspec.
+fn check_config_against_chainspec(
+    config: &participating::Config,
+    chainspec: &Chainspec,
+    report: &mut Report,
+) {
+    let min_round_length = chainspec.highway_config.min_round_length();
+    let step_soft_deadline = config.contract_runtime.step_soft_deadline(min_round_length);
+    if step_soft_deadline > min_round_length {
+        report.warning(
+            "step_soft_deadline",
+            format!(
+                "contract_runtime.step_soft_deadline {} is longer than the minimum round length \
+                {}",
+                step_soft_deadline, min_round_length
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::RESOURCES_PATH;
+
+    #[test]
+    fn should_accept_local_chainspec() {
+        let report = validate_chainspec(&RESOURCES_PATH.join("local"));
+        assert!(report.is_valid(), "{}", report.to_json());
+    }
+
+    #[test]
+    fn should_report_port_conflicts() {
+        let mut config = participating::Config::default();
+        config.network.bind_address = "0.0.0.0:34553".to_string();
+        config.rpc_server.enable_server = true;
+        config.rpc_server.address = "127.0.0.1:34553".to_string();
+        config.rest_server.enable_server = true;
+        config.rest_server.address = "127.0.0.1:0".to_string();
+        config.event_stream_server.enable_server = true;
+        config.event_stream_server.address = "not an address".to_string();
+        config.speculative_exec_server.enable_server = false;
+
+        let mut report = Report::default();
+        check_ports(&config, &mut report);
+        assert_eq!(
+            report.checks(Severity::Error),
+            vec!["port_conflict", "invalid_address"]
+        );
+    }
+
+    #[test]
+    fn should_report_inconsistent_chainspec_values() {
+        let (mut chainspec, _) =
+            <(Chainspec, ChainspecRawBytes)>::from_path(RESOURCES_PATH.join("local"))
+                .expect("should load local chainspec");
+        chainspec.highway_config.minimum_round_exponent = 20;
+        chainspec.highway_config.maximum_round_exponent = 19;
+        chainspec.core_config.era_duration = 1000.into();
+        chainspec.core_config.validator_slots = 0;
+
+        let mut report = Report::default();
+        check_chainspec(&chainspec, &mut report);
+        assert_eq!(
+            report.checks(Severity::Error),
+            vec!["round_exponents", "validator_weights"]
+        );
+        assert_eq!(
+            report.checks(Severity::Warning),
+            vec!["era_duration", "era_duration"]
+        );
+        assert!(!report.is_valid());
+        assert_eq!(report.exit_code(), ExitCode::Abort);
+    }
+}
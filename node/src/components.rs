@@ -71,6 +71,7 @@ pub(crate) use crate::testing::fake_deploy_acceptor;
 
 use crate::{
     effect::{EffectBuilder, Effects},
+    types::ComponentHealth,
     NodeRng,
 };
 
@@ -116,3 +117,12 @@ pub(crate) trait Component<REv> {
         event: Self::Event,
     ) -> Effects<Self::Event>;
 }
+
+/// A component which can report on its own health.
+///
+/// Health checks are run by the reactor, both for the node's status and readiness endpoints and
+/// for the health metrics, so they must be cheap and must not block.
+pub(crate) trait HealthCheck {
+    /// Returns the component's current health.
+    fn health(&self) -> ComponentHealth;
+}
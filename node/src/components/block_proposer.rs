@@ -32,6 +32,7 @@ use crate::{
     },
     effect::{
         announcements::BlockProposerAnnouncement,
+        diagnostics_port::ComponentMemory,
         requests::{BlockPayloadRequest, BlockProposerRequest, StateStoreRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
     },
@@ -136,6 +137,24 @@ impl BlockProposer {
 
         Ok((block_proposer, effects))
     }
+
+    /// Breaks down the component's estimated heap usage by its major collections.
+    pub(crate) fn report_memory(&self, memory: &mut ComponentMemory) {
+        match &self.state {
+            BlockProposerState::Initializing { pending, .. } => {
+                memory.add_collection("pending_events", pending);
+            }
+            BlockProposerState::Ready(ready) => {
+                memory
+                    .add_collection("pending_deploys", &ready.sets.pending_deploys)
+                    .add_collection("pending_transfers", &ready.sets.pending_transfers)
+                    .add_collection("finalized_deploys", &ready.sets.finalized_deploys)
+                    .add_collection("finalized_transfers", &ready.sets.finalized_transfers)
+                    .add_collection("finalization_queue", &ready.sets.finalization_queue)
+                    .add_collection("request_queue", &ready.request_queue);
+            }
+        }
+    }
 }
 
 impl<REv> Component<REv> for BlockProposer
@@ -519,8 +538,9 @@ impl BlockProposerReady {
                     // We added the maximum number of deploys.
                     AddError::DeployCount => break,
                     AddError::BlockSize => {
-                        if appendable_block.total_size() + DEPLOY_APPROX_MIN_SIZE
-                            > deploy_config.block_gas_limit as usize
+                        if appendable_block
+                            .remaining_size()
+                            .map_or(false, |remaining| remaining < DEPLOY_APPROX_MIN_SIZE)
                         {
                             break; // Probably no deploy will fit in this block anymore.
                         }
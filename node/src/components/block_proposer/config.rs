@@ -6,7 +6,7 @@ use casper_types::TimeDiff;
 /// Block proposer configuration.
 #[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Deploys are only proposed in a new block if they have been received at least this long ago.
     /// A longer delay makes it more likely that many proposed deploys are already known by the
@@ -558,6 +558,20 @@ fn should_allow_transfers_to_exceed_block_size_limit() {
     });
 }
 
+#[test]
+fn should_treat_zero_block_size_limit_as_unlimited() {
+    test_proposer_with(TestArgs {
+        deploy_count: 3,
+        payment_amount: default_gas_payment(),
+        block_gas_limit: 100,
+        max_deploy_count: 3,
+        proposed_count: 3,
+        remaining_pending_count: 0,
+        max_block_size: Some(0),
+        ..Default::default()
+    });
+}
+
 #[derive(Default)]
 struct TestArgs {
     /// Number of deploys to create.
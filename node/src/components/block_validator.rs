@@ -38,8 +38,10 @@ use crate::{
         EffectBuilder, EffectExt, Effects, Responder,
     },
     types::{
-        appendable_block::AppendableBlock, Approval, Block, Chainspec, Deploy, DeployHash,
-        DeployOrTransferHash, DeployWithApprovals, NodeId,
+        appendable_block::{AddError, AppendableBlock},
+        chainspec::DeployConfig,
+        Approval, Block, Chainspec, Deploy, DeployHash, DeployOrTransferHash, DeployWithApprovals,
+        NodeId,
     },
     NodeRng,
 };
@@ -89,6 +91,33 @@ impl ValidatingBlock {
         }
     }
 
+    /// Checks the numbers of deploys, transfers and approvals against the chainspec limits.
+    ///
+    /// This doesn't need any of the deploys themselves, so oversized blocks can be rejected before
+    /// fetching anything.  The block size and gas limits are checked once the deploys are known.
+    fn check_counts(&self, deploy_config: &DeployConfig) -> Result<(), AddError> {
+        if self.deploy_hashes().count() > deploy_config.block_max_deploy_count as usize {
+            return Err(AddError::DeployCount);
+        }
+        if self.transfer_hashes().count() > deploy_config.block_max_transfer_count as usize {
+            return Err(AddError::TransferCount);
+        }
+        // Approvals are only known for proposed blocks.
+        if let ValidatingBlock::ProposedBlock(pb) = self {
+            let approval_count: usize = pb
+                .value()
+                .deploys()
+                .iter()
+                .chain(pb.value().transfers())
+                .map(|dwa| dwa.approvals().len())
+                .sum();
+            if approval_count > deploy_config.block_max_approval_count as usize {
+                return Err(AddError::ApprovalCount);
+            }
+        }
+        Ok(())
+    }
+
     fn deploys_and_transfers_iter(
         &self,
     ) -> Box<dyn Iterator<Item = (DeployOrTransferHash, Option<BTreeSet<Approval>>)> + '_> {
@@ -264,6 +293,13 @@ where
                     self.log_block_with_replay(sender, &block);
                     return responder.respond(false).ignore();
                 }
+                if let Err(error) = block.check_counts(&self.chainspec.deploy_config) {
+                    info!(
+                        peer_id=?sender, %error, %block,
+                        "received invalid block exceeding the chainspec limits"
+                    );
+                    return responder.respond(false).ignore();
+                }
 
                 match self.validation_states.entry(block) {
                     Entry::Occupied(mut entry) => {
@@ -315,6 +351,16 @@ where
                 // mark it for removal.
                 let mut invalid = Vec::new();
 
+                // A deploy violating the chainspec's pricing rules can't be included in any block.
+                let payment_amount = match dt_hash {
+                    DeployOrTransferHash::Deploy(_) => Some(deploy_info.payment_amount.value()),
+                    DeployOrTransferHash::Transfer(_) => None,
+                };
+                let pricing_result = self
+                    .chainspec
+                    .pricing_config
+                    .check(deploy_info.header.gas_price(), payment_amount);
+
                 // Our first pass updates all validation states, crossing off the found deploy.
                 for (key, state) in self.validation_states.iter_mut() {
                     if let Some(maybe_approvals) = state.missing_deploys.remove(&dt_hash) {
@@ -340,6 +386,9 @@ where
                         if let Err(err) = add_result {
                             info!(block = ?key, %dt_hash, ?deploy_info, ?err, "block invalid");
                             invalid.push(key.clone());
+                        } else if let Err(err) = &pricing_result {
+                            info!(block = ?key, %dt_hash, ?deploy_info, %err, "block invalid");
+                            invalid.push(key.clone());
                         }
                     }
                 }
@@ -45,7 +45,7 @@ impl MockReactor {
     }
 
     async fn expect_block_validator_event(&self) -> Event {
-        let ((_ancestor, reactor_event), _) = self.scheduler.pop().await;
+        let ((_origin, reactor_event), _) = self.scheduler.pop().await;
         if let ReactorEvent::BlockValidator(event) = reactor_event {
             event
         } else {
@@ -57,7 +57,7 @@ impl MockReactor {
     where
         T: Into<Option<Deploy>>,
     {
-        let ((_ancestor, reactor_event), _) = self.scheduler.pop().await;
+        let ((_origin, reactor_event), _) = self.scheduler.pop().await;
         if let ReactorEvent::Fetcher(FetcherRequest {
             id,
             peer,
@@ -150,12 +150,26 @@ fn new_transfer(rng: &mut TestRng, timestamp: Timestamp, ttl: TimeDiff) -> Deplo
     )
 }
 
-/// Validates a block using a `BlockValidator` component, and returns the result.
+/// Validates a block using a `BlockValidator` component with the local chainspec, and returns the
+/// result.
 async fn validate_block(
     rng: &mut TestRng,
     timestamp: Timestamp,
     deploys: Vec<Deploy>,
     transfers: Vec<Deploy>,
+) -> bool {
+    let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+    validate_block_with_chainspec(rng, chainspec, timestamp, deploys, transfers).await
+}
+
+/// Validates a block using a `BlockValidator` component with the given chainspec, and returns the
+/// result.
+async fn validate_block_with_chainspec(
+    rng: &mut TestRng,
+    chainspec: Chainspec,
+    timestamp: Timestamp,
+    deploys: Vec<Deploy>,
+    transfers: Vec<Deploy>,
 ) -> bool {
     // Assemble the block to be validated.
     let deploys_for_block = deploys.iter().map(DeployWithApprovals::from).collect_vec();
@@ -168,7 +182,6 @@ async fn validate_block(
     // Create the reactor and component.
     let reactor = MockReactor::new();
     let effect_builder = EffectBuilder::new(EventQueueHandle::without_shutdown(reactor.scheduler));
-    let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
     let mut block_validator = BlockValidator::new(Arc::new(chainspec));
 
     // Pass the block to the component. This future will eventually resolve to the result, i.e.
@@ -289,3 +302,74 @@ async fn transfer_deploy_mixup_and_replay() {
     let transfers = vec![transfer1.clone(), transfer2.clone(), transfer2.clone()];
     assert!(!validate_block(&mut rng, timestamp, deploys, transfers).await);
 }
+
+/// Verifies that the block validator enforces the chainspec's deploy, transfer and approval count
+/// limits: a block exactly at each limit is valid, one more makes it invalid.
+#[tokio::test]
+async fn count_limits() {
+    let mut rng = TestRng::new();
+    let ttl = TimeDiff::from(200);
+    let timestamp = Timestamp::from(1000);
+    let deploys = (0..3)
+        .map(|_| new_deploy(&mut rng, timestamp, ttl))
+        .collect_vec();
+    let transfers = (0..3)
+        .map(|_| new_transfer(&mut rng, timestamp, ttl))
+        .collect_vec();
+
+    let limited_chainspec = |max_approval_count| {
+        let (mut chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        chainspec.deploy_config.block_max_deploy_count = 2;
+        chainspec.deploy_config.block_max_transfer_count = 2;
+        chainspec.deploy_config.block_max_approval_count = max_approval_count;
+        chainspec
+    };
+
+    // Two deploys and two transfers, with one approval each, are exactly at the limits.
+    assert!(
+        validate_block_with_chainspec(
+            &mut rng,
+            limited_chainspec(4),
+            timestamp,
+            deploys[..2].to_vec(),
+            transfers[..2].to_vec(),
+        )
+        .await
+    );
+
+    // One deploy too many.
+    assert!(
+        !validate_block_with_chainspec(
+            &mut rng,
+            limited_chainspec(4),
+            timestamp,
+            deploys.clone(),
+            vec![],
+        )
+        .await
+    );
+
+    // One transfer too many.
+    assert!(
+        !validate_block_with_chainspec(
+            &mut rng,
+            limited_chainspec(4),
+            timestamp,
+            vec![],
+            transfers.clone(),
+        )
+        .await
+    );
+
+    // One approval too many.
+    assert!(
+        !validate_block_with_chainspec(
+            &mut rng,
+            limited_chainspec(3),
+            timestamp,
+            deploys[..2].to_vec(),
+            transfers[..2].to_vec(),
+        )
+        .await
+    );
+}
@@ -1,3 +1,4 @@
+mod checkpoint;
 mod config;
 mod error;
 mod event;
@@ -5,11 +6,14 @@ mod metrics;
 mod operations;
 mod progress;
 
-use std::{collections::HashSet, convert::Infallible, fmt::Debug, marker::PhantomData, sync::Arc};
+use std::{
+    collections::HashSet, convert::Infallible, fmt::Debug, marker::PhantomData, sync::Arc,
+    time::Duration,
+};
 
 use datasize::DataSize;
 use prometheus::Registry;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use casper_execution_engine::{
     core::engine_state::{self, genesis::GenesisSuccess, UpgradeSuccess},
@@ -29,7 +33,8 @@ use crate::{
         },
         requests::{
             ChainspecLoaderRequest, ContractRuntimeRequest, FetcherRequest,
-            MarkBlockCompletedRequest, NetworkInfoRequest, NodeStateRequest,
+            MarkBlockCompletedRequest, NetworkInfoRequest, NodeStateRequest, StateStoreRequest,
+            SyncStatisticsRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
@@ -48,8 +53,11 @@ pub(crate) use event::Event;
 pub(crate) use metrics::Metrics;
 use operations::FastSyncOutcome;
 pub(crate) use operations::KeyBlockInfo;
-pub(crate) use progress::Progress;
 use progress::ProgressHolder;
+pub(crate) use progress::{Progress, SyncStatistics};
+
+/// The interval between reports of the progress of syncing blocks.
+const SYNC_PROGRESS_REPORT_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(DataSize, Debug)]
 pub(crate) enum JoiningOutcome {
@@ -83,6 +91,8 @@ pub(crate) struct ChainSynchronizer<REv> {
     progress: ProgressHolder,
     /// The current state of operation of the node.
     node_state: NodeState,
+    /// Whether we are currently catching up with the network in the background.
+    catching_up: bool,
     /// Association with the reactor event used in subtasks.
     _phantom: PhantomData<REv>,
 }
@@ -104,6 +114,7 @@ where
         + From<BlocklistAnnouncement>
         + From<ControlAnnouncement>
         + From<MarkBlockCompletedRequest>
+        + From<StateStoreRequest>
         + Send,
 {
     /// Constructs a new `ChainSynchronizer` suitable for use in the joiner reactor to perform the
@@ -120,13 +131,14 @@ where
         let progress = ProgressHolder::new_fast_sync();
         let node_state = NodeState::Joining(progress.progress());
 
-        let effects = operations::run_fast_sync_task(
+        let mut effects = operations::run_fast_sync_task(
             effect_builder,
             config.clone(),
             metrics.clone(),
             progress.clone(),
         )
         .event(Event::FastSyncResult);
+        effects.extend(schedule_sync_progress_report(effect_builder));
 
         let synchronizer = ChainSynchronizer {
             config,
@@ -134,6 +146,7 @@ where
             metrics,
             progress,
             node_state,
+            catching_up: false,
             _phantom: PhantomData,
         };
 
@@ -523,6 +536,35 @@ where
             }
         }
     }
+
+    /// Starts catching up with the network in the background, unless we are already doing so.
+    fn handle_catch_up(&mut self, effect_builder: EffectBuilder<REv>) -> Effects<Event> {
+        if self.catching_up {
+            debug!("already catching up");
+            return Effects::new();
+        }
+        self.catching_up = true;
+        operations::run_catch_up_task(
+            effect_builder,
+            self.config.clone(),
+            self.metrics.clone(),
+            self.progress.clone(),
+        )
+        .event(Event::CatchUpResult)
+    }
+
+    fn handle_catch_up_result(&mut self, result: Result<BlockHeader, Error>) -> Effects<Event> {
+        self.catching_up = false;
+        match result {
+            Ok(_) => Effects::new(),
+            // Consensus will ask us again if we're still behind once the network enters the next
+            // era.
+            Err(error) => {
+                warn!(%error, "failed to catch up");
+                Effects::new()
+            }
+        }
+    }
 }
 
 impl<REv> ChainSynchronizer<REv>
@@ -562,16 +604,18 @@ where
                 metrics,
                 progress: progress.clone(),
                 node_state,
+                catching_up: false,
                 _phantom: PhantomData,
             };
 
-            let effects = operations::run_sync_to_genesis_task(
+            let mut effects = operations::run_sync_to_genesis_task(
                 effect_builder,
                 synchronizer.config.clone(),
                 synchronizer.metrics.clone(),
                 progress,
             )
             .ignore();
+            effects.extend(schedule_sync_progress_report(effect_builder));
 
             return Ok((synchronizer, effects));
         }
@@ -585,6 +629,7 @@ where
             metrics,
             progress,
             node_state: NodeState::Participating,
+            catching_up: false,
             _phantom: PhantomData,
         };
 
@@ -612,6 +657,41 @@ impl<REv> ChainSynchronizer<REv> {
 
         request.0.respond(self.node_state.clone()).ignore()
     }
+
+    fn handle_get_sync_statistics_request(&self, request: SyncStatisticsRequest) -> Effects<Event> {
+        request.0.respond(self.progress.sync_statistics()).ignore()
+    }
+
+    /// Updates the sync progress metrics and logs the progress, rescheduling the next report
+    /// unless syncing has finished.
+    fn handle_report_sync_progress(&self, effect_builder: EffectBuilder<REv>) -> Effects<Event>
+    where
+        REv: Send,
+    {
+        if self.progress.progress().is_finished() {
+            return Effects::new();
+        }
+        if let Some(statistics) = self.progress.sync_statistics() {
+            self.metrics.update_sync_statistics(&statistics);
+            info!(
+                starting_height = statistics.starting_height,
+                current_height = statistics.current_height,
+                estimated_tip_height = ?statistics.estimated_tip_height,
+                blocks_per_second = statistics.blocks_per_second,
+                eta = %statistics
+                    .eta
+                    .map_or_else(|| "unknown".to_string(), |eta| eta.to_string()),
+                "sync progress"
+            );
+        }
+        schedule_sync_progress_report(effect_builder)
+    }
+}
+
+fn schedule_sync_progress_report<REv: Send>(effect_builder: EffectBuilder<REv>) -> Effects<Event> {
+    effect_builder
+        .set_timeout(SYNC_PROGRESS_REPORT_INTERVAL)
+        .event(|_| Event::ReportSyncProgress)
 }
 
 impl<REv> Component<REv> for ChainSynchronizer<REv>
@@ -632,6 +712,7 @@ where
         + From<BlocklistAnnouncement>
         + From<ControlAnnouncement>
         + From<MarkBlockCompletedRequest>
+        + From<StateStoreRequest>
         + Send,
 {
     type Event = Event;
@@ -680,6 +761,10 @@ where
                 result,
             ),
             Event::GetNodeState(request) => self.handle_get_node_state_request(request),
+            Event::GetSyncStatistics(request) => self.handle_get_sync_statistics_request(request),
+            Event::ReportSyncProgress => self.handle_report_sync_progress(effect_builder),
+            Event::CatchUp => self.handle_catch_up(effect_builder),
+            Event::CatchUpResult(result) => self.handle_catch_up_result(result),
         }
     }
 }
@@ -0,0 +1,219 @@
+use serde::{Deserialize, Serialize};
+
+use super::KeyBlockInfo;
+use crate::{
+    effect::{requests::StateStoreRequest, EffectBuilder},
+    types::BlockHash,
+};
+
+/// The key under which the checkpoint is persisted in the state store.
+const STATE_KEY: &[u8] = b"chain synchronizer checkpoint";
+
+/// A checkpoint of the fast-sync task, persisted while executing blocks so that if the node
+/// restarts mid-sync, it can resume executing from the last executed block rather than fetching
+/// and validating everything from the trusted hash again.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(super) struct SyncCheckpoint {
+    /// The hash of the trusted block the sync started from.
+    pub(super) trusted_hash: BlockHash,
+    /// The hash of the highest block which is stored and whose global state is complete, either
+    /// as a result of executing it or of syncing its trie store.
+    pub(super) highest_executed_block_hash: BlockHash,
+    /// The key block info for the era of the block following the highest executed one.
+    pub(super) key_block_info: KeyBlockInfo,
+    /// The height of the highest block downloaded and stored ahead of execution.  Blocks above the
+    /// highest executed one up to this height are read from storage rather than fetched again
+    /// when resuming.
+    pub(super) highest_downloaded_height: u64,
+}
+
+impl SyncCheckpoint {
+    /// Returns whether the checkpoint applies to a sync from the trusted block with the given hash
+    /// and height.
+    ///
+    /// Without a configured trusted hash, the highest block in storage is used as the trust anchor,
+    /// which may be one of the blocks downloaded ahead of execution before the restart.
+    pub(super) fn is_applicable(
+        &self,
+        trusted_hash: BlockHash,
+        is_trusted_hash_configured: bool,
+        trusted_block_height: u64,
+    ) -> bool {
+        self.trusted_hash == trusted_hash
+            || (!is_trusted_hash_configured
+                && trusted_block_height <= self.highest_downloaded_height)
+    }
+
+    /// Loads the persisted checkpoint, if any.
+    pub(super) async fn load<REv>(effect_builder: EffectBuilder<REv>) -> Option<Self>
+    where
+        REv: From<StateStoreRequest>,
+    {
+        effect_builder
+            .load_state::<Option<SyncCheckpoint>>(STATE_KEY.into())
+            .await
+            .flatten()
+    }
+
+    /// Persists the checkpoint, replacing any previous one.
+    pub(super) async fn save<REv>(self, effect_builder: EffectBuilder<REv>)
+    where
+        REv: From<StateStoreRequest>,
+    {
+        effect_builder
+            .save_state(STATE_KEY.into(), Some(self))
+            .await;
+    }
+
+    /// Removes the persisted checkpoint, once syncing has finished.
+    pub(super) async fn clear<REv>(effect_builder: EffectBuilder<REv>)
+    where
+        REv: From<StateStoreRequest>,
+    {
+        effect_builder
+            .save_state(STATE_KEY.into(), None::<SyncCheckpoint>)
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use num_rational::Ratio;
+
+    use casper_types::{EraId, ProtocolVersion};
+
+    use super::*;
+    use crate::{
+        components::storage::{self, Config as StorageConfig, Storage},
+        reactor::{EventQueueHandle, QueueKind, Scheduler},
+        testing::{ComponentHarness, UnitTestEvent},
+        types::Block,
+        utils::{self, WithDir},
+    };
+
+    fn random_checkpoint(harness: &mut ComponentHarness<UnitTestEvent>) -> SyncCheckpoint {
+        let verifiable_chunked_hash_activation = EraId::from(0);
+        let switch_block = Block::random_with_specifics(
+            &mut harness.rng,
+            EraId::from(2),
+            20,
+            ProtocolVersion::V1_0_0,
+            true,
+            verifiable_chunked_hash_activation,
+            None,
+        );
+        SyncCheckpoint {
+            trusted_hash: BlockHash::random(&mut harness.rng),
+            highest_executed_block_hash: *switch_block.hash(),
+            key_block_info: KeyBlockInfo::maybe_from_block_header(
+                switch_block.header(),
+                verifiable_chunked_hash_activation,
+            )
+            .expect("switch block should have key block info"),
+            highest_downloaded_height: 25,
+        }
+    }
+
+    fn open_storage(storage_config: &WithDir<StorageConfig>) -> Storage {
+        Storage::new(
+            storage_config,
+            None,
+            ProtocolVersion::V1_0_0,
+            "test",
+            Ratio::new(1, 3),
+            None,
+            EraId::from(0),
+        )
+        .expect("should create storage")
+    }
+
+    /// Runs `operation` on `harness`' runtime, serving the single state store request it makes
+    /// from `storage`.
+    fn run_against_storage<T, F>(
+        harness: &mut ComponentHarness<UnitTestEvent>,
+        storage: &mut Storage,
+        operation: impl FnOnce(EffectBuilder<StateStoreRequest>) -> F,
+    ) -> T
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let scheduler = utils::leak(Scheduler::<StateStoreRequest>::new(QueueKind::weights()));
+        let effect_builder = EffectBuilder::new(EventQueueHandle::without_shutdown(scheduler));
+        let join_handle = harness.runtime.spawn(operation(effect_builder));
+
+        let ((_origin, request), _queue_kind) = harness.runtime.block_on(scheduler.pop());
+        for effect in harness.send_event(storage, storage::Event::StateStoreRequest(request)) {
+            harness.runtime.block_on(effect);
+        }
+        harness
+            .runtime
+            .block_on(join_handle)
+            .expect("operation should not panic")
+    }
+
+    #[test]
+    fn should_only_apply_to_the_same_sync() {
+        let mut harness = ComponentHarness::<UnitTestEvent>::default();
+        let checkpoint = random_checkpoint(&mut harness);
+        let other_hash = BlockHash::random(&mut harness.rng);
+
+        // The sync from the same trusted block is resumed, whether or not its hash is configured.
+        assert!(checkpoint.is_applicable(checkpoint.trusted_hash, true, 10));
+        assert!(checkpoint.is_applicable(checkpoint.trusted_hash, false, 10));
+
+        // A different configured trusted hash starts a new sync.
+        assert!(!checkpoint.is_applicable(other_hash, true, 10));
+        assert!(!checkpoint.is_applicable(other_hash, true, 25));
+
+        // Without a configured trusted hash, the highest stored block is the trust anchor, which
+        // may be one of the blocks downloaded ahead of execution, but no higher one.
+        assert!(checkpoint.is_applicable(other_hash, false, 25));
+        assert!(!checkpoint.is_applicable(other_hash, false, 26));
+    }
+
+    #[test]
+    fn should_persist_checkpoint_across_restarts() {
+        let mut harness = ComponentHarness::<UnitTestEvent>::default();
+        let (storage_config, temp_dir) = StorageConfig::default_for_tests();
+        let storage_config = WithDir::new(temp_dir.path(), storage_config);
+        let checkpoint = random_checkpoint(&mut harness);
+
+        {
+            let mut storage = open_storage(&storage_config);
+            let loaded = run_against_storage(&mut harness, &mut storage, SyncCheckpoint::load);
+            assert!(loaded.is_none());
+
+            let to_save = checkpoint.clone();
+            run_against_storage(&mut harness, &mut storage, move |effect_builder| {
+                to_save.save(effect_builder)
+            });
+        }
+
+        // After restarting, the checkpoint is still there and resumes the sync, even if the
+        // highest block downloaded before the restart has become the trust anchor.
+        let mut storage = open_storage(&storage_config);
+        let loaded = run_against_storage(&mut harness, &mut storage, SyncCheckpoint::load)
+            .expect("should load checkpoint");
+        assert_eq!(loaded.trusted_hash, checkpoint.trusted_hash);
+        assert_eq!(
+            loaded.highest_executed_block_hash,
+            checkpoint.highest_executed_block_hash
+        );
+        assert_eq!(
+            loaded.key_block_info.block_hash(),
+            checkpoint.key_block_info.block_hash()
+        );
+        assert_eq!(loaded.key_block_info.era_id(), EraId::from(3));
+        assert_eq!(loaded.highest_downloaded_height, 25);
+        let highest_downloaded_hash = BlockHash::random(&mut harness.rng);
+        assert!(loaded.is_applicable(highest_downloaded_hash, false, 25));
+
+        // Once syncing has finished, the checkpoint is gone.
+        run_against_storage(&mut harness, &mut storage, SyncCheckpoint::clear);
+        let loaded = run_against_storage(&mut harness, &mut storage, SyncCheckpoint::load);
+        assert!(loaded.is_none());
+    }
+}
@@ -55,7 +55,7 @@ impl Config {
             max_parallel_trie_fetches: node_config.max_parallel_trie_fetches,
             max_parallel_block_fetches: node_config.max_parallel_block_fetches,
             retry_interval: Duration::from_millis(node_config.retry_interval.millis()),
-            sync_to_genesis: node_config.sync_to_genesis,
+            sync_to_genesis: node_config.sync_mode.is_archival(),
             max_retries_while_not_connected,
             redemption_interval: node_config.sync_peer_redemption_interval,
             minimum_peer_count_threshold_for_fetch_retry: small_network_config
@@ -188,6 +188,7 @@ impl Config {
             Some(self.chainspec.core_config.unbonding_delay),
             global_state_update,
             chainspec_registry,
+            self.chainspec.protocol_config.state_migrations.clone(),
         );
         Ok(Box::new(upgrade_config))
     }
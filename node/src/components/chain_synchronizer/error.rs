@@ -9,7 +9,7 @@ use casper_execution_engine::{
     storage::trie::TrieOrChunk,
 };
 use casper_hashing::Digest;
-use casper_types::{EraId, ProtocolVersion};
+use casper_types::{crypto, EraId, ProtocolVersion};
 
 use crate::{
     components::{
@@ -195,6 +195,10 @@ pub(crate) enum FetchTrieError {
          by a peer somehow. Trie digest: {digest:?}"
     )]
     TrieBeingFetchedByChunksSomehowFetchWholeFromPeer { digest: Digest },
+
+    /// The tries downloaded for a trie key repeatedly didn't hash to that key.
+    #[error("downloaded trie did not match its key {trie_key} in {attempts} attempts")]
+    TrieMismatch { trie_key: Digest, attempts: u64 },
 }
 
 #[derive(Error, Debug)]
@@ -206,3 +210,37 @@ pub(crate) enum FetchBlockHeadersBatchError {
     #[error("Batch from storage was empty")]
     EmptyBatchFromStorage,
 }
+
+/// Reasons for a block received from a peer to fail verification against the finality signatures
+/// of its era's validators.
+#[derive(Error, Debug)]
+pub(crate) enum BlockVerificationError {
+    /// The block is not in the era of the validator set it was verified against.
+    #[error("block is in era {block_era_id}, but was expected in era {validators_era_id}")]
+    UnexpectedEraId {
+        block_era_id: EraId,
+        validators_era_id: EraId,
+    },
+
+    /// The finality signatures are for a different block.
+    #[error("finality signatures are for block {signatures_block_hash}, not {block_hash}")]
+    SignaturesForOtherBlock {
+        block_hash: BlockHash,
+        signatures_block_hash: BlockHash,
+    },
+
+    /// The finality signatures are for a different era than the block.
+    #[error("finality signatures are for era {signatures_era_id}, not {block_era_id}")]
+    SignaturesForOtherEra {
+        block_era_id: EraId,
+        signatures_era_id: EraId,
+    },
+
+    /// The finality signatures are not from the era's validators, or insufficient.
+    #[error(transparent)]
+    FinalitySignature(#[from] FinalitySignatureError),
+
+    /// A finality signature is cryptographically invalid.
+    #[error("invalid finality signature: {0}")]
+    InvalidSignature(#[from] crypto::Error),
+}
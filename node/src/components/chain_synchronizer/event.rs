@@ -12,7 +12,7 @@ use casper_types::PublicKey;
 use super::{Error, FastSyncOutcome};
 use crate::{
     contract_runtime::{BlockAndExecutionEffects, BlockExecutionError},
-    effect::requests::NodeStateRequest,
+    effect::requests::{NodeStateRequest, SyncStatisticsRequest},
     types::BlockHeader,
 };
 
@@ -47,6 +47,16 @@ pub(crate) enum Event {
     /// A request to provide the node state.
     #[from]
     GetNodeState(NodeStateRequest),
+    /// A request to provide statistics about the blocks synced so far.
+    #[from]
+    GetSyncStatistics(SyncStatisticsRequest),
+    /// Time to report the progress of syncing blocks.
+    ReportSyncProgress,
+    /// Consensus noticed that the node has fallen behind the network, so we should catch up in the
+    /// background.
+    CatchUp,
+    /// The result of running the background catch-up task.
+    CatchUpResult(Result<BlockHeader, Error>),
 }
 
 impl Display for Event {
@@ -79,6 +89,10 @@ impl Display for Event {
                 )
             }
             Event::GetNodeState(_) => write!(formatter, "get node state"),
+            Event::GetSyncStatistics(_) => write!(formatter, "get sync statistics"),
+            Event::ReportSyncProgress => write!(formatter, "report sync progress"),
+            Event::CatchUp => write!(formatter, "catch up"),
+            Event::CatchUpResult(result) => write!(formatter, "catch up result: {:?}", result),
         }
     }
 }
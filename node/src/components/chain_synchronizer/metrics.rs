@@ -1,8 +1,9 @@
 use datasize::DataSize;
-use prometheus::{self, Histogram, IntCounter, IntGauge, Registry};
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
 
 use casper_types::Timestamp;
 
+use super::SyncStatistics;
 use crate::utils;
 
 /// Bucket parameters for the `chain_sync_sync_trie_store_duration_seconds` and
@@ -67,6 +68,21 @@ pub(crate) struct Metrics {
     /// Integer representing number of blocks that we've successfully downloaded.
     #[data_size(skip)]
     pub(super) chain_sync_blocks_synced: IntCounter,
+    /// Height of the highest block already synced when syncing blocks started.
+    #[data_size(skip)]
+    pub(super) chain_sync_starting_height: IntGauge,
+    /// Height of the highest block synced so far.
+    #[data_size(skip)]
+    pub(super) chain_sync_current_height: IntGauge,
+    /// Estimated height of the tip of the chain, or -1 if unknown.
+    #[data_size(skip)]
+    pub(super) chain_sync_estimated_tip_height: IntGauge,
+    /// Average number of blocks synced per second.
+    #[data_size(skip)]
+    pub(super) chain_sync_blocks_per_second: Gauge,
+    /// Estimated time in seconds until the tip of the chain is reached, or -1 if unknown.
+    #[data_size(skip)]
+    pub(super) chain_sync_eta_seconds: IntGauge,
     /// Registry stored to allow deregistration later.
     #[data_size(skip)]
     registry: Registry,
@@ -125,6 +141,26 @@ impl Metrics {
             "chain_sync_blocks_synced",
             "Number of full blocks we've synchronized.",
         )?;
+        let chain_sync_starting_height = IntGauge::new(
+            "chain_sync_starting_height",
+            "height of the highest block already synced when syncing blocks started",
+        )?;
+        let chain_sync_current_height = IntGauge::new(
+            "chain_sync_current_height",
+            "height of the highest block synced so far",
+        )?;
+        let chain_sync_estimated_tip_height = IntGauge::new(
+            "chain_sync_estimated_tip_height",
+            "estimated height of the tip of the chain, or -1 if unknown",
+        )?;
+        let chain_sync_blocks_per_second = Gauge::new(
+            "chain_sync_blocks_per_second",
+            "average number of blocks synced per second",
+        )?;
+        let chain_sync_eta_seconds = IntGauge::new(
+            "chain_sync_eta_seconds",
+            "estimated time in seconds until the tip of the chain is reached, or -1 if unknown",
+        )?;
 
         let buckets = prometheus::exponential_buckets(
             SYNC_TRIE_OR_DEPLOY_BUCKET_START,
@@ -156,6 +192,11 @@ impl Metrics {
             chain_sync_era_supervisor_init_duration_seconds.clone(),
         ))?;
         registry.register(Box::new(chain_sync_blocks_synced.clone()))?;
+        registry.register(Box::new(chain_sync_starting_height.clone()))?;
+        registry.register(Box::new(chain_sync_current_height.clone()))?;
+        registry.register(Box::new(chain_sync_estimated_tip_height.clone()))?;
+        registry.register(Box::new(chain_sync_blocks_per_second.clone()))?;
+        registry.register(Box::new(chain_sync_eta_seconds.clone()))?;
         registry.register(Box::new(chain_sync_execute_blocks_duration_seconds.clone()))?;
         registry.register(Box::new(
             chain_sync_fetch_and_store_initial_trusted_block_header_duration_seconds.clone(),
@@ -199,6 +240,11 @@ impl Metrics {
                     buckets,
                 )?,
             chain_sync_blocks_synced,
+            chain_sync_starting_height,
+            chain_sync_current_height,
+            chain_sync_estimated_tip_height,
+            chain_sync_blocks_per_second,
+            chain_sync_eta_seconds,
             registry: registry.clone(),
         })
     }
@@ -222,6 +268,25 @@ impl Metrics {
         self.chain_sync_fetch_finality_signatures_duration_seconds
             .observe(start.elapsed().millis() as f64 / 1000.0);
     }
+
+    pub(super) fn update_sync_statistics(&self, statistics: &SyncStatistics) {
+        self.chain_sync_starting_height
+            .set(statistics.starting_height as i64);
+        self.chain_sync_current_height
+            .set(statistics.current_height as i64);
+        self.chain_sync_estimated_tip_height.set(
+            statistics
+                .estimated_tip_height
+                .map_or(-1, |height| height as i64),
+        );
+        self.chain_sync_blocks_per_second
+            .set(statistics.blocks_per_second);
+        self.chain_sync_eta_seconds.set(
+            statistics
+                .eta
+                .map_or(-1, |eta| (eta.millis() / 1000) as i64),
+        );
+    }
 }
 
 impl Drop for Metrics {
@@ -1,7 +1,7 @@
 use std::{
     cmp,
-    collections::{BTreeMap, VecDeque},
-    mem,
+    collections::{BTreeMap, HashSet, VecDeque},
+    iter, mem,
     sync::{
         atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
         Arc, RwLock,
@@ -11,24 +11,27 @@ use std::{
 use async_trait::async_trait;
 use datasize::DataSize;
 use futures::{
-    stream::{futures_unordered::FuturesUnordered, StreamExt},
-    TryStreamExt,
+    stream::{self, futures_unordered::FuturesUnordered, StreamExt},
+    Stream, TryStreamExt,
 };
 use num::rational::Ratio;
 use prometheus::IntGauge;
 use quanta::Instant;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tokio::sync::Semaphore;
 use tracing::{debug, error, info, trace, warn};
 
-use casper_execution_engine::storage::trie::{TrieOrChunk, TrieOrChunkId};
+use casper_execution_engine::storage::trie::{
+    hash_bytes_into_chunks_if_necessary, TrieOrChunk, TrieOrChunkId,
+};
 use casper_hashing::Digest;
 use casper_types::{bytesrepr::Bytes, EraId, PublicKey, TimeDiff, Timestamp, U512};
 
 use crate::{
     components::{
         chain_synchronizer::{
-            error::{Error, FetchBlockHeadersBatchError, FetchTrieError},
+            checkpoint::SyncCheckpoint,
+            error::{BlockVerificationError, Error, FetchBlockHeadersBatchError, FetchTrieError},
             Config, Metrics, ProgressHolder,
         },
         consensus::{self, error::FinalitySignatureError},
@@ -39,6 +42,7 @@ use crate::{
         announcements::{BlocklistAnnouncement, ChainSynchronizerAnnouncement},
         requests::{
             ContractRuntimeRequest, FetcherRequest, MarkBlockCompletedRequest, NetworkInfoRequest,
+            StateStoreRequest,
         },
         EffectBuilder,
     },
@@ -110,6 +114,9 @@ where
     /// A range of blocks for which we already have all required data stored locally.
     locally_available_block_range_on_start: AvailableBlockRange,
     trie_fetch_limit: Semaphore,
+    /// Whether to only fetch from peers not known to be running in recent sync mode, i.e. peers
+    /// which may hold history older than their trusted block.
+    archival_peers_only: bool,
 }
 
 impl<'a, REv> ChainSyncContext<'a, REv>
@@ -142,6 +149,7 @@ where
             filter_count: AtomicI64::new(0),
             locally_available_block_range_on_start,
             trie_fetch_limit: Semaphore::new(config.max_parallel_trie_fetches()),
+            archival_peers_only: false,
         };
 
         let trusted_block_header = match config.trusted_hash() {
@@ -193,6 +201,7 @@ where
             filter_count: AtomicI64::new(0),
             locally_available_block_range_on_start,
             trie_fetch_limit: Semaphore::new(config.max_parallel_trie_fetches()),
+            archival_peers_only: true,
         };
 
         let trusted_block_header = match effect_builder
@@ -242,6 +251,24 @@ impl<'a, REv> ChainSyncContext<'a, REv> {
             .is_last_block_before_activation(self.trusted_block_header())
     }
 
+    /// Removes peers known to be running in recent sync mode from a given peer list, if this
+    /// context only fetches from archival peers.
+    async fn filter_non_archival_peers(&self, peers: &mut Vec<NodeId>)
+    where
+        REv: From<NetworkInfoRequest>,
+    {
+        if !self.archival_peers_only {
+            return;
+        }
+        let archival_peers: HashSet<NodeId> = self
+            .effect_builder
+            .get_fully_connected_archival_peers()
+            .await
+            .into_iter()
+            .collect();
+        peers.retain(|peer| archival_peers.contains(peer));
+    }
+
     /// Removes known bad peers from a given peer list.
     ///
     /// Automatically redeems the oldest bad peer after `redemption_interval` filterings.
@@ -316,6 +343,10 @@ impl<'a, REv> ChainSyncContext<'a, REv> {
 /// Restrict the fan-out for a trie being retrieved by chunks to query at most 10 peers at a time.
 const TRIE_CHUNK_FETCH_FAN_OUT: usize = 10;
 
+/// The number of times the download of a trie is retried if the downloaded trie doesn't match its
+/// key.
+const MAX_TRIE_FETCH_RETRIES: u64 = 3;
+
 /// Allows us to decide whether syncing peers can also be used when calling `fetch_retry_forever`.
 trait CanUseSyncingNodes {
     fn can_use_syncing_nodes() -> bool {
@@ -351,6 +382,7 @@ where
         .get_fully_connected_non_syncing_peers()
         .await;
     ctx.filter_bad_peers(&mut peer_list);
+    ctx.filter_non_archival_peers(&mut peer_list).await;
     peer_list
 }
 
@@ -361,6 +393,7 @@ where
 {
     let mut peer_list = ctx.effect_builder.get_fully_connected_peers().await;
     ctx.filter_bad_peers(&mut peer_list);
+    ctx.filter_non_archival_peers(&mut peer_list).await;
     peer_list
 }
 
@@ -438,7 +471,11 @@ where
 
 enum TrieAlreadyPresentOrDownloaded {
     AlreadyPresent,
-    Downloaded(Bytes),
+    /// The trie was downloaded from the given peers, one for each chunk if it was chunked.
+    Downloaded {
+        trie_bytes: Bytes,
+        peers: HashSet<NodeId>,
+    },
 }
 
 async fn fetch_trie_retry_forever<REv>(
@@ -448,18 +485,23 @@ async fn fetch_trie_retry_forever<REv>(
 where
     REv: From<FetcherRequest<TrieOrChunk>> + From<NetworkInfoRequest>,
 {
-    let trie_or_chunk =
+    let (trie_or_chunk, first_peer) =
         match fetch_retry_forever::<_, TrieOrChunk>(ctx, TrieOrChunkId(0, id)).await? {
             FetchedData::FromStorage { .. } => {
                 return Ok(TrieAlreadyPresentOrDownloaded::AlreadyPresent)
             }
             FetchedData::FromPeer {
                 item: trie_or_chunk,
-                ..
-            } => *trie_or_chunk,
+                peer,
+            } => (*trie_or_chunk, peer),
         };
     let chunk_with_proof = match trie_or_chunk {
-        TrieOrChunk::Trie(trie) => return Ok(TrieAlreadyPresentOrDownloaded::Downloaded(trie)),
+        TrieOrChunk::Trie(trie_bytes) => {
+            return Ok(TrieAlreadyPresentOrDownloaded::Downloaded {
+                trie_bytes,
+                peers: iter::once(first_peer).collect(),
+            })
+        }
         TrieOrChunk::ChunkWithProof(chunk_with_proof) => chunk_with_proof,
     };
 
@@ -479,7 +521,7 @@ where
                 FetchedData::FromStorage { .. } => {
                     Err(FetchTrieError::TrieBeingFetchByChunksSomehowFetchedFromStorage)
                 }
-                FetchedData::FromPeer { item, .. } => match *item {
+                FetchedData::FromPeer { item, peer } => match *item {
                     TrieOrChunk::Trie(_) => Err(
                         FetchTrieError::TrieBeingFetchedByChunksSomehowFetchWholeFromPeer {
                             digest: id,
@@ -488,7 +530,7 @@ where
                     TrieOrChunk::ChunkWithProof(chunk_with_proof) => {
                         let index = chunk_with_proof.proof().index();
                         let chunk = chunk_with_proof.into_chunk();
-                        Ok((index, chunk))
+                        Ok((index, (chunk, peer)))
                     }
                 },
             }
@@ -498,7 +540,7 @@ where
         // Doing `buffer_unordered` followed by `try_collect` here means if one of the
         // fetches fails, then the outstanding futures are canceled.
         .buffer_unordered(TRIE_CHUNK_FETCH_FAN_OUT)
-        .try_collect::<BTreeMap<u64, Bytes>>()
+        .try_collect::<BTreeMap<u64, (Bytes, NodeId)>>()
         .await;
 
     // Handle if a parallel process downloaded all the trie chunks before us.
@@ -512,11 +554,15 @@ where
             return Err(error);
         }
     };
-    chunk_map.insert(0, first_chunk);
+    chunk_map.insert(0, (first_chunk, first_peer));
 
     // Concatenate all of the chunks into a trie
-    let trie_bytes = chunk_map.into_values().flat_map(Vec::<u8>::from).collect();
-    Ok(TrieAlreadyPresentOrDownloaded::Downloaded(trie_bytes))
+    let peers = chunk_map.values().map(|(_, peer)| *peer).collect();
+    let trie_bytes = chunk_map
+        .into_values()
+        .flat_map(|(chunk, _)| Vec::<u8>::from(chunk))
+        .collect();
+    Ok(TrieAlreadyPresentOrDownloaded::Downloaded { trie_bytes, peers })
 }
 
 /// Fetches and stores a block header from the network.
@@ -623,7 +669,7 @@ where
 ///
 /// If the data was scraped from genesis, then `era_id` is 0.
 /// Otherwise if it came from a switch block it is that switch block's `era_id + 1`.
-#[derive(DataSize, Clone, Serialize, Debug)]
+#[derive(DataSize, Clone, Serialize, Deserialize, Debug)]
 pub(crate) struct KeyBlockInfo {
     /// The block hash of the key block
     key_block_hash: BlockHash,
@@ -841,29 +887,12 @@ where
                     continue;
                 }
 
-                if key_block_info.era_id() != item.header().era_id() {
-                    error!(
-                        key_block_info_era_id = key_block_info.era_id().value(),
-                        item_header_era_id = item.header().era_id().value(),
-                        "mismatch between key block era id and item header era id"
-                    );
-                }
-
-                if let Err(error) = consensus::check_sufficient_finality_signatures(
-                    key_block_info.validator_weights(),
-                    ctx.config.finality_threshold_fraction(),
-                    Some(item.finality_signatures()),
-                ) {
-                    warn!(?error, ?peer, "insufficient finality signatures from peer");
-                    ctx.effect_builder.announce_disconnect_from_peer(peer).await;
-                    continue;
-                }
-
-                if let Err(error) = item.finality_signatures().verify() {
+                if let Err(error) = verify_block_with_metadata(&*item, key_block_info, ctx) {
                     warn!(
-                        ?error,
+                        %error,
                         ?peer,
-                        "error validating finality signatures from peer"
+                        height,
+                        "block from peer failed finality signature verification, banning peer"
                     );
                     ctx.effect_builder.announce_disconnect_from_peer(peer).await;
                     continue;
@@ -891,6 +920,64 @@ where
     })
 }
 
+/// Verifies a block or block header received from a peer against the finality signatures of the
+/// validators of its era, given by `key_block_info`.
+fn verify_block_with_metadata<REv, I>(
+    item: &I,
+    key_block_info: &KeyBlockInfo,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Result<(), BlockVerificationError>
+where
+    I: BlockOrHeaderWithMetadata,
+{
+    if key_block_info.era_id() != item.header().era_id() {
+        return Err(BlockVerificationError::UnexpectedEraId {
+            block_era_id: item.header().era_id(),
+            validators_era_id: key_block_info.era_id(),
+        });
+    }
+    verify_finality_signatures(
+        item.header(),
+        item.finality_signatures(),
+        key_block_info.validator_weights(),
+        ctx.config.verifiable_chunked_hash_activation(),
+    )?;
+    consensus::check_sufficient_finality_signatures(
+        key_block_info.validator_weights(),
+        ctx.config.finality_threshold_fraction(),
+        Some(item.finality_signatures()),
+    )?;
+    Ok(())
+}
+
+/// Verifies that `signatures` are valid finality signatures for the block with the given header,
+/// all from validators in `validator_weights`.
+///
+/// This does _not_ check that the signatures' total weight is sufficient for finality.
+fn verify_finality_signatures(
+    block_header: &BlockHeader,
+    signatures: &BlockSignatures,
+    validator_weights: &BTreeMap<PublicKey, U512>,
+    verifiable_chunked_hash_activation: EraId,
+) -> Result<(), BlockVerificationError> {
+    let block_hash = block_header.hash(verifiable_chunked_hash_activation);
+    if signatures.block_hash != block_hash {
+        return Err(BlockVerificationError::SignaturesForOtherBlock {
+            block_hash,
+            signatures_block_hash: signatures.block_hash,
+        });
+    }
+    if signatures.era_id != block_header.era_id() {
+        return Err(BlockVerificationError::SignaturesForOtherEra {
+            block_era_id: block_header.era_id(),
+            signatures_era_id: signatures.era_id,
+        });
+    }
+    consensus::validate_finality_signatures(signatures, validator_weights)?;
+    signatures.verify()?;
+    Ok(())
+}
+
 /// Prepares a list of peers applicable for the next fetch operation.
 async fn prepare_peers_applicable_for_block_fetch<REv>(
     ctx: &ChainSyncContext<'_, REv>,
@@ -914,25 +1001,55 @@ where
 
 /// Queries all of the peers for a trie, puts the trie found from the network in the trie-store, and
 /// returns any outstanding descendant tries.
+///
+/// If the downloaded trie doesn't match `trie_key`, the peers it was downloaded from are
+/// disconnected and the download is retried, up to `MAX_TRIE_FETCH_RETRIES` times.
 async fn fetch_and_store_trie<REv>(
     trie_key: Digest,
     ctx: &ChainSyncContext<'_, REv>,
 ) -> Result<Vec<Digest>, Error>
 where
-    REv:
-        From<FetcherRequest<TrieOrChunk>> + From<NetworkInfoRequest> + From<ContractRuntimeRequest>,
+    REv: From<FetcherRequest<TrieOrChunk>>
+        + From<NetworkInfoRequest>
+        + From<ContractRuntimeRequest>
+        + From<BlocklistAnnouncement>,
 {
-    let fetched_trie = fetch_trie_retry_forever(trie_key, ctx).await?;
-    match fetched_trie {
-        TrieAlreadyPresentOrDownloaded::AlreadyPresent => Ok(ctx
-            .effect_builder
-            .find_missing_descendant_trie_keys(trie_key)
-            .await?),
-        TrieAlreadyPresentOrDownloaded::Downloaded(trie_bytes) => Ok(ctx
-            .effect_builder
-            .put_trie_and_find_missing_descendant_trie_keys(trie_bytes)
-            .await?),
+    let max_attempts = MAX_TRIE_FETCH_RETRIES + 1;
+    for attempt in 1..=max_attempts {
+        let (trie_bytes, peers) = match fetch_trie_retry_forever(trie_key, ctx).await? {
+            TrieAlreadyPresentOrDownloaded::AlreadyPresent => {
+                return Ok(ctx
+                    .effect_builder
+                    .find_missing_descendant_trie_keys(trie_key)
+                    .await?)
+            }
+            TrieAlreadyPresentOrDownloaded::Downloaded { trie_bytes, peers } => (trie_bytes, peers),
+        };
+        // The individual chunks have been verified already, but make sure the reassembled trie is
+        // really the one referenced by its parent before storing it.
+        let downloaded_trie_key = hash_bytes_into_chunks_if_necessary(&trie_bytes);
+        if downloaded_trie_key == trie_key {
+            return Ok(ctx
+                .effect_builder
+                .put_trie_and_find_missing_descendant_trie_keys(trie_bytes)
+                .await?);
+        }
+        warn!(
+            %trie_key,
+            %downloaded_trie_key,
+            ?peers,
+            attempt,
+            "downloaded trie does not match requested key, disconnecting from peers"
+        );
+        for peer in peers {
+            ctx.effect_builder.announce_disconnect_from_peer(peer).await;
+        }
     }
+    Err(FetchTrieError::TrieMismatch {
+        trie_key,
+        attempts: max_attempts,
+    }
+    .into())
 }
 
 /// Downloads and stores a block.
@@ -992,8 +1109,10 @@ async fn sync_trie_store_worker<REv>(
     ctx: &ChainSyncContext<'_, REv>,
 ) -> Result<(), Error>
 where
-    REv:
-        From<FetcherRequest<TrieOrChunk>> + From<NetworkInfoRequest> + From<ContractRuntimeRequest>,
+    REv: From<FetcherRequest<TrieOrChunk>>
+        + From<NetworkInfoRequest>
+        + From<ContractRuntimeRequest>
+        + From<BlocklistAnnouncement>,
 {
     while let Some(job) = queue.next_job().await {
         let permit = match ctx.trie_fetch_limit.acquire().await {
@@ -1034,8 +1153,10 @@ async fn sync_trie_store<REv>(
     ctx: &ChainSyncContext<'_, REv>,
 ) -> Result<(), Error>
 where
-    REv:
-        From<FetcherRequest<TrieOrChunk>> + From<NetworkInfoRequest> + From<ContractRuntimeRequest>,
+    REv: From<FetcherRequest<TrieOrChunk>>
+        + From<NetworkInfoRequest>
+        + From<ContractRuntimeRequest>
+        + From<BlocklistAnnouncement>,
 {
     let block_height = block_header.height();
     debug_assert!(ctx.progress.is_fetching_tries(block_height));
@@ -1076,12 +1197,52 @@ where
         return Ok(());
     }
 
+    let mut trie_keys_to_fetch = vec![state_root_hash];
+    loop {
+        fetch_tries(block_height, trie_keys_to_fetch, ctx).await?;
+
+        // Verify the complete global state under the target state root is now held locally,
+        // fetching anything still missing.
+        trie_keys_to_fetch = ctx
+            .effect_builder
+            .find_missing_descendant_trie_keys(state_root_hash)
+            .await?;
+        if trie_keys_to_fetch.is_empty() {
+            break;
+        }
+        warn!(
+            %state_root_hash,
+            missing_trie_count = trie_keys_to_fetch.len(),
+            "trie store incomplete after syncing, fetching missing tries"
+        );
+    }
+
+    ctx.metrics
+        .observe_sync_trie_store_duration_seconds(start_instant);
+    Ok(())
+}
+
+/// Downloads the tries under the given keys and all of their missing descendants.
+async fn fetch_tries<REv>(
+    block_height: u64,
+    trie_keys: Vec<Digest>,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Result<(), Error>
+where
+    REv: From<FetcherRequest<TrieOrChunk>>
+        + From<NetworkInfoRequest>
+        + From<ContractRuntimeRequest>
+        + From<BlocklistAnnouncement>,
+{
     // Flag set by a worker when it encounters an error.
     let abort = Arc::new(AtomicBool::new(false));
 
     let queue = Arc::new(WorkQueue::default());
-    queue.push_job(state_root_hash);
-    ctx.progress.set_num_tries_to_fetch(block_height, 1);
+    for trie_key in trie_keys {
+        queue.push_job(trie_key);
+    }
+    ctx.progress
+        .set_num_tries_to_fetch(block_height, queue.num_jobs());
 
     let mut workers: FuturesUnordered<_> = (0..ctx.config.max_parallel_trie_fetches())
         .map(|worker_id| {
@@ -1091,9 +1252,6 @@ where
     while let Some(result) = workers.next().await {
         result?; // Return the error if a download failed.
     }
-
-    ctx.metrics
-        .observe_sync_trie_store_duration_seconds(start_instant);
     Ok(())
 }
 
@@ -1497,6 +1655,11 @@ where
     // choose to "re-sync" from Genesis, even if it means we will go through thousands of blocks
     // that we already have. Hopefully, local checks will be fast enough.
     let latest_height_requested: Arc<AtomicU64> = Arc::new(AtomicU64::new(0));
+    ctx.progress.start_syncing_blocks(
+        0,
+        Timestamp::zero(),
+        Some(ctx.trusted_block_header().height()),
+    );
 
     let mut workers: FuturesUnordered<_> = (0..ctx.config.max_parallel_block_fetches())
         .map(|worker_id| fetch_block_worker(worker_id, latest_height_requested.clone(), ctx))
@@ -1570,6 +1733,8 @@ where
                 }
                 ctx.effect_builder.mark_block_completed(block_height).await;
                 ctx.metrics.chain_sync_blocks_synced.inc();
+                ctx.progress
+                    .block_synced(block_height, block_header.timestamp());
             }
             Err(err) => {
                 // We're using `fetch_retry_forever` internally so we should never get
@@ -1672,10 +1837,15 @@ impl BlockSignaturesCollector {
         let (era_for_validators_retrieval, validator_weights) =
             era_validator_weights_for_block(block_header, ctx).await?;
 
-        if let Err(err) = consensus::validate_finality_signatures(&signatures, &validator_weights) {
+        if let Err(error) = verify_finality_signatures(
+            block_header,
+            &signatures,
+            &validator_weights,
+            ctx.config.verifiable_chunked_hash_activation(),
+        ) {
             warn!(
                 ?peer,
-                ?err,
+                %error,
                 height = block_header.height(),
                 "peer sent invalid finality signatures, banning peer"
             );
@@ -1924,6 +2094,7 @@ where
         + From<FetcherRequest<TrieOrChunk>>
         + From<BlocklistAnnouncement>
         + From<MarkBlockCompletedRequest>
+        + From<StateStoreRequest>
         + Send,
 {
     info!("fast syncing chain");
@@ -1957,16 +2128,34 @@ where
         return Ok(outcome);
     }
 
-    let (highest_synced_block_header, highest_synced_key_block_info) = fast_sync(&ctx).await?;
+    let (highest_synced_block_header, checkpoint) = match resume_from_checkpoint(&ctx).await {
+        Some(resumed) => resumed,
+        None => {
+            let (highest_synced_block_header, highest_synced_key_block_info) =
+                fast_sync(&ctx).await?;
+            let checkpoint = SyncCheckpoint {
+                trusted_hash: ctx.trusted_hash(),
+                highest_executed_block_hash: highest_synced_block_header
+                    .hash(config.verifiable_chunked_hash_activation()),
+                key_block_info: highest_synced_key_block_info,
+                highest_downloaded_height: highest_synced_block_header.height(),
+            };
+            checkpoint.clone().save(effect_builder).await;
+            (highest_synced_block_header, checkpoint)
+        }
+    };
 
     // Iterate forwards, fetching each full block and deploys but executing each block to generate
     // global state. Stop once we get to a block in the current era.
     let highest_synced_block_header = fetch_and_execute_blocks(
         &highest_synced_block_header,
-        highest_synced_key_block_info,
+        checkpoint.key_block_info,
+        checkpoint.trusted_hash,
+        checkpoint.highest_downloaded_height,
         &ctx,
     )
     .await?;
+    SyncCheckpoint::clear(effect_builder).await;
 
     // If we just committed an emergency upgrade and are re-syncing right after this, potentially
     // the call to `fast_sync` and `execute_blocks` could yield a `highest_synced_block_header`
@@ -1994,6 +2183,54 @@ where
     })
 }
 
+/// Returns the persisted checkpoint along with the header of its highest executed block.
+///
+/// Returns `None` if there is no checkpoint, or if it doesn't apply to the current trusted block,
+/// in which case syncing needs to start from the trusted block.
+async fn resume_from_checkpoint<REv>(
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Option<(BlockHeader, SyncCheckpoint)>
+where
+    REv: From<StateStoreRequest> + From<StorageRequest>,
+{
+    let checkpoint = SyncCheckpoint::load(*ctx.effect_builder).await?;
+
+    if !checkpoint.is_applicable(
+        ctx.trusted_hash(),
+        ctx.config.trusted_hash().is_some(),
+        ctx.trusted_block_header().height(),
+    ) {
+        info!(
+            checkpoint_trusted_hash = %checkpoint.trusted_hash,
+            trusted_hash = %ctx.trusted_hash(),
+            "chain sync checkpoint doesn't apply to trusted block; ignoring it"
+        );
+        return None;
+    }
+
+    let highest_executed_block_header = match ctx
+        .effect_builder
+        .get_block_header_from_storage(checkpoint.highest_executed_block_hash, false)
+        .await
+    {
+        Some(block_header) => block_header,
+        None => {
+            warn!(
+                block_hash = %checkpoint.highest_executed_block_hash,
+                "block of chain sync checkpoint missing from storage; ignoring checkpoint"
+            );
+            return None;
+        }
+    };
+
+    info!(
+        highest_executed_height = highest_executed_block_header.height(),
+        highest_downloaded_height = checkpoint.highest_downloaded_height,
+        "resuming chain sync from checkpoint"
+    );
+    Some((highest_executed_block_header, checkpoint))
+}
+
 async fn fetch_and_store_initial_trusted_block_header<REv>(
     ctx: &ChainSyncContext<'_, REv>,
     metrics: &Metrics,
@@ -2054,7 +2291,8 @@ where
     REv: From<FetcherRequest<TrieOrChunk>>
         + From<NetworkInfoRequest>
         + From<ContractRuntimeRequest>
-        + From<StorageRequest>,
+        + From<StorageRequest>
+        + From<BlocklistAnnouncement>,
 {
     let emergency_restart_era = match ctx.config.last_emergency_restart() {
         Some(era_id) if era_id == ctx.config.activation_point() => era_id,
@@ -2188,15 +2426,214 @@ where
         .await?)
 }
 
+/// Executes the block with the given deploys and transfers, and checks that the result matches
+/// the block.
+///
+/// The deploys may carry different approvals than the ones that were finalized in the block, in
+/// which case the block's hash won't match.  We then fetch the finalized approvals from one peer
+/// after the other and re-execute, disconnecting from every peer whose approvals don't yield a
+/// matching block.  On success, `deploys` and `transfers` hold the finalized approvals.
+async fn execute_and_verify_block<REv>(
+    block: &Block,
+    deploys: &mut [Deploy],
+    transfers: &mut [Deploy],
+    execution_pre_state: &ExecutionPreState,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Result<BlockAndExecutionEffects, Error>
+where
+    REv: From<FetcherRequest<FinalizedApprovalsWithId>>
+        + From<ContractRuntimeRequest>
+        + From<NetworkInfoRequest>
+        + From<BlocklistAnnouncement>,
+{
+    let block_and_execution_effects = ctx
+        .effect_builder
+        .execute_finalized_block(
+            block.protocol_version(),
+            execution_pre_state.clone(),
+            FinalizedBlock::from(block.clone()),
+            deploys.to_vec(),
+            transfers.to_vec(),
+        )
+        .await?;
+    if *block == *block_and_execution_effects.block() {
+        return Ok(block_and_execution_effects);
+    }
+
+    let mut attempts = 0;
+    loop {
+        // Could be wrong approvals - fetch new sets of approvals from a single peer and retry.
+        for peer in get_filtered_fully_connected_peers(ctx).await {
+            warn!(
+                block_hash=%block.hash(),
+                "retrying execution due to deploy approvals mismatch"
+            );
+            attempts += 1;
+            ctx.progress.retry_executing_block(block.height(), attempts);
+            let block_and_execution_effects = retry_execution_with_approvals_from_peer(
+                deploys,
+                transfers,
+                peer,
+                block,
+                execution_pre_state,
+                ctx,
+            )
+            .await?;
+            debug!(block_hash=%block.hash(), "finish - re-executing finalized block");
+            if *block == *block_and_execution_effects.block() {
+                return Ok(block_and_execution_effects);
+            }
+            warn!(
+                %peer,
+                "block executed with approvals from this peer doesn't match the received \
+                block; blocking peer"
+            );
+            ctx.effect_builder.announce_disconnect_from_peer(peer).await;
+        }
+        tokio::time::sleep(ctx.config.retry_interval()).await;
+    }
+}
+
+/// Stores the approvals of the given deploys as their finalized approvals.
+async fn store_finalized_approvals<'a, REv>(
+    deploys: impl Iterator<Item = &'a Deploy>,
+    ctx: &ChainSyncContext<'_, REv>,
+) where
+    REv: From<StorageRequest>,
+{
+    for deploy in deploys {
+        ctx.effect_builder
+            .store_finalized_approvals(
+                *deploy.id(),
+                FinalizedApprovals::new(deploy.approvals().clone()),
+            )
+            .await;
+    }
+}
+
+/// Returns a stream of the headers of the blocks following `highest_synced_block_header`, each
+/// verified against its parent and the finality signatures of its era's validators.
+///
+/// Headers are only fetched as the stream is polled.  It ends after the first error, or once no
+/// higher block header can be fetched from any peer.
+///
+/// Headers up to `highest_downloaded_height` have already been fetched, verified and stored
+/// before, so they are read from storage instead, as long as they still chain up to their parent.
+fn fetch_block_headers_ahead<'a, REv>(
+    highest_synced_block_header: &BlockHeader,
+    highest_synced_key_block_info: KeyBlockInfo,
+    highest_downloaded_height: u64,
+    ctx: &'a ChainSyncContext<'a, REv>,
+) -> impl Stream<Item = Result<BlockHeader, Error>> + 'a
+where
+    REv: From<FetcherRequest<BlockHeaderWithMetadata>>
+        + From<NetworkInfoRequest>
+        + From<BlocklistAnnouncement>
+        + From<StorageRequest>
+        + Send,
+{
+    let initial_state = Some((
+        highest_synced_block_header.clone(),
+        highest_synced_key_block_info,
+    ));
+    stream::unfold(initial_state, move |state| async move {
+        let (parent_header, key_block_info) = state?;
+        let stored_header = if parent_header.height() < highest_downloaded_height {
+            get_stored_child_header(&parent_header, ctx).await
+        } else {
+            None
+        };
+        let result = match stored_header {
+            Some(header) => Ok(Some(header)),
+            None => fetch_and_store_next::<_, BlockHeaderWithMetadata>(
+                &parent_header,
+                &key_block_info,
+                ctx,
+            )
+            .await
+            .map(|maybe_item| maybe_item.map(|item| item.block_header)),
+        };
+        match result {
+            Ok(Some(header)) => {
+                let key_block_info = KeyBlockInfo::maybe_from_block_header(
+                    &header,
+                    ctx.config.verifiable_chunked_hash_activation(),
+                )
+                .unwrap_or(key_block_info);
+                Some((Ok(header.clone()), Some((header, key_block_info))))
+            }
+            Ok(None) => None,
+            Err(error) => Some((Err(error), None)),
+        }
+    })
+}
+
+/// Returns the stored header of the block following `parent_header`, if it is a child of it.
+async fn get_stored_child_header<REv>(
+    parent_header: &BlockHeader,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Option<BlockHeader>
+where
+    REv: From<StorageRequest>,
+{
+    let header = ctx
+        .effect_builder
+        .get_block_header_at_height_from_storage(parent_header.height().checked_add(1)?, false)
+        .await?;
+    let parent_hash = parent_header.hash(ctx.config.verifiable_chunked_hash_activation());
+    if *header.parent_hash() == parent_hash {
+        Some(header)
+    } else {
+        warn!(
+            height = header.height(),
+            "stored block header doesn't chain up to its parent; fetching it again"
+        );
+        None
+    }
+}
+
+/// Downloads and stores the block with the given header, along with its deploys and transfers in
+/// the order they are executed in.
+async fn fetch_and_store_block_to_execute<REv>(
+    block_header: BlockHeader,
+    ctx: &ChainSyncContext<'_, REv>,
+) -> Result<(Block, Vec<Deploy>, Vec<Deploy>), Error>
+where
+    REv: From<FetcherRequest<Block>>
+        + From<FetcherRequest<Deploy>>
+        + From<NetworkInfoRequest>
+        + From<StorageRequest>,
+{
+    let block_hash = block_header.hash(ctx.config.verifiable_chunked_hash_activation());
+    let block = *fetch_and_store_block_by_hash(block_hash, ctx).await?;
+    debug_assert_eq!(block_header, *block.header());
+    let (deploys, transfers) = futures::try_join!(
+        fetch_and_store_deploys(block.deploy_hashes().iter(), ctx),
+        fetch_and_store_deploys(block.transfer_hashes().iter(), ctx),
+    )?;
+    Ok((block, deploys, transfers))
+}
+
 /// Executes forwards from the block after `highest_synced_block_header` until we can get no higher
 /// block from any peer, or the block we executed is in the current era.
+///
+/// Blocks are fetched in a pipeline: block headers are fetched and verified ahead of execution,
+/// and up to `max_parallel_block_fetches` blocks are downloaded with their deploys in parallel,
+/// each from randomly chosen peers, while the blocks are executed one after the other.
+///
+/// After each executed block, a checkpoint is persisted, recording `trusted_hash` as the trusted
+/// block the sync started from.  Blocks up to `highest_downloaded_height` were already downloaded
+/// before a restart, and their headers are read from storage rather than fetched again.
 async fn fetch_and_execute_blocks<REv>(
     highest_synced_block_header: &BlockHeader,
     highest_synced_key_block_info: KeyBlockInfo,
+    trusted_hash: BlockHash,
+    highest_downloaded_height: u64,
     ctx: &ChainSyncContext<'_, REv>,
 ) -> Result<BlockHeader, Error>
 where
-    REv: From<FetcherRequest<BlockWithMetadata>>
+    REv: From<FetcherRequest<Block>>
+        + From<FetcherRequest<BlockHeaderWithMetadata>>
         + From<FetcherRequest<FinalizedApprovalsWithId>>
         + From<FetcherRequest<Deploy>>
         + From<NetworkInfoRequest>
@@ -2204,6 +2641,7 @@ where
         + From<BlocklistAnnouncement>
         + From<StorageRequest>
         + From<MarkBlockCompletedRequest>
+        + From<StateStoreRequest>
         + Send,
 {
     let _metric = ScopeTimer::new(&ctx.metrics.chain_sync_execute_blocks_duration_seconds);
@@ -2220,6 +2658,29 @@ where
         block_timestamp = %highest_synced_block_header.timestamp(),
         "fetching and executing blocks to synchronize to current",
     );
+    ctx.progress.start_syncing_blocks(
+        highest_synced_block_header.height(),
+        highest_synced_block_header.timestamp(),
+        None,
+    );
+
+    let downloaded_height =
+        AtomicU64::new(highest_downloaded_height.max(highest_synced_block_header.height()));
+    let downloaded_height = &downloaded_height;
+    let mut blocks = Box::pin(
+        fetch_block_headers_ahead(
+            highest_synced_block_header,
+            highest_synced_key_block_info.clone(),
+            highest_downloaded_height,
+            ctx,
+        )
+        .map(move |result| async move {
+            let fetched = fetch_and_store_block_to_execute(result?, ctx).await?;
+            downloaded_height.fetch_max(fetched.0.height(), Ordering::SeqCst);
+            Ok::<_, Error>(fetched)
+        })
+        .buffered(ctx.config.max_parallel_block_fetches()),
+    );
 
     let mut highest_synced_block_header = highest_synced_block_header.clone();
     let mut key_block_info = highest_synced_key_block_info;
@@ -2227,13 +2688,7 @@ where
         ctx.progress.start_fetching_block_and_deploys_to_execute(
             highest_synced_block_header.height().saturating_add(1),
         );
-        let result = fetch_and_store_next::<_, BlockWithMetadata>(
-            &highest_synced_block_header,
-            &key_block_info,
-            ctx,
-        )
-        .await?;
-        let block = match result {
+        let (block, mut deploys, mut transfers) = match blocks.next().await {
             None => {
                 let in_current_era =
                     is_current_era(&highest_synced_block_header, &key_block_info, ctx.config);
@@ -2246,13 +2701,9 @@ where
                 );
                 break;
             }
-            Some(block_with_metadata) => block_with_metadata.block,
+            Some(result) => result?,
         };
 
-        let mut deploys = fetch_and_store_deploys(block.deploy_hashes().iter(), ctx).await?;
-
-        let mut transfers = fetch_and_store_deploys(block.transfer_hashes().iter(), ctx).await?;
-
         info!(
             era_id = ?block.header().era_id(),
             height = block.height(),
@@ -2261,64 +2712,19 @@ where
             "executing block",
         );
         ctx.progress.start_executing_block(block.height());
-        let block_and_execution_effects = ctx
-            .effect_builder
-            .execute_finalized_block(
-                block.protocol_version(),
-                execution_pre_state.clone(),
-                FinalizedBlock::from(block.clone()),
-                deploys.clone(),
-                transfers.clone(),
-            )
-            .await?;
-
-        let mut blocks_match = block == *block_and_execution_effects.block();
-
-        let mut attempts = 0;
-        while !blocks_match {
-            // Could be wrong approvals - fetch new sets of approvals from a single peer and retry.
-            for peer in get_filtered_fully_connected_peers(ctx).await {
-                warn!(
-                    block_hash=%block.hash(),
-                    "retrying execution due to deploy approvals mismatch"
-                );
-                attempts += 1;
-                ctx.progress.retry_executing_block(block.height(), attempts);
-                let block_and_execution_effects = retry_execution_with_approvals_from_peer(
-                    &mut deploys,
-                    &mut transfers,
-                    peer,
-                    &block,
-                    &execution_pre_state,
-                    ctx,
-                )
-                .await?;
-                debug!(block_hash=%block.hash(), "finish - re-executing finalized block");
-                blocks_match = block == *block_and_execution_effects.block();
-                if blocks_match {
-                    break;
-                }
-                warn!(
-                    %peer,
-                    "block executed with approvals from this peer doesn't match the received \
-                    block; blocking peer"
-                );
-                ctx.effect_builder.announce_disconnect_from_peer(peer).await;
-            }
-        }
-
-        // Matching now - store new approval sets for the deploys.
-        for deploy in deploys.into_iter().chain(transfers.into_iter()) {
-            ctx.effect_builder
-                .store_finalized_approvals(
-                    *deploy.id(),
-                    FinalizedApprovals::new(deploy.approvals().clone()),
-                )
-                .await;
-        }
+        execute_and_verify_block(
+            &block,
+            &mut deploys,
+            &mut transfers,
+            &execution_pre_state,
+            ctx,
+        )
+        .await?;
+        store_finalized_approvals(deploys.iter().chain(transfers.iter()), ctx).await;
         ctx.effect_builder
-            .mark_block_completed(block_and_execution_effects.block().height())
+            .mark_block_completed(block.height())
             .await;
+        ctx.progress.block_synced(block.height(), block.timestamp());
 
         highest_synced_block_header = block.take_header();
         execution_pre_state = ExecutionPreState::from_block_header(
@@ -2333,6 +2739,16 @@ where
             key_block_info = new_key_block_info;
         }
 
+        SyncCheckpoint {
+            trusted_hash,
+            highest_executed_block_hash: highest_synced_block_header
+                .hash(ctx.config.verifiable_chunked_hash_activation()),
+            key_block_info: key_block_info.clone(),
+            highest_downloaded_height: downloaded_height.load(Ordering::SeqCst),
+        }
+        .save(*ctx.effect_builder)
+        .await;
+
         // If we managed to sync up to the current era, stop - we'll have to sync the consensus
         // protocol state, anyway.
         if is_current_era(&highest_synced_block_header, &key_block_info, ctx.config) {
@@ -2348,6 +2764,127 @@ where
     Ok(highest_synced_block_header)
 }
 
+/// Catches up with the network in the background while participating, after consensus noticed
+/// that the node has fallen behind.
+///
+/// Starting from the highest complete block in storage, blocks are fetched and verified as in
+/// `fetch_and_execute_blocks`, including retrying execution with the finalized approvals fetched
+/// from peers.  Only once a block is known to execute to the fetched one is it handed to the
+/// contract runtime to be executed like blocks finalized by consensus.  That way consensus
+/// initializes each new era once its key block has been executed, and can then handle the
+/// messages it buffered for it.  Stops once a block in the current era has been enqueued, or no
+/// peer has a higher block.
+///
+/// Returns the header of the highest block enqueued.
+pub(super) async fn run_catch_up_task<REv>(
+    effect_builder: EffectBuilder<REv>,
+    config: Config,
+    metrics: Metrics,
+    progress: ProgressHolder,
+) -> Result<BlockHeader, Error>
+where
+    REv: From<StorageRequest>
+        + From<NetworkInfoRequest>
+        + From<FetcherRequest<Block>>
+        + From<FetcherRequest<BlockHeader>>
+        + From<FetcherRequest<BlockHeaderWithMetadata>>
+        + From<FetcherRequest<Deploy>>
+        + From<FetcherRequest<FinalizedApprovalsWithId>>
+        + From<ContractRuntimeRequest>
+        + From<BlocklistAnnouncement>
+        + Send,
+{
+    // As for the sync to genesis, the highest complete block in storage is the trust anchor.
+    let mut ctx =
+        ChainSyncContext::new_for_sync_to_genesis(&effect_builder, &config, &metrics, &progress)
+            .await?;
+    // Unlike historical blocks, blocks ahead of ours are held by recent-mode peers too.
+    ctx.archival_peers_only = false;
+    let ctx = &ctx;
+    let mut highest_block_header = ctx.trusted_block_header().clone();
+    info!(
+        era = highest_block_header.era_id().value(),
+        height = highest_block_header.height(),
+        "starting background catch-up",
+    );
+    let mut key_block_info = get_trusted_key_block_info(ctx).await?;
+    let mut execution_pre_state = ExecutionPreState::from_block_header(
+        &highest_block_header,
+        config.verifiable_chunked_hash_activation(),
+    );
+
+    let mut blocks = Box::pin(
+        fetch_block_headers_ahead(
+            &highest_block_header,
+            key_block_info.clone(),
+            highest_block_header.height(),
+            ctx,
+        )
+        .map(move |result| async move { fetch_and_store_block_to_execute(result?, ctx).await })
+        .buffered(config.max_parallel_block_fetches()),
+    );
+    while let Some(result) = blocks.next().await {
+        let (block, mut deploys, mut transfers) = result?;
+        // The deploys we fetched may carry other approvals than the finalized ones, and the
+        // contract runtime would then store a block that doesn't match the network's.
+        execute_and_verify_block(
+            &block,
+            &mut deploys,
+            &mut transfers,
+            &execution_pre_state,
+            ctx,
+        )
+        .await?;
+        store_finalized_approvals(deploys.iter().chain(transfers.iter()), ctx).await;
+
+        // Don't let downloaded blocks pile up in the contract runtime's execution queue.
+        let max_execution_lag = config.max_parallel_block_fetches() as u64;
+        wait_for_execution(block.height().saturating_sub(max_execution_lag), ctx).await;
+        debug!(height = block.height(), "enqueuing block for execution");
+        effect_builder
+            .enqueue_block_for_execution(FinalizedBlock::from(block.clone()), deploys, transfers)
+            .await;
+
+        highest_block_header = block.take_header();
+        execution_pre_state = ExecutionPreState::from_block_header(
+            &highest_block_header,
+            config.verifiable_chunked_hash_activation(),
+        );
+        if let Some(new_key_block_info) = KeyBlockInfo::maybe_from_block_header(
+            &highest_block_header,
+            config.verifiable_chunked_hash_activation(),
+        ) {
+            key_block_info = new_key_block_info;
+        }
+        if is_current_era(&highest_block_header, &key_block_info, &config) {
+            break;
+        }
+    }
+
+    info!(
+        era = highest_block_header.era_id().value(),
+        height = highest_block_header.height(),
+        "finished background catch-up",
+    );
+    Ok(highest_block_header)
+}
+
+/// Waits until the block at the given height has been executed and marked complete in storage.
+async fn wait_for_execution<REv>(height: u64, ctx: &ChainSyncContext<'_, REv>)
+where
+    REv: From<StorageRequest>,
+{
+    while ctx
+        .effect_builder
+        .get_available_block_range_from_storage()
+        .await
+        .high()
+        < height
+    {
+        tokio::time::sleep(ctx.config.retry_interval()).await;
+    }
+}
+
 async fn fetch_and_store_deploys<REv>(
     hashes: impl Iterator<Item = &DeployHash>,
     ctx: &ChainSyncContext<'_, REv>,
@@ -2359,9 +2896,10 @@ where
 
     let hashes: Vec<_> = hashes.cloned().collect();
     let mut deploys: Vec<Deploy> = Vec::with_capacity(hashes.len());
+    // Keep the deploys in the order of `hashes`, which is the order they are executed in.
     let mut stream = futures::stream::iter(hashes)
         .map(|hash| fetch_and_store_deploy(hash, ctx))
-        .buffer_unordered(ctx.config.max_parallel_deploy_fetches());
+        .buffered(ctx.config.max_parallel_deploy_fetches());
     while let Some(result) = stream.next().await {
         let deploy = result?;
         trace!("fetched {:?}", deploy);
@@ -2425,7 +2963,10 @@ mod tests {
     use super::*;
     use crate::{
         components::consensus::EraReport,
-        types::{Block, BlockPayload, Chainspec, ChainspecRawBytes, FinalizedBlock, NodeConfig},
+        types::{
+            Block, BlockPayload, Chainspec, ChainspecRawBytes, FinalitySignature, FinalizedBlock,
+            NodeConfig,
+        },
         utils::Loadable,
         SmallNetworkConfig,
     };
@@ -2659,4 +3200,69 @@ mod tests {
         });
         assert!(are_signatures_sufficient_for_sync_to_genesis(consensus_verdict).is_err());
     }
+
+    #[test]
+    fn verifies_finality_signatures() {
+        let mut rng = TestRng::new();
+        let verifiable_chunked_hash_activation = EraId::from(0);
+        let era_id = EraId::from(3);
+        let block_header = create_block(
+            Timestamp::now(),
+            era_id,
+            10,
+            false,
+            verifiable_chunked_hash_activation,
+        );
+        let block_hash = block_header.hash(verifiable_chunked_hash_activation);
+
+        let secret_key = SecretKey::random(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let validator_weights: BTreeMap<_, _> =
+            iter::once((public_key.clone(), U512::from(100))).collect();
+        let sign = |block_hash, era_id| {
+            let mut signatures = BlockSignatures::new(block_hash, era_id);
+            let finality_signature =
+                FinalitySignature::new(block_hash, era_id, &secret_key, public_key.clone());
+            signatures.insert_proof(public_key.clone(), finality_signature.signature);
+            signatures
+        };
+        let verify = |signatures: &BlockSignatures, validator_weights: &BTreeMap<_, _>| {
+            verify_finality_signatures(
+                &block_header,
+                signatures,
+                validator_weights,
+                verifiable_chunked_hash_activation,
+            )
+        };
+
+        assert!(verify(&sign(block_hash, era_id), &validator_weights).is_ok());
+
+        let other_block_hash = BlockHash::random(&mut rng);
+        assert!(matches!(
+            verify(&sign(other_block_hash, era_id), &validator_weights),
+            Err(BlockVerificationError::SignaturesForOtherBlock { .. })
+        ));
+
+        assert!(matches!(
+            verify(&sign(block_hash, era_id.successor()), &validator_weights),
+            Err(BlockVerificationError::SignaturesForOtherEra { .. })
+        ));
+
+        let other_validator_weights: BTreeMap<_, _> =
+            iter::once((PublicKey::random(&mut rng), U512::from(100))).collect();
+        assert!(matches!(
+            verify(&sign(block_hash, era_id), &other_validator_weights),
+            Err(BlockVerificationError::FinalitySignature(
+                FinalitySignatureError::BogusValidator { .. }
+            ))
+        ));
+
+        // A signature over another block, claimed to be for this one.
+        let mut forged_signatures = sign(other_block_hash, era_id);
+        forged_signatures.block_hash = block_hash;
+        assert!(matches!(
+            verify(&forged_signatures, &validator_weights),
+            Err(BlockVerificationError::InvalidSignature(_))
+        ));
+    }
 }
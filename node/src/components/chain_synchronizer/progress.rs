@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use tracing::error;
 
 use casper_hashing::Digest;
+use casper_types::{TimeDiff, Timestamp};
 
 use crate::types::BlockHash;
 
@@ -146,6 +147,88 @@ pub enum Progress {
     SyncToGenesis(SyncToGenesis),
 }
 
+/// Statistics about the blocks synced so far, to tell whether syncing makes progress and estimate
+/// when it will be finished.
+#[derive(Clone, PartialEq, Serialize, Deserialize, DataSize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SyncStatistics {
+    /// The height of the highest block already synced when syncing blocks started.
+    pub(super) starting_height: u64,
+    /// The height of the highest block synced so far.
+    pub(super) current_height: u64,
+    /// The estimated height of the tip of the chain, i.e. the height syncing needs to reach, if
+    /// known.
+    pub(super) estimated_tip_height: Option<u64>,
+    /// The average number of blocks synced per second since syncing blocks started.
+    pub(super) blocks_per_second: f64,
+    /// The estimated time remaining until the tip is reached, if known.
+    pub(super) eta: Option<TimeDiff>,
+}
+
+/// Tracks the blocks synced so far, in order to provide [`SyncStatistics`].
+#[derive(Clone, DataSize, Debug)]
+struct BlockSyncTracker {
+    /// When syncing blocks started.
+    started_at: Timestamp,
+    /// The height of the highest block already synced when syncing blocks started.
+    starting_height: u64,
+    /// The timestamp of the block at `starting_height`.
+    starting_block_timestamp: Timestamp,
+    /// The height of the highest block synced so far.
+    current_height: u64,
+    /// The timestamp of the block at `current_height`.
+    current_block_timestamp: Timestamp,
+    /// The number of blocks synced so far.
+    blocks_synced: u64,
+    /// The height syncing needs to reach, if known in advance.
+    target_height: Option<u64>,
+}
+
+impl BlockSyncTracker {
+    fn statistics(&self, now: Timestamp) -> SyncStatistics {
+        let elapsed_seconds = now.saturating_diff(self.started_at).millis() as f64 / 1000.0;
+        let blocks_per_second = if elapsed_seconds > 0.0 {
+            self.blocks_synced as f64 / elapsed_seconds
+        } else {
+            0.0
+        };
+        let estimated_tip_height = self
+            .target_height
+            .or_else(|| self.extrapolate_tip_height(now));
+        let eta = estimated_tip_height
+            .filter(|_| blocks_per_second > 0.0)
+            .map(|tip_height| {
+                let remaining_blocks = tip_height.saturating_sub(self.current_height);
+                TimeDiff::from((remaining_blocks as f64 / blocks_per_second * 1000.0) as u64)
+            });
+        SyncStatistics {
+            starting_height: self.starting_height,
+            current_height: self.current_height,
+            estimated_tip_height,
+            blocks_per_second,
+            eta,
+        }
+    }
+
+    /// Estimates the height of the tip of the chain, assuming blocks keep being created at the
+    /// average rate of those synced so far.
+    fn extrapolate_tip_height(&self, now: Timestamp) -> Option<u64> {
+        let blocks = self.current_height.checked_sub(self.starting_height)?;
+        if blocks == 0 {
+            return None;
+        }
+        let block_time = self
+            .current_block_timestamp
+            .saturating_diff(self.starting_block_timestamp)
+            / blocks;
+        if block_time.millis() == 0 {
+            return None;
+        }
+        let behind = now.saturating_diff(self.current_block_timestamp);
+        Some(self.current_height + behind / block_time)
+    }
+}
+
 impl Progress {
     pub(super) fn is_finished(&self) -> bool {
         match self {
@@ -158,6 +241,7 @@ impl Progress {
 #[derive(Clone, DataSize, Debug)]
 pub(super) struct ProgressHolder {
     inner: Arc<Mutex<Progress>>,
+    block_sync: Arc<Mutex<Option<BlockSyncTracker>>>,
 }
 
 /// This impl is specific to fast-sync progress.
@@ -165,6 +249,7 @@ impl ProgressHolder {
     pub(super) fn new_fast_sync() -> Self {
         ProgressHolder {
             inner: Arc::new(Mutex::new(Progress::FastSync(FastSync::NotYetStarted))),
+            block_sync: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -256,6 +341,7 @@ impl ProgressHolder {
             inner: Arc::new(Mutex::new(Progress::SyncToGenesis(
                 SyncToGenesis::NotYetStarted,
             ))),
+            block_sync: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -505,6 +591,54 @@ impl ProgressHolder {
     pub(super) fn progress(&self) -> Progress {
         self.inner.lock().expect("lock poisoned").clone()
     }
+
+    /// Starts tracking the rate at which blocks are synced, from the block at `starting_height`
+    /// towards `target_height`, if known.
+    pub(super) fn start_syncing_blocks(
+        &self,
+        starting_height: u64,
+        starting_block_timestamp: Timestamp,
+        target_height: Option<u64>,
+    ) {
+        *self.block_sync.lock().expect("lock poisoned") = Some(BlockSyncTracker {
+            started_at: Timestamp::now(),
+            starting_height,
+            starting_block_timestamp,
+            current_height: starting_height,
+            current_block_timestamp: starting_block_timestamp,
+            blocks_synced: 0,
+            target_height,
+        });
+    }
+
+    /// Records that the block with the given height and timestamp has been synced.
+    pub(super) fn block_synced(&self, block_height: u64, block_timestamp: Timestamp) {
+        match &mut *self.block_sync.lock().expect("lock poisoned") {
+            Some(tracker) => {
+                tracker.blocks_synced += 1;
+                if block_height > tracker.current_height {
+                    tracker.current_height = block_height;
+                    tracker.current_block_timestamp = block_timestamp;
+                }
+            }
+            None => error!(
+                block_height,
+                "should be syncing blocks if recording synced block"
+            ),
+        }
+    }
+
+    /// Returns statistics about the blocks synced so far, or `None` if no blocks are being synced.
+    pub(super) fn sync_statistics(&self) -> Option<SyncStatistics> {
+        if self.progress().is_finished() {
+            return None;
+        }
+        self.block_sync
+            .lock()
+            .expect("lock poisoned")
+            .as_ref()
+            .map(|tracker| tracker.statistics(Timestamp::now()))
+    }
 }
 
 /// This impl is specific to functionality used for `debug_assert`s.
@@ -555,3 +689,44 @@ impl ProgressHolder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_estimate_tip_height_and_eta() {
+        let started_at = Timestamp::from(1_000_000);
+        let mut tracker = BlockSyncTracker {
+            started_at,
+            starting_height: 100,
+            starting_block_timestamp: Timestamp::from(0),
+            current_height: 100,
+            current_block_timestamp: Timestamp::from(0),
+            blocks_synced: 0,
+            target_height: None,
+        };
+
+        // Without any blocks synced, neither the tip nor the ETA can be estimated.
+        let statistics = tracker.statistics(started_at);
+        assert_eq!(statistics.estimated_tip_height, None);
+        assert_eq!(statistics.eta, None);
+
+        // 100 blocks synced in 50 seconds, created 1 second apart, 950 seconds behind now.
+        tracker.current_height = 200;
+        tracker.current_block_timestamp = Timestamp::from(100_000);
+        tracker.blocks_synced = 100;
+        let now = started_at + TimeDiff::from_seconds(50);
+        let statistics = tracker.statistics(now);
+        assert_eq!(statistics.current_height, 200);
+        assert_eq!(statistics.estimated_tip_height, Some(1_150));
+        assert!((statistics.blocks_per_second - 2.0).abs() < f64::EPSILON);
+        assert_eq!(statistics.eta, Some(TimeDiff::from_seconds(475)));
+
+        // A known target height takes precedence over the estimate.
+        tracker.target_height = Some(300);
+        let statistics = tracker.statistics(now);
+        assert_eq!(statistics.estimated_tip_height, Some(300));
+        assert_eq!(statistics.eta, Some(TimeDiff::from_seconds(50)));
+    }
+}
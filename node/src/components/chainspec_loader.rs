@@ -10,6 +10,8 @@
 // TODO - remove once schemars stops causing warning.
 #![allow(clippy::field_reassign_with_default)]
 
+mod upgrade_handoff;
+
 use std::{
     fmt::{self, Display, Formatter},
     fs,
@@ -28,19 +30,20 @@ use tracing::{debug, error, info, trace, warn};
 
 use casper_types::{file_utils, EraId, ProtocolVersion};
 
+use self::upgrade_handoff::UpgradeHandoff;
 #[cfg(test)]
 use crate::utils::RESOURCES_PATH;
 use crate::{
     components::Component,
     effect::{
         announcements::ChainspecLoaderAnnouncement, requests::ChainspecLoaderRequest,
-        EffectBuilder, EffectExt, Effects,
+        EffectBuilder, EffectExt, Effects, Responder,
     },
     reactor::ReactorExit,
     storage::StorageRequest,
     types::{
         chainspec::{ChainspecRawBytes, Error, ProtocolConfig, CHAINSPEC_FILENAME},
-        ActivationPoint, BlockHeader, Chainspec, ChainspecInfo, ExitCode,
+        ActivationPoint, BlockHash, BlockHeader, Chainspec, ChainspecInfo, ExitCode,
     },
     utils::Loadable,
     NodeRng,
@@ -268,6 +271,11 @@ impl ChainspecLoader {
     where
         REv: Send,
     {
+        if !self.check_upgrade_handoff(maybe_highest_block.as_deref()) {
+            self.reactor_exit = Some(ReactorExit::ProcessShouldExit(ExitCode::Abort));
+            return Effects::new();
+        }
+
         self.reactor_exit = Some(
             Self::should_exit_for_upgrade(
                 maybe_highest_block,
@@ -304,6 +312,75 @@ impl ChainspecLoader {
         })
     }
 
+    /// Checks the handoff left by the previous protocol version if it stopped for an upgrade to
+    /// this one.  Returns `false` if the handoff is inconsistent with our chainspec or storage.
+    fn check_upgrade_handoff(&self, maybe_highest_block: Option<&BlockHeader>) -> bool {
+        let current_dir = self.root_dir.join(dir_name_from_version(
+            &self.chainspec.protocol_config.version,
+        ));
+        let handoff = match UpgradeHandoff::read(&current_dir) {
+            Ok(Some(handoff)) => handoff,
+            Ok(None) => return true,
+            Err(error) => {
+                warn!(%error, "failed to read upgrade handoff, ignoring it");
+                return true;
+            }
+        };
+
+        if let Err(error) = handoff.validate(&self.chainspec.protocol_config, maybe_highest_block) {
+            error!(%error, "invalid upgrade handoff");
+            return false;
+        }
+
+        let highest_block_height = maybe_highest_block.map(|header| header.height());
+        if highest_block_height < Some(handoff.last_block_height()) {
+            warn!(
+                handoff_height = handoff.last_block_height(),
+                ?highest_block_height,
+                "storage is behind the last block of the previous protocol version"
+            );
+        }
+        info!(
+            previous_version = %handoff.previous_version(),
+            "taking over from previous protocol version"
+        );
+        true
+    }
+
+    /// Writes the handoff for the next upgrade into the staged version's chainspec dir.
+    fn write_upgrade_handoff(
+        &self,
+        last_block_hash: BlockHash,
+        last_block_header: Box<BlockHeader>,
+        responder: Responder<()>,
+    ) -> Effects<Event> {
+        let next_upgrade = match self.next_upgrade {
+            Some(ref next_upgrade) => next_upgrade,
+            None => {
+                error!("stopping for upgrade, but no next upgrade known");
+                return responder.respond(()).ignore();
+            }
+        };
+        let handoff = UpgradeHandoff::new(
+            self.chainspec.protocol_config.version,
+            next_upgrade,
+            last_block_hash,
+            &last_block_header,
+        );
+        let next_dir = self
+            .root_dir
+            .join(dir_name_from_version(&next_upgrade.protocol_version));
+        async move {
+            match task::spawn_blocking(move || handoff.write(&next_dir)).await {
+                Ok(Ok(())) => info!(%last_block_hash, "wrote upgrade handoff"),
+                Ok(Err(error)) => error!(%error, "failed to write upgrade handoff"),
+                Err(error) => error!(%error, "failed to join tokio task"),
+            }
+            responder.respond(()).await
+        }
+        .ignore()
+    }
+
     /// This is a workaround while we have multiple reactors.  It should be used in the joiner and
     /// participating reactors' constructors to start the recurring task of checking for upgrades.
     /// The recurring tasks of the previous reactors will be cancelled when the relevant reactor
@@ -428,6 +505,11 @@ where
             Event::Request(ChainspecLoaderRequest::GetChainspecRawBytes(responder)) => responder
                 .respond(Arc::clone(&self.chainspec_raw_bytes))
                 .ignore(),
+            Event::Request(ChainspecLoaderRequest::WriteUpgradeHandoff {
+                last_block_hash,
+                last_block_header,
+                responder,
+            }) => self.write_upgrade_handoff(last_block_hash, last_block_header, responder),
             Event::CheckForNextUpgrade => self.check_for_next_upgrade(effect_builder),
             Event::GotNextUpgrade(next_upgrade) => self.handle_got_next_upgrade(next_upgrade),
         }
@@ -547,13 +629,24 @@ fn next_upgrade(dir: PathBuf, current_version: ProtocolVersion) -> Option<NextUp
         return None;
     }
 
+    if let ActivationPoint::Genesis(_) = upgrade_point.protocol_config.activation_point {
+        warn!(
+            subdir=%subdir.display(),
+            "next chainspec must have an era ID as its activation point"
+        );
+        return None;
+    }
+
     Some(NextUpgrade::from(upgrade_point.protocol_config))
 }
 
 #[cfg(test)]
 mod tests {
-    use casper_types::testing::TestRng;
+    use rand::Rng;
 
+    use casper_types::{testing::TestRng, Timestamp};
+
+    use super::upgrade_handoff::HandoffError;
     use super::*;
     use crate::types::{chainspec::CHAINSPEC_FILENAME, Block};
 
@@ -749,6 +842,7 @@ mod tests {
     ) -> Chainspec {
         let mut chainspec = Chainspec::random(rng);
         chainspec.protocol_config.version = *version;
+        chainspec.protocol_config.activation_point = ActivationPoint::EraId(rng.gen());
 
         let subdir = root_dir.join(dir_name_from_version(version));
         fs::create_dir(&subdir).unwrap();
@@ -831,6 +925,18 @@ mod tests {
         .expect("should install upgrade point");
         assert!(maybe_next_point(&current).is_none());
 
+        // Check we return `None` if the next version upgrade_point has a genesis activation point.
+        chainspec_v0_9_9.protocol_config.version = v1_0_0;
+        chainspec_v0_9_9.protocol_config.activation_point =
+            ActivationPoint::Genesis(Timestamp::random(&mut rng));
+        chainspec_v0_9_9.protocol_config.state_migrations.clear();
+        fs::write(
+            &path_v1_0_0,
+            toml::to_string_pretty(&chainspec_v0_9_9).expect("should encode to toml"),
+        )
+        .expect("should install upgrade point");
+        assert!(maybe_next_point(&current).is_none());
+
         // Check we return `None` if the next version upgrade_point file is corrupt.
         fs::write(&path_v1_0_0, "bad data".as_bytes()).unwrap();
         assert!(maybe_next_point(&current).is_none());
@@ -839,4 +945,71 @@ mod tests {
         fs::remove_file(&path_v1_0_0).unwrap();
         assert!(maybe_next_point(&current).is_none());
     }
+
+    #[test]
+    fn should_write_read_and_validate_upgrade_handoff() {
+        let tempdir = tempfile::tempdir().expect("should create temp dir");
+        let mut rng = crate::new_rng();
+
+        // No handoff file present.
+        assert!(UpgradeHandoff::read(tempdir.path()).unwrap().is_none());
+
+        let v1_0_0 = ProtocolVersion::from_parts(1, 0, 0);
+        let v1_1_0 = ProtocolVersion::from_parts(1, 1, 0);
+        let chainspec = install_chainspec(&mut rng, tempdir.path(), &v1_1_0);
+        let protocol_config = &chainspec.protocol_config;
+        let next_upgrade = NextUpgrade::from(protocol_config.clone());
+
+        let verifiable_chunked_hash_activation = protocol_config.verifiable_chunked_hash_activation;
+        let last_block = Block::random_with_verifiable_chunked_hash_activation(
+            &mut rng,
+            verifiable_chunked_hash_activation,
+        );
+        let handoff = UpgradeHandoff::new(
+            v1_0_0,
+            &next_upgrade,
+            *last_block.hash(),
+            last_block.header(),
+        );
+        handoff.write(tempdir.path()).expect("should write handoff");
+        let read_handoff = UpgradeHandoff::read(tempdir.path())
+            .expect("should read handoff")
+            .expect("should have handoff");
+        assert_eq!(read_handoff, handoff);
+
+        // Valid with or without stored blocks, as long as storage agrees at the handoff height.
+        assert!(handoff.validate(protocol_config, None).is_ok());
+        assert!(handoff
+            .validate(protocol_config, Some(last_block.header()))
+            .is_ok());
+
+        let other_block = Block::random_with_specifics(
+            &mut rng,
+            last_block.header().era_id(),
+            last_block.height(),
+            ProtocolVersion::V1_0_0,
+            true,
+            verifiable_chunked_hash_activation,
+            None,
+        );
+        assert!(matches!(
+            handoff.validate(protocol_config, Some(other_block.header())),
+            Err(HandoffError::BlockHashMismatch { .. })
+        ));
+
+        let mut other_config = protocol_config.clone();
+        other_config.version = ProtocolVersion::from_parts(1, 2, 0);
+        assert!(matches!(
+            handoff.validate(&other_config, None),
+            Err(HandoffError::VersionMismatch { .. })
+        ));
+
+        let mut other_config = protocol_config.clone();
+        other_config.activation_point =
+            ActivationPoint::EraId(protocol_config.activation_point.era_id().successor());
+        assert!(matches!(
+            handoff.validate(&other_config, None),
+            Err(HandoffError::ActivationPointMismatch { .. })
+        ));
+    }
 }
@@ -0,0 +1,174 @@
+//! The state handed from a node which stopped for an upgrade to the version replacing it.
+//!
+//! When the linear chain has a sufficiently signed switch block immediately preceding the next
+//! upgrade's activation point, the running version writes an `UpgradeHandoff` into the staged
+//! version's chainspec dir before exiting.  The node launcher then starts the staged version, which
+//! checks the handoff against its own chainspec and the contents of storage before proceeding.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use casper_hashing::Digest;
+use casper_types::{EraId, ProtocolVersion, Timestamp};
+
+use super::NextUpgrade;
+use crate::types::{chainspec::ProtocolConfig, BlockHash, BlockHeader};
+
+/// The name of the file holding the handoff, written into the staged version's chainspec dir.
+pub(super) const UPGRADE_HANDOFF_FILENAME: &str = "upgrade_handoff.json";
+
+/// An error relating to the upgrade handoff.
+#[derive(Debug, Error)]
+pub(crate) enum HandoffError {
+    /// Failed to read or write the handoff file.
+    #[error("upgrade handoff file {}: {error}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        error: io::Error,
+    },
+    /// Failed to (de)serialize the handoff.
+    #[error("upgrade handoff encoding: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The handoff was written for a different protocol version.
+    #[error("upgrade handoff is for protocol version {handoff}, but running {running}")]
+    VersionMismatch {
+        handoff: ProtocolVersion,
+        running: ProtocolVersion,
+    },
+    /// The previous version stopped at a different activation point to the one in our chainspec.
+    #[error(
+        "previous version stopped for an upgrade at era {handoff}, but chainspec activation point \
+        is {chainspec}"
+    )]
+    ActivationPointMismatch { handoff: EraId, chainspec: EraId },
+    /// Storage holds a different block at the height of the handoff's last block.
+    #[error(
+        "previous version stopped after block {handoff} at height {height}, but storage has block \
+        {stored} at that height"
+    )]
+    BlockHashMismatch {
+        height: u64,
+        handoff: BlockHash,
+        stored: BlockHash,
+    },
+}
+
+/// The state in which a node stopped to allow the next protocol version to take over.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct UpgradeHandoff {
+    /// The protocol version which stopped for the upgrade.
+    from_version: ProtocolVersion,
+    /// The protocol version expected to take over.
+    to_version: ProtocolVersion,
+    /// The era at which the new protocol version activates.
+    activation_era: EraId,
+    /// The hash of the last block executed under the old protocol version.
+    last_block_hash: BlockHash,
+    /// The height of the last block executed under the old protocol version.
+    last_block_height: u64,
+    /// The global state root hash after the last block.
+    last_state_root_hash: Digest,
+    /// When the old protocol version stopped.
+    stopped_at: Timestamp,
+}
+
+impl UpgradeHandoff {
+    pub(super) fn new(
+        from_version: ProtocolVersion,
+        next_upgrade: &NextUpgrade,
+        last_block_hash: BlockHash,
+        last_block_header: &BlockHeader,
+    ) -> Self {
+        UpgradeHandoff {
+            from_version,
+            to_version: next_upgrade.protocol_version,
+            activation_era: next_upgrade.activation_point.era_id(),
+            last_block_hash,
+            last_block_height: last_block_header.height(),
+            last_state_root_hash: *last_block_header.state_root_hash(),
+            stopped_at: Timestamp::now(),
+        }
+    }
+
+    /// Writes the handoff to `dir`.
+    ///
+    /// The file is written under a temporary name and then renamed, so a partially written
+    /// handoff is never picked up by the next version.
+    pub(super) fn write(&self, dir: &Path) -> Result<(), HandoffError> {
+        let path = dir.join(UPGRADE_HANDOFF_FILENAME);
+        let temp_path = path.with_extension("json.tmp");
+        let contents = serde_json::to_vec_pretty(self)?;
+        fs::write(&temp_path, contents).map_err(|error| HandoffError::Io {
+            path: temp_path.clone(),
+            error,
+        })?;
+        fs::rename(&temp_path, &path).map_err(|error| HandoffError::Io { path, error })
+    }
+
+    /// Reads the handoff from `dir`, returning `None` if there is no handoff file there.
+    pub(super) fn read(dir: &Path) -> Result<Option<Self>, HandoffError> {
+        let path = dir.join(UPGRADE_HANDOFF_FILENAME);
+        let contents = match fs::read(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(HandoffError::Io { path, error }),
+        };
+        Ok(Some(serde_json::from_slice(&contents)?))
+    }
+
+    /// Checks the handoff was written for the given protocol config, and that storage agrees with
+    /// the previous version about the last block before the upgrade.
+    pub(super) fn validate(
+        &self,
+        protocol_config: &ProtocolConfig,
+        maybe_highest_block_header: Option<&BlockHeader>,
+    ) -> Result<(), HandoffError> {
+        if self.to_version != protocol_config.version {
+            return Err(HandoffError::VersionMismatch {
+                handoff: self.to_version,
+                running: protocol_config.version,
+            });
+        }
+
+        let activation_era = protocol_config.activation_point.era_id();
+        if self.activation_era != activation_era {
+            return Err(HandoffError::ActivationPointMismatch {
+                handoff: self.activation_era,
+                chainspec: activation_era,
+            });
+        }
+
+        if let Some(highest_block_header) = maybe_highest_block_header {
+            let stored =
+                highest_block_header.hash(protocol_config.verifiable_chunked_hash_activation);
+            if highest_block_header.height() == self.last_block_height
+                && stored != self.last_block_hash
+            {
+                return Err(HandoffError::BlockHashMismatch {
+                    height: self.last_block_height,
+                    handoff: self.last_block_hash,
+                    stored,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the protocol version which stopped for the upgrade.
+    pub(super) fn previous_version(&self) -> ProtocolVersion {
+        self.from_version
+    }
+
+    /// Returns the height of the last block executed under the old protocol version.
+    pub(super) fn last_block_height(&self) -> u64 {
+        self.last_block_height
+    }
+}
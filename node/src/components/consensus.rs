@@ -52,7 +52,7 @@ use crate::{
 };
 
 pub(crate) use cl_context::ClContext;
-pub(crate) use config::{ChainspecConsensusExt, Config};
+pub(crate) use config::{ChainspecConsensusExt, Config, LoadSigningKeyError};
 pub(crate) use consensus_protocol::{BlockContext, EraReport, ProposedBlock};
 pub(crate) use era_supervisor::{debug::EraDump, EraSupervisor};
 pub(crate) use protocols::highway::HighwayProtocol;
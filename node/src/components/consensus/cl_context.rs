@@ -2,24 +2,24 @@ use std::sync::Arc;
 
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use tracing::{error, info};
 
 use casper_hashing::Digest;
 use casper_types::{crypto, PublicKey, SecretKey, Signature};
 
 use crate::{
     components::consensus::traits::{ConsensusValueT, Context, ValidatorSecret},
-    types::BlockPayload,
+    types::{BlockPayload, SigningKey},
 };
 
 #[derive(DataSize)]
 pub struct Keypair {
-    secret_key: Arc<SecretKey>,
+    secret_key: SigningKey,
     public_key: PublicKey,
 }
 
 impl Keypair {
-    pub(crate) fn new(secret_key: Arc<SecretKey>, public_key: PublicKey) -> Self {
+    pub(crate) fn new(secret_key: SigningKey, public_key: PublicKey) -> Self {
         Self {
             secret_key,
             public_key,
@@ -30,7 +30,7 @@ impl Keypair {
 impl From<Arc<SecretKey>> for Keypair {
     fn from(secret_key: Arc<SecretKey>) -> Self {
         let public_key: PublicKey = secret_key.as_ref().into();
-        Self::new(secret_key, public_key)
+        Self::new(SigningKey::from(secret_key), public_key)
     }
 }
 
@@ -38,8 +38,11 @@ impl ValidatorSecret for Keypair {
     type Hash = Digest;
     type Signature = Signature;
 
-    fn sign(&self, hash: &Digest) -> Signature {
-        crypto::sign(hash, self.secret_key.as_ref(), &self.public_key)
+    fn sign(&self, hash: &Digest) -> Option<Signature> {
+        self.secret_key
+            .sign(hash, &self.public_key)
+            .map_err(|error| error!(%error, "failed to sign consensus message"))
+            .ok()
     }
 }
 
@@ -1,48 +1,175 @@
-use std::{path::Path, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use datasize::DataSize;
 use serde::Deserialize;
+use thiserror::Error;
 
-use casper_types::{PublicKey, SecretKey};
+#[cfg(feature = "pkcs11")]
+use casper_types::file_utils::ReadFileError;
+use casper_types::{crypto::ErrorExt as CryptoError, PublicKey, SecretKey};
 
+#[cfg(feature = "pkcs11")]
+use crate::pkcs11::{self, Pkcs11Key};
 use crate::{
     components::consensus::{
         era_supervisor::PAST_OPEN_ERAS, protocols::highway::config::Config as HighwayConfig, EraId,
     },
-    types::Chainspec,
-    utils::{External, LoadError, Loadable},
+    types::{Chainspec, SigningKey},
+    utils::{External, LoadError},
 };
 
+/// Default value for `CatchUpConfig::max_buffered_bytes`.
+const DEFAULT_MAX_BUFFERED_BYTES: usize = 64 * 1024 * 1024;
+/// Default value for `CatchUpConfig::max_buffered_bytes_per_peer`.
+const DEFAULT_MAX_BUFFERED_BYTES_PER_PEER: usize = 4 * 1024 * 1024;
+
 /// Consensus configuration.
 #[derive(DataSize, Debug, Deserialize, Clone)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub(crate) struct Config {
-    /// Path to secret key file.
+    /// Path to secret key file.  Ignored if `pkcs11` is set.
     pub(crate) secret_key_path: External,
+    /// The key held in a PKCS#11 token to sign with instead of the secret key file, if any.
+    pub(crate) pkcs11: Option<Pkcs11Config>,
     /// Highway-specific node configuration.
     pub(crate) highway: HighwayConfig,
+    /// Configuration for catching up in the background after falling behind.
+    pub(crate) catch_up: CatchUpConfig,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             secret_key_path: External::Missing,
+            pkcs11: None,
             highway: HighwayConfig::default(),
+            catch_up: CatchUpConfig::default(),
+        }
+    }
+}
+
+/// Configuration for catching up with the network in the background while participating.
+#[derive(DataSize, Debug, Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct CatchUpConfig {
+    /// Whether to buffer consensus messages for future eras and fetch and execute the missing
+    /// blocks in the background if the node falls behind the network.
+    pub(crate) enabled: bool,
+    /// The maximum number of eras the node may be behind for messages to still be buffered.
+    pub(crate) max_eras_behind: u64,
+    /// The maximum total size in bytes of buffered consensus messages for future eras.
+    pub(crate) max_buffered_bytes: usize,
+    /// The maximum total size in bytes of buffered consensus messages for future eras from a
+    /// single peer.
+    pub(crate) max_buffered_bytes_per_peer: usize,
+    /// The number of distinct peers that must have sent messages for an era more than one era
+    /// ahead of ours for the node to consider itself behind and start catching up.
+    pub(crate) min_peers_ahead: usize,
+}
+
+impl Default for CatchUpConfig {
+    fn default() -> Self {
+        CatchUpConfig {
+            enabled: false,
+            max_eras_behind: 2,
+            max_buffered_bytes: DEFAULT_MAX_BUFFERED_BYTES,
+            max_buffered_bytes_per_peer: DEFAULT_MAX_BUFFERED_BYTES_PER_PEER,
+            min_peers_ahead: 3,
         }
     }
 }
 
+/// Configuration of a signing key held in a PKCS#11 token, such as a hardware security module.
+#[derive(DataSize, Debug, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Pkcs11Config {
+    /// Path to the PKCS#11 module (shared library) provided by the token vendor.
+    pub(crate) module_path: PathBuf,
+    /// Label of the token holding the key.
+    pub(crate) token_label: String,
+    /// Label of the private and public key objects in the token.
+    pub(crate) key_label: String,
+    /// Path to a file containing the user PIN of the token.
+    pub(crate) pin_path: External,
+}
+
+/// Error loading the signing key.
+#[derive(Debug, Error)]
+pub enum LoadSigningKeyError {
+    /// Failed to load the secret key file.
+    #[error(transparent)]
+    SecretKey(#[from] LoadError<CryptoError>),
+    /// Failed to load the PIN of the PKCS#11 token.
+    #[cfg(feature = "pkcs11")]
+    #[error("could not load PKCS#11 PIN: {0}")]
+    Pkcs11Pin(LoadError<ReadFileError>),
+    /// Failed to open the key held in the PKCS#11 token.
+    #[cfg(feature = "pkcs11")]
+    #[error(transparent)]
+    Pkcs11(#[from] pkcs11::Error),
+    /// A PKCS#11 key is configured, but the node was built without PKCS#11 support.
+    #[cfg(not(feature = "pkcs11"))]
+    #[error(
+        "PKCS#11 key {key_label:?} in token {token_label:?} is configured, but this node was \
+        built without the \"pkcs11\" feature"
+    )]
+    Pkcs11Unsupported {
+        /// Label of the configured token.
+        token_label: String,
+        /// Label of the configured key.
+        key_label: String,
+    },
+}
+
 impl Config {
-    /// Loads the secret key from the configuration file and derives the public key.
-    #[allow(clippy::type_complexity)]
+    /// Loads the signing key, either from the secret key file or from the PKCS#11 token if one is
+    /// configured, and derives the public key.
     pub(crate) fn load_keys<P: AsRef<Path>>(
         &self,
         root: P,
-    ) -> Result<(Arc<SecretKey>, PublicKey), LoadError<<Arc<SecretKey> as Loadable>::Error>> {
-        let secret_signing_key: Arc<SecretKey> = self.secret_key_path.clone().load(root)?;
-        let public_key: PublicKey = PublicKey::from(secret_signing_key.as_ref());
-        Ok((secret_signing_key, public_key))
+    ) -> Result<(SigningKey, PublicKey), LoadSigningKeyError> {
+        let signing_key = match self.pkcs11 {
+            Some(ref pkcs11_config) => pkcs11_config.open(root.as_ref())?,
+            None => {
+                let secret_key: Arc<SecretKey> = self.secret_key_path.clone().load(root)?;
+                SigningKey::from(secret_key)
+            }
+        };
+        let public_key = signing_key.public_key();
+        Ok((signing_key, public_key))
+    }
+}
+
+impl Pkcs11Config {
+    /// Opens the configured key, resolving relative paths from `root`.
+    #[cfg(feature = "pkcs11")]
+    fn open(&self, root: &Path) -> Result<SigningKey, LoadSigningKeyError> {
+        let pin: Vec<u8> = self
+            .pin_path
+            .clone()
+            .load(root)
+            .map_err(LoadSigningKeyError::Pkcs11Pin)?;
+        let pin = String::from_utf8_lossy(&pin);
+        let key = Pkcs11Key::open_shared(
+            &root.join(&self.module_path),
+            &self.token_label,
+            &self.key_label,
+            pin.trim_end(),
+        )?;
+        Ok(SigningKey::Pkcs11(key))
+    }
+
+    /// Fails, as the node was built without PKCS#11 support.
+    #[cfg(not(feature = "pkcs11"))]
+    fn open(&self, _root: &Path) -> Result<SigningKey, LoadSigningKeyError> {
+        Err(LoadSigningKeyError::Pkcs11Unsupported {
+            token_label: self.token_label.clone(),
+            key_label: self.key_label.clone(),
+        })
     }
 }
 
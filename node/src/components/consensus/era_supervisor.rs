@@ -7,6 +7,7 @@
 
 pub(super) mod debug;
 mod era;
+mod future_era_messages;
 
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
@@ -24,10 +25,10 @@ use futures::FutureExt;
 use itertools::Itertools;
 use prometheus::Registry;
 use rand::Rng;
-use tracing::{debug, error, info, trace, warn};
+use tracing::{debug, error, error_span, info, trace, warn};
 
 use casper_hashing::Digest;
-use casper_types::{AsymmetricType, EraId, PublicKey, SecretKey, TimeDiff, Timestamp, U512};
+use casper_types::{AsymmetricType, EraId, PublicKey, TimeDiff, Timestamp, U512};
 
 use crate::{
     components::{
@@ -43,6 +44,7 @@ use crate::{
             ReactorEventT, ResolveValidity, TimerId,
         },
         storage::Storage,
+        HealthCheck,
     },
     effect::{
         announcements::ControlAnnouncement,
@@ -51,13 +53,15 @@ use crate::{
     },
     fatal,
     types::{
-        ActivationPoint, BlockHash, BlockHeader, Chainspec, Deploy, DeployHash,
+        ActivationPoint, BlockHash, BlockHeader, Chainspec, ComponentHealth, Deploy, DeployHash,
         DeployOrTransferHash, FinalitySignature, FinalizedApprovals, FinalizedBlock, NodeId,
+        SigningKey,
     },
     NodeRng,
 };
 
 pub use self::era::Era;
+use self::future_era_messages::{BufferOutcome, FutureEraMessages};
 use crate::components::consensus::error::CreateNewEraError;
 
 /// The delay in milliseconds before we shutdown after the number of faulty validators exceeded the
@@ -104,7 +108,7 @@ pub struct EraSupervisor {
     /// Since eras at or before the most recent activation point are never instantiated, shortly
     /// after that there can temporarily be fewer than three entries in the map.
     open_eras: HashMap<EraId, Era>,
-    secret_signing_key: Arc<SecretKey>,
+    secret_signing_key: SigningKey,
     public_signing_key: PublicKey,
     current_era: EraId,
     chainspec: Arc<Chainspec>,
@@ -130,6 +134,11 @@ pub struct EraSupervisor {
     next_upgrade_activation_point: Option<ActivationPoint>,
     /// The era that was current when this node joined the network.
     era_where_we_joined: EraId,
+    /// Whether we stopped creating new units and proposals because the node is shutting down.
+    stopped_proposing: bool,
+    /// Messages for eras that haven't been initialized yet, buffered while catching up with the
+    /// network in the background.
+    future_era_messages: FutureEraMessages,
 }
 
 impl Debug for EraSupervisor {
@@ -145,7 +154,7 @@ impl EraSupervisor {
     pub(crate) fn new<REv: ReactorEventT>(
         current_era: EraId,
         storage_dir: &Path,
-        secret_signing_key: Arc<SecretKey>,
+        secret_signing_key: SigningKey,
         public_signing_key: PublicKey,
         config: Config,
         effect_builder: EffectBuilder<REv>,
@@ -186,6 +195,8 @@ impl EraSupervisor {
             next_upgrade_activation_point,
             next_executed_height: next_height,
             era_where_we_joined: current_era,
+            stopped_proposing: false,
+            future_era_messages: FutureEraMessages::default(),
         };
 
         // Collect the information needed to initialize all open eras.
@@ -308,19 +319,45 @@ impl EraSupervisor {
 
     /// Updates `next_executed_height` based on the given block header, and unpauses consensus if
     /// block execution has caught up with finalization.
+    ///
+    /// While catching up in the background, blocks are executed that this node didn't finalize
+    /// itself, so `next_block_height` is advanced as well.
     #[allow(clippy::integer_arithmetic)] // Block height should never reach u64::MAX.
     fn executed_block(&mut self, block_header: &BlockHeader) {
         self.next_executed_height = self.next_executed_height.max(block_header.height() + 1);
+        self.next_block_height = self.next_block_height.max(self.next_executed_height);
+        self.update_consensus_pause();
+    }
+
+    /// Returns whether the last executed block is too far behind the last finalized block.
+    fn is_execution_delayed(&self) -> bool {
+        self.next_block_height
+            .saturating_sub(self.next_executed_height)
+            > self.config.highway.max_execution_delay
+    }
+
+    /// Returns the height of the next block to be finalized.
+    pub(crate) fn next_block_height(&self) -> u64 {
+        self.next_block_height
+    }
+
+    /// Returns whether all finalized blocks have been executed.
+    pub(crate) fn is_execution_complete(&self) -> bool {
+        self.next_executed_height >= self.next_block_height
+    }
+
+    /// Stops creating new units and proposals for the rest of this node's lifetime, in preparation
+    /// for shutting down.
+    pub(crate) fn stop_proposing(&mut self) {
+        info!("stop proposing");
+        self.stopped_proposing = true;
         self.update_consensus_pause();
     }
 
     /// Pauses or unpauses consensus: Whenever the last executed block is too far behind the last
-    /// finalized block, we suspend consensus.
+    /// finalized block, or we are shutting down, we suspend consensus.
     fn update_consensus_pause(&mut self) {
-        let paused = self
-            .next_block_height
-            .saturating_sub(self.next_executed_height)
-            > self.config.highway.max_execution_delay;
+        let paused = self.stopped_proposing || self.is_execution_delayed();
         match self.open_eras.get_mut(&self.current_era) {
             Some(era) => era.set_paused(paused),
             None => error!(
@@ -340,7 +377,10 @@ impl EraSupervisor {
     ) -> Effects<Event> {
         match self.create_new_era(switch_blocks) {
             Ok((era_id, outcomes)) => {
-                self.handle_consensus_outcomes(effect_builder, rng, era_id, outcomes)
+                let mut effects =
+                    self.handle_consensus_outcomes(effect_builder, rng, era_id, outcomes);
+                effects.extend(self.handle_buffered_messages(effect_builder, rng));
+                effects
             }
             Err(err) => fatal!(
                 effect_builder,
@@ -504,7 +544,10 @@ impl EraSupervisor {
                     secret,
                     now,
                     Some(unit_hash_file),
-                ))
+                ));
+                if self.stopped_proposing {
+                    self.era_mut(era_id).set_paused(true);
+                }
             };
         }
 
@@ -582,6 +625,8 @@ impl EraSupervisor {
                 Effects::new()
             }
             Some(era) => {
+                // Attach the era to everything logged while handling this.
+                let _entered = error_span!("era", era_id = era_id.value()).entered();
                 let outcomes = f(&mut *era.consensus, rng);
                 self.handle_consensus_outcomes(effect_builder, rng, era_id, outcomes)
             }
@@ -625,8 +670,13 @@ impl EraSupervisor {
                 // If the era is already unbonded, only accept new evidence, because still-bonded
                 // eras could depend on that.
                 trace!(era = era_id.value(), "received a consensus message");
-                self.delegate_to_era(effect_builder, rng, era_id, move |consensus, rng| {
-                    consensus.handle_message(rng, sender, payload, Timestamp::now())
+                if self.config.catch_up.enabled && era_id > self.current_era {
+                    return self.buffer_future_era_message(effect_builder, sender, era_id, payload);
+                }
+                error_span!("peer", peer_id = %sender).in_scope(|| {
+                    self.delegate_to_era(effect_builder, rng, era_id, move |consensus, rng| {
+                        consensus.handle_message(rng, sender, payload, Timestamp::now())
+                    })
                 })
             }
             ConsensusMessage::EvidenceRequest { era_id, pub_key } => {
@@ -647,6 +697,84 @@ impl EraSupervisor {
         }
     }
 
+    /// Buffers a message for a future era, to be handled once that era is initialized. Announces
+    /// that we fell behind once enough peers are more than one era ahead of ours.
+    fn buffer_future_era_message<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        sender: NodeId,
+        era_id: EraId,
+        payload: Vec<u8>,
+    ) -> Effects<Event> {
+        let outcome = self.future_era_messages.push(
+            &self.config.catch_up,
+            self.current_era,
+            sender,
+            era_id,
+            payload,
+        );
+        match outcome {
+            BufferOutcome::Buffered => Effects::new(),
+            BufferOutcome::FellBehind => {
+                info!(
+                    era = era_id.value(),
+                    current_era = self.current_era.value(),
+                    "fell behind the network; catching up in the background"
+                );
+                effect_builder
+                    .announce_fell_behind(self.current_era, era_id)
+                    .ignore()
+            }
+            BufferOutcome::TooFarAhead => {
+                info!(
+                    era = era_id.value(),
+                    current_era = self.current_era.value(),
+                    "received message for era too far ahead to catch up with"
+                );
+                Effects::new()
+            }
+            BufferOutcome::BudgetExceeded => {
+                debug!(
+                    era = era_id.value(),
+                    %sender,
+                    "future era message buffer full; dropping message"
+                );
+                Effects::new()
+            }
+        }
+    }
+
+    /// Handles the buffered messages for eras that have been initialized by now, and drops the
+    /// ones for eras that have become obsolete in the meantime.
+    fn handle_buffered_messages<REv: ReactorEventT>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        rng: &mut NodeRng,
+    ) -> Effects<Event> {
+        let ready = self.future_era_messages.take_ready(self.current_era);
+        let mut effects = Effects::new();
+        for (era_id, messages) in ready {
+            if !self.open_eras.contains_key(&era_id) {
+                debug!(
+                    era = era_id.value(),
+                    count = messages.len(),
+                    "dropping buffered messages for obsolete era"
+                );
+                continue;
+            }
+            debug!(
+                era = era_id.value(),
+                count = messages.len(),
+                "handling buffered messages"
+            );
+            for (sender, payload) in messages {
+                let msg = ConsensusMessage::Protocol { era_id, payload };
+                effects.extend(self.handle_message(effect_builder, rng, sender, msg));
+            }
+        }
+        effects
+    }
+
     pub(super) fn handle_new_block_payload<REv: ReactorEventT>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -680,14 +808,20 @@ impl EraSupervisor {
         let era_id = block_header.era_id();
         self.executed_block(&block_header);
         let mut effects = if self.is_validator_in(&our_pk, era_id) {
-            effect_builder
-                .announce_created_finality_signature(FinalitySignature::new(
-                    block_header.hash(self.verifiable_chunked_hash_activation()),
-                    era_id,
-                    &our_sk,
-                    our_pk,
-                ))
-                .ignore()
+            match FinalitySignature::create(
+                block_header.hash(self.verifiable_chunked_hash_activation()),
+                era_id,
+                &our_sk,
+                our_pk,
+            ) {
+                Ok(signature) => effect_builder
+                    .announce_created_finality_signature(signature)
+                    .ignore(),
+                Err(error) => {
+                    error!(%error, %era_id, "failed to create finality signature");
+                    Effects::new()
+                }
+            }
         } else {
             Effects::new()
         };
@@ -1078,13 +1212,17 @@ impl EraSupervisor {
         responder: Responder<Option<(PublicKey, Option<TimeDiff>)>>,
     ) -> Effects<Event> {
         let public_key = self.public_signing_key.clone();
-        let round_length = self
-            .open_eras
-            .get(&self.current_era)
-            .and_then(|era| era.consensus.next_round_length());
+        let round_length = self.next_round_length();
         responder.respond(Some((public_key, round_length))).ignore()
     }
 
+    /// Returns the round length in the current era, if this node is an active validator in it.
+    pub(crate) fn next_round_length(&self) -> Option<TimeDiff> {
+        self.open_eras
+            .get(&self.current_era)
+            .and_then(|era| era.consensus.next_round_length())
+    }
+
     fn disconnect<REv: ReactorEventT>(
         &self,
         effect_builder: EffectBuilder<REv>,
@@ -1113,6 +1251,21 @@ impl EraSupervisor {
     }
 }
 
+impl HealthCheck for EraSupervisor {
+    fn health(&self) -> ComponentHealth {
+        if !self.open_eras.contains_key(&self.current_era) {
+            ComponentHealth::failed(format!("current era {} not initialized", self.current_era))
+        } else if self.is_execution_delayed() {
+            ComponentHealth::degraded(format!(
+                "paused: next block height {}, next executed height {}",
+                self.next_block_height, self.next_executed_height
+            ))
+        } else {
+            ComponentHealth::healthy()
+        }
+    }
+}
+
 #[cfg(test)]
 impl EraSupervisor {
     /// Returns this node's validator key.
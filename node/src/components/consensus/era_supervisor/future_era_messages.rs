@@ -0,0 +1,271 @@
+//! A buffer for consensus messages for eras that haven't been initialized yet.
+//!
+//! While a node catches up with the network in the background, its peers are already sending it
+//! messages for eras it hasn't reached.  Those are kept here, within a per-peer and a total byte
+//! budget, until the era supervisor has initialized their eras.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use datasize::DataSize;
+
+use casper_types::EraId;
+
+use crate::{components::consensus::config::CatchUpConfig, types::NodeId};
+
+/// The result of offering a message to the buffer.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum BufferOutcome {
+    /// The message was buffered.
+    Buffered,
+    /// The message was buffered, and enough distinct peers have now sent messages for an era
+    /// beyond our next one that we have evidently fallen behind the network.
+    FellBehind,
+    /// The message was dropped because its era is too far ahead of ours.
+    TooFarAhead,
+    /// The message was dropped because it would exceed the sender's or the total byte budget.
+    BudgetExceeded,
+}
+
+/// The messages buffered for a single era.
+#[derive(DataSize, Debug, Default)]
+struct EraMessages {
+    /// The messages with their senders, in the order they were received.
+    messages: Vec<(NodeId, Vec<u8>)>,
+    /// The distinct senders of the messages.
+    senders: HashSet<NodeId>,
+}
+
+/// Consensus messages for future eras, with their senders.
+#[derive(DataSize, Debug, Default)]
+pub(super) struct FutureEraMessages {
+    /// The buffered messages, by era.
+    eras: BTreeMap<EraId, EraMessages>,
+    /// The total size of the payloads buffered per sender.
+    bytes_by_sender: HashMap<NodeId, usize>,
+    /// The total size of all buffered payloads.
+    total_bytes: usize,
+}
+
+impl FutureEraMessages {
+    /// Buffers a message for `era_id`, if it is within the configured limits.
+    ///
+    /// Messages for the next era can arrive shortly before we have executed our current era's
+    /// switch block, so we only consider ourselves behind once `config.min_peers_ahead` distinct
+    /// peers have sent messages for a single era further ahead than that.  That way a single peer
+    /// can't make us start catching up.
+    pub(super) fn push(
+        &mut self,
+        config: &CatchUpConfig,
+        current_era: EraId,
+        sender: NodeId,
+        era_id: EraId,
+        payload: Vec<u8>,
+    ) -> BufferOutcome {
+        if era_id > current_era.saturating_add(config.max_eras_behind) {
+            return BufferOutcome::TooFarAhead;
+        }
+        let size = payload.len();
+        let sender_bytes = self
+            .bytes_by_sender
+            .get(&sender)
+            .copied()
+            .unwrap_or_default();
+        if sender_bytes.saturating_add(size) > config.max_buffered_bytes_per_peer
+            || self.total_bytes.saturating_add(size) > config.max_buffered_bytes
+        {
+            return BufferOutcome::BudgetExceeded;
+        }
+        self.bytes_by_sender
+            .insert(sender, sender_bytes.saturating_add(size));
+        self.total_bytes = self.total_bytes.saturating_add(size);
+
+        let era_messages = self.eras.entry(era_id).or_default();
+        era_messages.messages.push((sender, payload));
+        let is_new_sender = era_messages.senders.insert(sender);
+        if is_new_sender
+            && era_messages.senders.len() == config.min_peers_ahead
+            && era_id > current_era.successor()
+        {
+            BufferOutcome::FellBehind
+        } else {
+            BufferOutcome::Buffered
+        }
+    }
+
+    /// Removes and returns the messages for all eras up to and including `current_era`, in order
+    /// of era and then in the order they were received.
+    pub(super) fn take_ready(
+        &mut self,
+        current_era: EraId,
+    ) -> BTreeMap<EraId, Vec<(NodeId, Vec<u8>)>> {
+        let still_future = self.eras.split_off(&current_era.successor());
+        let ready = std::mem::replace(&mut self.eras, still_future);
+        ready
+            .into_iter()
+            .map(|(era_id, era_messages)| {
+                for (sender, payload) in &era_messages.messages {
+                    self.release(sender, payload.len());
+                }
+                (era_id, era_messages.messages)
+            })
+            .collect()
+    }
+
+    /// Returns the total size of all buffered payloads.
+    #[cfg(test)]
+    fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    /// Releases `size` bytes of the budget of `sender`.
+    fn release(&mut self, sender: &NodeId, size: usize) {
+        self.total_bytes = self.total_bytes.saturating_sub(size);
+        if let Some(sender_bytes) = self.bytes_by_sender.get_mut(sender) {
+            *sender_bytes = sender_bytes.saturating_sub(size);
+            if *sender_bytes == 0 {
+                self.bytes_by_sender.remove(sender);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CatchUpConfig {
+        CatchUpConfig {
+            enabled: true,
+            max_eras_behind: 3,
+            max_buffered_bytes: 100,
+            max_buffered_bytes_per_peer: 40,
+            min_peers_ahead: 2,
+        }
+    }
+
+    #[test]
+    fn should_buffer_within_budgets() {
+        let mut rng = crate::new_rng();
+        let config = config();
+        let mut buffer = FutureEraMessages::default();
+        let current_era = EraId::from(5);
+        let next_era = current_era.successor();
+        let peers: Vec<_> = (0..3).map(|_| NodeId::random(&mut rng)).collect();
+
+        // Messages for eras too far ahead aren't buffered.
+        assert_eq!(
+            buffer.push(&config, current_era, peers[0], EraId::from(9), vec![0; 1]),
+            BufferOutcome::TooFarAhead
+        );
+
+        // A single peer can't use up more than its own budget.
+        assert_eq!(
+            buffer.push(&config, current_era, peers[0], next_era, vec![0; 30]),
+            BufferOutcome::Buffered
+        );
+        assert_eq!(
+            buffer.push(&config, current_era, peers[0], next_era, vec![0; 11]),
+            BufferOutcome::BudgetExceeded
+        );
+        assert_eq!(
+            buffer.push(&config, current_era, peers[0], next_era, vec![0; 10]),
+            BufferOutcome::Buffered
+        );
+
+        // Nor can all peers together exceed the total budget.
+        assert_eq!(
+            buffer.push(&config, current_era, peers[1], next_era, vec![0; 40]),
+            BufferOutcome::Buffered
+        );
+        assert_eq!(
+            buffer.push(&config, current_era, peers[2], next_era, vec![0; 21]),
+            BufferOutcome::BudgetExceeded
+        );
+        assert_eq!(
+            buffer.push(&config, current_era, peers[2], next_era, vec![0; 20]),
+            BufferOutcome::Buffered
+        );
+        assert_eq!(buffer.total_bytes(), 100);
+
+        // Handling the messages frees up the budgets again.
+        assert_eq!(buffer.take_ready(next_era)[&next_era].len(), 4);
+        assert_eq!(buffer.total_bytes(), 0);
+        assert_eq!(
+            buffer.push(&config, next_era, peers[0], next_era, vec![0; 40]),
+            BufferOutcome::Buffered
+        );
+    }
+
+    #[test]
+    fn should_replay_messages_in_order_once_era_is_reached() {
+        let mut rng = crate::new_rng();
+        let config = config();
+        let mut buffer = FutureEraMessages::default();
+        let current_era = EraId::from(5);
+        let peer0 = NodeId::random(&mut rng);
+        let peer1 = NodeId::random(&mut rng);
+
+        buffer.push(&config, current_era, peer0, EraId::from(7), vec![3]);
+        buffer.push(&config, current_era, peer0, EraId::from(6), vec![1]);
+        buffer.push(&config, current_era, peer1, EraId::from(6), vec![2]);
+
+        // Nothing is ready before its era has been reached.
+        assert!(buffer.take_ready(current_era).is_empty());
+
+        let ready = buffer.take_ready(EraId::from(6));
+        let expected: BTreeMap<_, _> =
+            vec![(EraId::from(6), vec![(peer0, vec![1]), (peer1, vec![2])])]
+                .into_iter()
+                .collect();
+        assert_eq!(ready, expected);
+
+        // Eras that were skipped are returned as well, so the caller can drop them.
+        let ready = buffer.take_ready(EraId::from(8));
+        let expected: BTreeMap<_, _> = vec![(EraId::from(7), vec![(peer0, vec![3])])]
+            .into_iter()
+            .collect();
+        assert_eq!(ready, expected);
+        assert!(buffer.take_ready(EraId::from(8)).is_empty());
+    }
+
+    #[test]
+    fn should_only_fall_behind_once_enough_peers_are_ahead() {
+        let mut rng = crate::new_rng();
+        let config = config();
+        let mut buffer = FutureEraMessages::default();
+        let current_era = EraId::from(5);
+        let peer0 = NodeId::random(&mut rng);
+        let peer1 = NodeId::random(&mut rng);
+        let peer2 = NodeId::random(&mut rng);
+
+        // Messages for the next era don't mean we're behind, no matter how many peers send them.
+        assert_eq!(
+            buffer.push(&config, current_era, peer0, EraId::from(6), vec![0]),
+            BufferOutcome::Buffered
+        );
+        assert_eq!(
+            buffer.push(&config, current_era, peer1, EraId::from(6), vec![0]),
+            BufferOutcome::Buffered
+        );
+
+        // A single peer claiming to be further ahead isn't enough, however often it does.
+        assert_eq!(
+            buffer.push(&config, current_era, peer0, EraId::from(7), vec![0]),
+            BufferOutcome::Buffered
+        );
+        assert_eq!(
+            buffer.push(&config, current_era, peer0, EraId::from(7), vec![0]),
+            BufferOutcome::Buffered
+        );
+
+        // The second peer is, but only the first time.
+        assert_eq!(
+            buffer.push(&config, current_era, peer1, EraId::from(7), vec![0]),
+            BufferOutcome::FellBehind
+        );
+        assert_eq!(
+            buffer.push(&config, current_era, peer2, EraId::from(7), vec![0]),
+            BufferOutcome::Buffered
+        );
+    }
+}
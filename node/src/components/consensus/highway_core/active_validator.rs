@@ -7,7 +7,7 @@ use std::{
 };
 
 use datasize::DataSize;
-use tracing::{error, info, trace, warn};
+use tracing::{error, error_span, info, trace, warn};
 
 use casper_types::{TimeDiff, Timestamp};
 
@@ -128,7 +128,7 @@ impl<C: Context> ActiveValidator<C> {
             paused: false,
         };
         let mut effects = av.schedule_timer(start_time, state);
-        effects.push(av.send_ping(current_time, instance_id));
+        effects.extend(av.send_ping(current_time, instance_id));
         (av, effects)
     }
 
@@ -190,6 +190,7 @@ impl<C: Context> ActiveValidator<C> {
         let r_exp = self.round_exp(state, timestamp);
         let r_id = state::round_id(timestamp, r_exp);
         let r_len = state::round_len(r_exp);
+        let _entered = error_span!("round", round_id = %r_id).entered();
         // Only create new units if enough validators are online.
         if !self.paused && self.enough_validators_online(state, timestamp) {
             if timestamp == r_id && state.leader(r_id) == self.vidx {
@@ -216,15 +217,19 @@ impl<C: Context> ActiveValidator<C> {
         let one_max_round_ago = timestamp.saturating_sub(state.params().max_round_length());
         if !state.has_ping(self.vidx, one_max_round_ago + 1.into()) {
             warn!(%timestamp, "too many validators offline, sending ping");
-            effects.push(self.send_ping(timestamp, instance_id));
+            effects.extend(self.send_ping(timestamp, instance_id));
         }
         effects
     }
 
-    /// Creates a Ping vertex.
-    pub(crate) fn send_ping(&self, timestamp: Timestamp, instance_id: C::InstanceId) -> Effect<C> {
-        let ping = Ping::new(self.vidx, timestamp, instance_id, &self.secret);
-        Effect::NewVertex(ValidVertex(Vertex::Ping(ping)))
+    /// Creates a Ping vertex, unless signing it fails.
+    pub(crate) fn send_ping(
+        &self,
+        timestamp: Timestamp,
+        instance_id: C::InstanceId,
+    ) -> Option<Effect<C>> {
+        let ping = Ping::new(self.vidx, timestamp, instance_id, &self.secret)?;
+        Some(Effect::NewVertex(ValidVertex(Vertex::Ping(ping))))
     }
 
     /// Returns whether enough validators are online to finalize values with the target fault
@@ -267,8 +272,9 @@ impl<C: Context> ActiveValidator<C> {
             }
         };
         if self.should_endorse(uhash, state) {
-            let endorsement = self.endorse(uhash);
-            effects.extend(vec![Effect::NewVertex(ValidVertex(endorsement))]);
+            if let Some(endorsement) = self.endorse(uhash) {
+                effects.push(Effect::NewVertex(ValidVertex(endorsement)));
+            }
         }
         effects
     }
@@ -291,7 +297,7 @@ impl<C: Context> ActiveValidator<C> {
                 let unit = state.unit(v);
                 unit.new_hash_obs(state, vidx)
             })
-            .map(|v| self.endorse(v))
+            .filter_map(|v| self.endorse(v))
             .map(|endorsement| Effect::NewVertex(ValidVertex(endorsement)))
             .collect()
     }
@@ -458,7 +464,7 @@ impl<C: Context> ActiveValidator<C> {
             endorsed,
         }
         .into_hashed();
-        let swunit = SignedWireUnit::new(hwunit, &self.secret);
+        let swunit = SignedWireUnit::new(hwunit, &self.secret)?;
         write_last_unit(&self.unit_file, swunit.clone()).unwrap_or_else(|err| {
             panic!(
                 "should successfully write unit's hash to {:?}, got {:?}",
@@ -559,11 +565,13 @@ impl<C: Context> ActiveValidator<C> {
                 .any(|(vidx, _)| state.is_faulty(vidx) && unit.new_hash_obs(state, vidx))
     }
 
-    /// Creates endorsement of the `vhash`.
-    fn endorse(&self, vhash: &C::Hash) -> Vertex<C> {
+    /// Creates endorsement of the `vhash`, unless signing it fails.
+    fn endorse(&self, vhash: &C::Hash) -> Option<Vertex<C>> {
         let endorsement = Endorsement::new(*vhash, self.vidx);
-        let signature = self.secret.sign(&endorsement.hash());
-        Vertex::Endorsements(SignedEndorsement::new(endorsement, signature).into())
+        let signature = self.secret.sign(&endorsement.hash())?;
+        Some(Vertex::Endorsements(
+            SignedEndorsement::new(endorsement, signature).into(),
+        ))
     }
 
     /// Returns a panorama that is valid to use in our own unit at the given timestamp.
@@ -988,7 +996,8 @@ mod tests {
             TEST_INSTANCE_ID,
         );
 
-        let ping = Vertex::Ping(Ping::new(ALICE, 500.into(), TEST_INSTANCE_ID, &ALICE_SEC));
+        let ping =
+            Vertex::Ping(Ping::new(ALICE, 500.into(), TEST_INSTANCE_ID, &ALICE_SEC).unwrap());
 
         // The ping is suspicious if it is newer than the latest ping (or unit) that has been added
         // to the state.
@@ -146,15 +146,16 @@ where
 }
 
 impl<C: Context> SignedWireUnit<C> {
+    /// Signs the unit, or returns `None` if signing failed.
     pub(crate) fn new(
         hashed_wire_unit: HashedWireUnit<C>,
         secret_key: &C::ValidatorSecret,
-    ) -> Self {
-        let signature = secret_key.sign(&hashed_wire_unit.hash);
-        SignedWireUnit {
+    ) -> Option<Self> {
+        let signature = secret_key.sign(&hashed_wire_unit.hash)?;
+        Some(SignedWireUnit {
             hashed_wire_unit,
             signature,
-        }
+        })
     }
 
     pub(crate) fn wire_unit(&self) -> &WireUnit<C> {
@@ -335,20 +336,20 @@ where
 }
 
 impl<C: Context> Ping<C> {
-    /// Creates a new signed ping.
+    /// Creates a new signed ping, or returns `None` if signing failed.
     pub(crate) fn new(
         creator: ValidatorIndex,
         timestamp: Timestamp,
         instance_id: C::InstanceId,
         sk: &C::ValidatorSecret,
-    ) -> Self {
-        let signature = sk.sign(&Self::hash(creator, timestamp, instance_id));
-        Ping {
+    ) -> Option<Self> {
+        let signature = sk.sign(&Self::hash(creator, timestamp, instance_id))?;
+        Some(Ping {
             creator,
             timestamp,
             instance_id,
             signature,
-        }
+        })
     }
 
     /// The creator who signals that it is online.
@@ -48,8 +48,8 @@ impl ValidatorSecret for TestSecret {
     type Hash = u64;
     type Signature = u64;
 
-    fn sign(&self, data: &Self::Hash) -> Self::Signature {
-        data + u64::from(self.0)
+    fn sign(&self, data: &Self::Hash) -> Option<Self::Signature> {
+        Some(data + u64::from(self.0))
     }
 }
 
@@ -187,12 +187,12 @@ fn add_unit() -> Result<(), AddUnitError<TestContext>> {
         round_exp: 4u8,
         endorsed: BTreeSet::new(),
     };
-    let unit = SignedWireUnit::new(wunit.clone().into_hashed(), &BOB_SEC);
+    let unit = SignedWireUnit::new(wunit.clone().into_hashed(), &BOB_SEC).unwrap();
     let maybe_err = state.add_unit(unit).err().map(unit_err);
     assert_eq!(Some(UnitError::SequenceNumber), maybe_err);
     // Still not valid: This would be the third unit in the first round.
     wunit.seq_number = 2;
-    let unit = SignedWireUnit::new(wunit.into_hashed(), &BOB_SEC);
+    let unit = SignedWireUnit::new(wunit.into_hashed(), &BOB_SEC).unwrap();
     let maybe_err = state.add_unit(unit).err().map(unit_err);
     assert_eq!(Some(UnitError::ThreeUnitsInRound), maybe_err);
 
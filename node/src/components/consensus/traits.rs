@@ -45,7 +45,9 @@ pub trait ValidatorSecret: Send + DataSize {
 
     type Signature: Eq + PartialEq + Clone + Debug + Hash + Serialize + DeserializeOwned + DataSize;
 
-    fn sign(&self, hash: &Self::Hash) -> Self::Signature;
+    /// Signs the given hash, or returns `None` if signing failed, e.g. because the device holding
+    /// the key is unavailable.  The cause of the failure is logged by the implementation.
+    fn sign(&self, hash: &Self::Hash) -> Option<Self::Signature>;
 }
 
 /// The collection of types the user can choose for cryptography, IDs, transactions, etc.
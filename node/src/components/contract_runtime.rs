@@ -4,13 +4,17 @@ mod config;
 mod error;
 mod metrics;
 mod operations;
+mod trie_pruner;
 mod types;
 
 use std::{
     collections::BTreeMap,
     fmt::{self, Debug, Display, Formatter},
     path::Path,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
     time::Instant,
 };
 
@@ -33,33 +37,41 @@ use casper_execution_engine::{
     storage::{
         global_state::lmdb::LmdbGlobalState,
         transaction_source::lmdb::LmdbEnvironment,
-        trie::{TrieOrChunk, TrieOrChunkId},
+        trie::{hash_bytes_into_chunks_if_necessary, TrieOrChunk, TrieOrChunkId},
         trie_store::lmdb::LmdbTrieStore,
     },
 };
 use casper_hashing::Digest;
-use casper_types::{bytesrepr::Bytes, EraId, ProtocolVersion, Timestamp};
+use casper_types::{bytesrepr::Bytes, EraId, ProtocolVersion, TimeDiff, Timestamp};
 
 use crate::{
-    components::{contract_runtime::types::StepEffectAndUpcomingEraValidators, Component},
+    components::{
+        contract_runtime::types::StepEffectAndUpcomingEraValidators, Component, HealthCheck,
+    },
     effect::{
         announcements::{ContractRuntimeAnnouncement, ControlAnnouncement},
         incoming::{TrieDemand, TrieRequest, TrieRequestIncoming},
-        requests::{ContractRuntimeRequest, MarkBlockCompletedRequest, NetworkRequest},
+        requests::{
+            ContractRuntimeRequest, MarkBlockCompletedRequest, NetworkRequest, StateStoreRequest,
+            StorageRequest,
+        },
         EffectBuilder, EffectExt, Effects,
     },
     fatal,
     protocol::Message,
-    types::{BlockHash, BlockHeader, Chainspec, ChainspecRawBytes, Deploy, FinalizedBlock},
+    types::{
+        chainspec::PricingConfig, BlockHash, BlockHeader, Chainspec, ChainspecRawBytes,
+        ComponentHealth, Deploy, FinalizedBlock, SyncMode,
+    },
     NodeRng,
 };
 pub(crate) use config::Config;
-pub(crate) use error::{BlockExecutionError, ConfigError};
+pub(crate) use error::{BlockExecutionError, ConfigError, SpeculativeExecutionError};
 use metrics::Metrics;
 pub use operations::execute_finalized_block;
 pub(crate) use types::{BlockAndExecutionEffects, EraValidatorsRequest};
 
-use self::operations::execute_only;
+use self::{operations::execute_only, trie_pruner::TriePruner};
 
 use super::fetcher::FetchedOrNotFound;
 
@@ -83,6 +95,14 @@ const MAX_PARALLEL_INTENSIVE_TASKS: usize = 4;
 static INTENSIVE_TASKS_SEMAPHORE: Lazy<tokio::sync::Semaphore> =
     Lazy::new(|| tokio::sync::Semaphore::new(MAX_PARALLEL_INTENSIVE_TASKS));
 
+/// Number of resource intensive tasks waiting for the semaphore.
+static WAITING_INTENSIVE_TASKS: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of resource intensive tasks waiting to be run.
+pub(crate) fn waiting_intensive_tasks() -> usize {
+    WAITING_INTENSIVE_TASKS.load(Ordering::Relaxed)
+}
+
 /// Asynchronously runs a resource intensive task.
 /// At most `MAX_PARALLEL_INTENSIVE_TASKS` are being run in parallel at any time.
 ///
@@ -93,8 +113,10 @@ where
     T: 'static + Send + FnOnce() -> V,
     V: 'static + Send,
 {
+    WAITING_INTENSIVE_TASKS.fetch_add(1, Ordering::Relaxed);
     // This will never panic since the semaphore is never closed.
     let _permit = INTENSIVE_TASKS_SEMAPHORE.acquire().await.unwrap();
+    WAITING_INTENSIVE_TASKS.fetch_sub(1, Ordering::Relaxed);
     tokio::task::spawn_blocking(task)
         .await
         .expect("task panicked")
@@ -163,6 +185,10 @@ impl ExecutionPreState {
     }
 }
 
+/// The number of finalized blocks waiting for execution above which the contract runtime reports
+/// itself degraded.
+const MAX_HEALTHY_EXEC_QUEUE_LENGTH: usize = 10;
+
 type ExecQueue = Arc<Mutex<BTreeMap<u64, (FinalizedBlock, Vec<Deploy>, Vec<Deploy>)>>>;
 
 #[derive(Debug, From, Serialize)]
@@ -197,11 +223,18 @@ pub(crate) struct ContractRuntime {
     metrics: Arc<Metrics>,
     protocol_version: ProtocolVersion,
     verifiable_chunked_hash_activation: EraId,
+    /// The duration after which a still-running step at the end of an era is reported.
+    step_soft_deadline: TimeDiff,
+    /// The chainspec's pricing rules, which speculatively executed deploys must comply with.
+    pricing_config: PricingConfig,
 
     /// Finalized blocks waiting for their pre-state hash to start executing.
     exec_queue: ExecQueue,
     /// Cached instance of a [`SystemContractRegistry`].
     system_contract_registry: Option<SystemContractRegistry>,
+    /// Pruner of tries which are no longer reachable from retained global state.
+    #[data_size(skip)]
+    trie_pruner: Arc<TriePruner>,
 }
 
 impl Debug for ContractRuntime {
@@ -217,6 +250,8 @@ where
         + From<ControlAnnouncement>
         + From<NetworkRequest<Message>>
         + From<MarkBlockCompletedRequest>
+        + From<StorageRequest>
+        + From<StateStoreRequest>
         + Send,
 {
     type Event = Event;
@@ -240,6 +275,20 @@ where
     }
 }
 
+impl HealthCheck for ContractRuntime {
+    fn health(&self) -> ComponentHealth {
+        let queued = match self.exec_queue.lock() {
+            Ok(exec_queue) => exec_queue.len(),
+            Err(_) => return ComponentHealth::failed("execution queue mutex poisoned"),
+        };
+        if queued > MAX_HEALTHY_EXEC_QUEUE_LENGTH {
+            ComponentHealth::degraded(format!("{} blocks waiting for execution", queued))
+        } else {
+            ComponentHealth::healthy()
+        }
+    }
+}
+
 impl ContractRuntime {
     /// Handles an incoming request to get a trie.
     fn handle_trie_request<REv>(
@@ -310,6 +359,8 @@ impl ContractRuntime {
             + From<ContractRuntimeAnnouncement>
             + From<ControlAnnouncement>
             + From<MarkBlockCompletedRequest>
+            + From<StorageRequest>
+            + From<StateStoreRequest>
             + Send,
     {
         match request {
@@ -456,13 +507,20 @@ impl ContractRuntime {
                 trace!(?trie_bytes, "put_trie request");
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let trie_pruner = Arc::clone(&self.trie_pruner);
                 async move {
                     let correlation_id = CorrelationId::new();
                     let start = Instant::now();
+                    let commit_guard = trie_pruner.commit_guard().await;
                     let result = engine_state.put_trie_and_find_missing_descendant_trie_keys(
                         correlation_id,
                         &*trie_bytes,
                     );
+                    if result.is_ok() {
+                        trie_pruner
+                            .record_new_trie_key(hash_bytes_into_chunks_if_necessary(&trie_bytes));
+                    }
+                    drop(commit_guard);
                     // PERF: this *could* be called only periodically.
                     if let Err(lmdb_error) = engine_state.flush_environment() {
                         fatal!(
@@ -496,8 +554,11 @@ impl ContractRuntime {
                 );
                 let engine_state = Arc::clone(&self.engine_state);
                 let metrics = Arc::clone(&self.metrics);
+                let trie_pruner = Arc::clone(&self.trie_pruner);
                 let verifiable_chunked_hash_activation = self.verifiable_chunked_hash_activation();
+                let step_soft_deadline = self.step_soft_deadline;
                 async move {
+                    let commit_guard = trie_pruner.commit_guard().await;
                     let result = run_intensive_task(move || {
                         execute_finalized_block(
                             engine_state.as_ref(),
@@ -508,9 +569,16 @@ impl ContractRuntime {
                             deploys,
                             transfers,
                             verifiable_chunked_hash_activation,
+                            Some(step_soft_deadline.into()),
                         )
                     })
                     .await;
+                    if let Ok(block_and_execution_effects) = &result {
+                        trie_pruner.record_new_trie_key(
+                            *block_and_execution_effects.block.header().state_root_hash(),
+                        );
+                    }
+                    drop(commit_guard);
                     trace!(?result, "execute block response");
                     responder.respond(result).await
                 }
@@ -527,6 +595,7 @@ impl ContractRuntime {
                 let metrics = Arc::clone(&self.metrics);
                 let exec_queue = Arc::clone(&self.exec_queue);
                 let execution_pre_state = Arc::clone(&self.execution_pre_state);
+                let trie_pruner = Arc::clone(&self.trie_pruner);
                 let protocol_version = self.protocol_version;
                 if self.execution_pre_state.lock().unwrap().next_block_height
                     == finalized_block.height()
@@ -537,12 +606,14 @@ impl ContractRuntime {
                             metrics,
                             exec_queue,
                             execution_pre_state,
+                            trie_pruner,
                             effect_builder,
                             protocol_version,
                             finalized_block,
                             deploys,
                             transfers,
                             self.verifiable_chunked_hash_activation(),
+                            self.step_soft_deadline,
                         )
                         .ignore(),
                     )
@@ -595,13 +666,17 @@ impl ContractRuntime {
                 deploy,
                 responder,
             } => {
+                // A deploy which the network wouldn't accept isn't worth executing.
+                if let Err(error) = deploy.is_pricing_compliant(&self.pricing_config) {
+                    return responder.respond(Err(error.into())).ignore();
+                }
                 let engine_state = Arc::clone(&self.engine_state);
                 async move {
                     let result = run_intensive_task(move || {
                         execute_only(engine_state.as_ref(), execution_prestate, (*deploy).into())
                     })
                     .await;
-                    responder.respond(result).await
+                    responder.respond(result.map_err(Into::into)).await
                 }
                 .ignore()
             }
@@ -615,6 +690,7 @@ impl ContractRuntime {
         protocol_version: ProtocolVersion,
         storage_dir: &Path,
         contract_runtime_config: &Config,
+        sync_mode: SyncMode,
         wasm_config: WasmConfig,
         system_config: SystemConfig,
         max_associated_keys: u32,
@@ -623,6 +699,8 @@ impl ContractRuntime {
         strict_argument_checking: bool,
         registry: &Registry,
         verifiable_chunked_hash_activation: EraId,
+        min_round_length: TimeDiff,
+        pricing_config: PricingConfig,
     ) -> Result<Self, ConfigError> {
         // TODO: This is bogus, get rid of this
         let execution_pre_state = Arc::new(Mutex::new(ExecutionPreState {
@@ -660,14 +738,19 @@ impl ContractRuntime {
 
         let metrics = Arc::new(Metrics::new(registry)?);
 
+        let trie_pruner = Arc::new(TriePruner::new(contract_runtime_config, sync_mode));
+
         Ok(ContractRuntime {
             execution_pre_state,
             engine_state,
             metrics,
             protocol_version,
             verifiable_chunked_hash_activation,
+            step_soft_deadline: contract_runtime_config.step_soft_deadline(min_round_length),
+            pricing_config,
             exec_queue: Arc::new(Mutex::new(BTreeMap::new())),
             system_contract_registry: None,
+            trie_pruner,
         })
     }
 
@@ -780,34 +863,43 @@ impl ContractRuntime {
         metrics: Arc<Metrics>,
         exec_queue: ExecQueue,
         execution_pre_state: Arc<Mutex<ExecutionPreState>>,
+        trie_pruner: Arc<TriePruner>,
         effect_builder: EffectBuilder<REv>,
         protocol_version: ProtocolVersion,
         finalized_block: FinalizedBlock,
         deploys: Vec<Deploy>,
         transfers: Vec<Deploy>,
         verifiable_chunked_hash_activation: EraId,
+        step_soft_deadline: TimeDiff,
     ) where
         REv: From<ContractRuntimeRequest>
             + From<ContractRuntimeAnnouncement>
             + From<ControlAnnouncement>
             + From<MarkBlockCompletedRequest>
+            + From<StorageRequest>
+            + From<StateStoreRequest>
             + Send,
     {
         let current_execution_pre_state = execution_pre_state.lock().unwrap().clone();
+        let pre_state_root_hash = current_execution_pre_state.pre_state_root_hash;
+        let commit_guard = trie_pruner.commit_guard().await;
+        let execution_engine_state = Arc::clone(&engine_state);
+        let execution_metrics = Arc::clone(&metrics);
         let BlockAndExecutionEffects {
             block,
             execution_results,
             maybe_step_effect_and_upcoming_era_validators,
         } = match run_intensive_task(move || {
             execute_finalized_block(
-                engine_state.as_ref(),
-                Some(metrics),
+                execution_engine_state.as_ref(),
+                Some(execution_metrics),
                 protocol_version,
                 current_execution_pre_state,
                 finalized_block,
                 deploys,
                 transfers,
                 verifiable_chunked_hash_activation,
+                Some(step_soft_deadline.into()),
             )
         })
         .await
@@ -815,6 +907,12 @@ impl ContractRuntime {
             Ok(block_and_execution_effects) => block_and_execution_effects,
             Err(error) => return fatal!(effect_builder, "{}", error).await,
         };
+        let state_root_hash = *block.header().state_root_hash();
+        trie_pruner.record_new_trie_key(state_root_hash);
+        // Pruning only starts after switch blocks; it has to begin before releasing the guard so
+        // that every subsequently written trie is recorded.
+        let start_trie_pruning = block.header().is_switch_block() && trie_pruner.try_begin();
+        drop(commit_guard);
 
         let new_execution_pre_state = ExecutionPreState::from_block_header(
             block.header(),
@@ -856,6 +954,17 @@ impl ContractRuntime {
                 .enqueue_block_for_execution(finalized_block, deploys, transfers)
                 .await
         }
+
+        if start_trie_pruning {
+            trie_pruner
+                .run(
+                    effect_builder,
+                    engine_state,
+                    metrics,
+                    vec![pre_state_root_hash, state_root_hash],
+                )
+                .await;
+        }
     }
 
     /// Reads the trie (or chunk of a trie) under the given key and index.
@@ -2,22 +2,29 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::shared::utils;
+use casper_types::TimeDiff;
+
+use crate::{types::SyncMode, utils::byte_size};
 
 const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 const DEFAULT_MANUAL_SYNC_ENABLED: bool = true;
+const DEFAULT_TRIE_PRUNING_ENABLED: bool = false;
+const DEFAULT_TRIE_PRUNING_BATCH_SIZE: usize = 10_000;
+const DEFAULT_TRIE_PRUNING_BATCH_DELAY: TimeDiff = TimeDiff::from_seconds(1);
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// The maximum size of the database to use for the global state store.
     ///
     /// Defaults to 805,306,368,000 == 750 GiB.
     ///
     /// The size should be a multiple of the OS page size.
+    #[serde(deserialize_with = "byte_size::deserialize_option")]
     max_global_state_size: Option<usize>,
     /// The maximum number of readers to use for the global state store.
     ///
@@ -31,6 +38,25 @@ pub struct Config {
     ///
     /// Defaults to `false`.
     enable_manual_sync: Option<bool>,
+    /// Enable pruning of tries which are unreachable from the state roots of retained blocks.
+    /// Ignored in archival sync mode, where all global state is retained.
+    ///
+    /// Defaults to `false`.
+    enable_trie_pruning: Option<bool>,
+    /// The maximum number of tries marked as reachable or scanned for deletion in a single
+    /// transaction by trie pruning.
+    ///
+    /// Defaults to 10,000.
+    trie_pruning_batch_size: Option<usize>,
+    /// The pause between two consecutive batches of trie pruning.
+    ///
+    /// Defaults to 1 second.
+    trie_pruning_batch_delay: Option<TimeDiff>,
+    /// The duration after which a warning is logged if the step at the end of an era is still
+    /// running.
+    ///
+    /// Defaults to half of the minimum round length specified in the chainspec.
+    step_soft_deadline: Option<TimeDiff>,
 }
 
 impl Config {
@@ -54,6 +80,30 @@ impl Config {
         self.enable_manual_sync
             .unwrap_or(DEFAULT_MANUAL_SYNC_ENABLED)
     }
+
+    pub(crate) fn trie_pruning_enabled(&self, sync_mode: SyncMode) -> bool {
+        match sync_mode {
+            SyncMode::Archival => false,
+            SyncMode::Recent => self
+                .enable_trie_pruning
+                .unwrap_or(DEFAULT_TRIE_PRUNING_ENABLED),
+        }
+    }
+
+    pub(crate) fn trie_pruning_batch_size(&self) -> usize {
+        self.trie_pruning_batch_size
+            .unwrap_or(DEFAULT_TRIE_PRUNING_BATCH_SIZE)
+            .max(1)
+    }
+
+    pub(crate) fn trie_pruning_batch_delay(&self) -> TimeDiff {
+        self.trie_pruning_batch_delay
+            .unwrap_or(DEFAULT_TRIE_PRUNING_BATCH_DELAY)
+    }
+
+    pub(crate) fn step_soft_deadline(&self, min_round_length: TimeDiff) -> TimeDiff {
+        self.step_soft_deadline.unwrap_or(min_round_length / 2)
+    }
 }
 
 impl Default for Config {
@@ -63,6 +113,10 @@ impl Default for Config {
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
             enable_manual_sync: Some(DEFAULT_MANUAL_SYNC_ENABLED),
+            enable_trie_pruning: Some(DEFAULT_TRIE_PRUNING_ENABLED),
+            trie_pruning_batch_size: Some(DEFAULT_TRIE_PRUNING_BATCH_SIZE),
+            trie_pruning_batch_delay: Some(DEFAULT_TRIE_PRUNING_BATCH_DELAY),
+            step_soft_deadline: None,
         }
     }
 }
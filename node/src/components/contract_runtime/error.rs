@@ -10,7 +10,7 @@ use casper_execution_engine::{
 
 use crate::{
     components::contract_runtime::ExecutionPreState,
-    types::{error::BlockCreationError, FinalizedBlock},
+    types::{error::BlockCreationError, DeployConfigurationFailure, FinalizedBlock},
 };
 use casper_execution_engine::core::engine_state::GetEraValidatorsError;
 
@@ -28,6 +28,17 @@ pub(crate) enum ConfigError {
     EngineState(#[from] EngineStateError),
 }
 
+/// An error during speculative execution of a deploy.
+#[derive(Debug, Error)]
+pub(crate) enum SpeculativeExecutionError {
+    /// The deploy doesn't comply with the chainspec's pricing rules, so it wasn't executed.
+    #[error("deploy does not comply with pricing rules: {0}")]
+    InvalidDeploy(#[from] DeployConfigurationFailure),
+    /// A core error thrown by the execution engine.
+    #[error(transparent)]
+    EngineState(#[from] EngineStateError),
+}
+
 /// An error during block execution.
 #[derive(Debug, Error, Serialize)]
 pub enum BlockExecutionError {
@@ -1,4 +1,4 @@
-use prometheus::{self, Gauge, Histogram, IntGauge, Registry};
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
 
 use crate::{unregister_metric, utils};
 
@@ -61,6 +61,30 @@ const EXEC_BLOCK_HELP: &str = "time in seconds to execute all deploys in a block
 const LATEST_COMMIT_STEP_NAME: &str = "contract_runtime_latest_commit_step";
 const LATEST_COMMIT_STEP_HELP: &str = "duration in seconds of latest commit step at era end";
 
+const STEP_DISTRIBUTE_REWARDS_NAME: &str = "contract_runtime_step_distribute_rewards";
+const STEP_DISTRIBUTE_REWARDS_HELP: &str =
+    "time in seconds to distribute rewards during the step at era end";
+
+const STEP_SLASH_NAME: &str = "contract_runtime_step_slash";
+const STEP_SLASH_HELP: &str = "time in seconds to slash validators during the step at era end";
+
+const STEP_RUN_AUCTION_NAME: &str = "contract_runtime_step_run_auction";
+const STEP_RUN_AUCTION_HELP: &str = "time in seconds to run the auction during the step at era end";
+
+const STEP_COMMIT_NAME: &str = "contract_runtime_step_commit";
+const STEP_COMMIT_HELP: &str =
+    "time in seconds to commit the effects of the step at era end to global state";
+
+const STEP_SOFT_DEADLINE_EXCEEDED_NAME: &str = "contract_runtime_step_soft_deadline_exceeded";
+const STEP_SOFT_DEADLINE_EXCEEDED_HELP: &str =
+    "number of steps at era end which were still running after their soft deadline";
+
+const PRUNED_TRIES_NAME: &str = "contract_runtime_pruned_tries";
+const PRUNED_TRIES_HELP: &str = "total number of tries deleted by trie pruning";
+
+const LATEST_TRIE_PRUNING_NAME: &str = "contract_runtime_latest_trie_pruning";
+const LATEST_TRIE_PRUNING_HELP: &str = "duration in seconds of latest completed trie pruning run";
+
 /// Metrics for the contract runtime component.
 #[derive(Debug)]
 pub struct Metrics {
@@ -79,6 +103,13 @@ pub struct Metrics {
     pub(super) chain_height: IntGauge,
     pub(super) exec_block: Histogram,
     pub(super) latest_commit_step: Gauge,
+    pub(super) step_distribute_rewards: Histogram,
+    pub(super) step_slash: Histogram,
+    pub(super) step_run_auction: Histogram,
+    pub(super) step_commit: Histogram,
+    pub(super) step_soft_deadline_exceeded: IntCounter,
+    pub(super) pruned_tries: IntCounter,
+    pub(super) latest_trie_pruning: Gauge,
     registry: Registry,
 }
 
@@ -103,6 +134,18 @@ impl Metrics {
         let latest_commit_step = Gauge::new(LATEST_COMMIT_STEP_NAME, LATEST_COMMIT_STEP_HELP)?;
         registry.register(Box::new(latest_commit_step.clone()))?;
 
+        let step_soft_deadline_exceeded = IntCounter::new(
+            STEP_SOFT_DEADLINE_EXCEEDED_NAME,
+            STEP_SOFT_DEADLINE_EXCEEDED_HELP,
+        )?;
+        registry.register(Box::new(step_soft_deadline_exceeded.clone()))?;
+
+        let pruned_tries = IntCounter::new(PRUNED_TRIES_NAME, PRUNED_TRIES_HELP)?;
+        registry.register(Box::new(pruned_tries.clone()))?;
+
+        let latest_trie_pruning = Gauge::new(LATEST_TRIE_PRUNING_NAME, LATEST_TRIE_PRUNING_HELP)?;
+        registry.register(Box::new(latest_trie_pruning.clone()))?;
+
         Ok(Metrics {
             run_execute: utils::register_histogram_metric(
                 registry,
@@ -181,9 +224,36 @@ impl Metrics {
                 registry,
                 EXEC_BLOCK_NAME,
                 EXEC_BLOCK_HELP,
-                common_buckets,
+                common_buckets.clone(),
             )?,
             latest_commit_step,
+            step_distribute_rewards: utils::register_histogram_metric(
+                registry,
+                STEP_DISTRIBUTE_REWARDS_NAME,
+                STEP_DISTRIBUTE_REWARDS_HELP,
+                common_buckets.clone(),
+            )?,
+            step_slash: utils::register_histogram_metric(
+                registry,
+                STEP_SLASH_NAME,
+                STEP_SLASH_HELP,
+                common_buckets.clone(),
+            )?,
+            step_run_auction: utils::register_histogram_metric(
+                registry,
+                STEP_RUN_AUCTION_NAME,
+                STEP_RUN_AUCTION_HELP,
+                common_buckets.clone(),
+            )?,
+            step_commit: utils::register_histogram_metric(
+                registry,
+                STEP_COMMIT_NAME,
+                STEP_COMMIT_HELP,
+                common_buckets,
+            )?,
+            step_soft_deadline_exceeded,
+            pruned_tries,
+            latest_trie_pruning,
             registry: registry.clone(),
         })
     }
@@ -206,5 +276,12 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.chain_height);
         unregister_metric!(self.registry, self.exec_block);
         unregister_metric!(self.registry, self.latest_commit_step);
+        unregister_metric!(self.registry, self.step_distribute_rewards);
+        unregister_metric!(self.registry, self.step_slash);
+        unregister_metric!(self.registry, self.step_run_auction);
+        unregister_metric!(self.registry, self.step_commit);
+        unregister_metric!(self.registry, self.step_soft_deadline_exceeded);
+        unregister_metric!(self.registry, self.pruned_tries);
+        unregister_metric!(self.registry, self.latest_trie_pruning);
     }
 }
@@ -1,4 +1,9 @@
-use std::{collections::BTreeMap, sync::Arc, time::Instant};
+use std::{
+    collections::BTreeMap,
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
 
 use itertools::Itertools;
 use tracing::{debug, trace, warn};
@@ -43,6 +48,7 @@ pub fn execute_finalized_block(
     deploys: Vec<Deploy>,
     transfers: Vec<Deploy>,
     verifiable_chunked_hash_activation: EraId,
+    step_soft_deadline: Option<Duration>,
 ) -> Result<BlockAndExecutionEffects, BlockExecutionError> {
     if finalized_block.height() != execution_pre_state.next_block_height {
         return Err(BlockExecutionError::WrongBlockHeight {
@@ -108,6 +114,7 @@ pub fn execute_finalized_block(
             let StepSuccess {
                 post_state_hash: _, // ignore the post-state-hash returned from scratch
                 execution_journal: step_execution_journal,
+                timings: _,
             } = commit_step(
                 &scratch_state, // engine_state
                 metrics.clone(),
@@ -116,6 +123,7 @@ pub fn execute_finalized_block(
                 era_report,
                 finalized_block.timestamp().millis(),
                 finalized_block.era_id().successor(),
+                step_soft_deadline,
             )?;
 
             state_root_hash =
@@ -315,6 +323,7 @@ where
     result
 }
 
+#[allow(clippy::too_many_arguments)]
 fn commit_step<S>(
     engine_state: &EngineState<S>,
     maybe_metrics: Option<Arc<Metrics>>,
@@ -323,6 +332,7 @@ fn commit_step<S>(
     era_report: &EraReport<PublicKey>,
     era_end_timestamp_millis: u64,
     next_era_id: EraId,
+    soft_deadline: Option<Duration>,
 ) -> Result<StepSuccess, StepError>
 where
     S: StateProvider + CommitProvider,
@@ -361,13 +371,73 @@ where
 
     // Have the EE commit the step.
     let correlation_id = CorrelationId::new();
+    let watchdog = soft_deadline
+        .map(|deadline| StepWatchdog::start(next_era_id, deadline, maybe_metrics.clone()));
     let start = Instant::now();
     let result = engine_state.commit_step(correlation_id, step_request);
+    let elapsed = start.elapsed();
+    drop(watchdog);
     if let Some(metrics) = maybe_metrics {
-        let elapsed = start.elapsed().as_secs_f64();
-        metrics.commit_step.observe(elapsed);
-        metrics.latest_commit_step.set(elapsed);
+        metrics.commit_step.observe(elapsed.as_secs_f64());
+        metrics.latest_commit_step.set(elapsed.as_secs_f64());
+        if let Ok(StepSuccess { timings, .. }) = &result {
+            metrics
+                .step_distribute_rewards
+                .observe(timings.distribute_rewards.as_secs_f64());
+            metrics.step_slash.observe(timings.slash.as_secs_f64());
+            metrics
+                .step_run_auction
+                .observe(timings.run_auction.as_secs_f64());
+            metrics.step_commit.observe(timings.commit.as_secs_f64());
+        }
+    }
+    if let Some(deadline) = soft_deadline {
+        if elapsed > deadline {
+            let timings = result.as_ref().ok().map(|success| success.timings);
+            warn!(
+                %next_era_id,
+                ?elapsed,
+                ?deadline,
+                ?timings,
+                "step exceeded its soft deadline"
+            );
+        }
     }
     trace!(?result, "step response");
     result
 }
+
+/// Logs a warning if the step at the end of an era is still running once its soft deadline
+/// has passed.
+///
+/// Slow steps delay the switch block and hence stall consensus, so this makes them visible while
+/// they are still in progress.  The watchdog is stopped by dropping it.
+struct StepWatchdog {
+    _stop_sender: mpsc::Sender<()>,
+}
+
+impl StepWatchdog {
+    fn start(next_era_id: EraId, deadline: Duration, maybe_metrics: Option<Arc<Metrics>>) -> Self {
+        let (stop_sender, stop_receiver) = mpsc::channel::<()>();
+        let spawn_result = thread::Builder::new()
+            .name("step-watchdog".to_string())
+            .spawn(move || {
+                if let Err(mpsc::RecvTimeoutError::Timeout) = stop_receiver.recv_timeout(deadline) {
+                    warn!(
+                        %next_era_id,
+                        ?deadline,
+                        "step is still running after its soft deadline"
+                    );
+                    if let Some(metrics) = maybe_metrics {
+                        metrics.step_soft_deadline_exceeded.inc();
+                    }
+                }
+            });
+        if let Err(error) = spawn_result {
+            warn!(%error, "failed to start step watchdog");
+        }
+        StepWatchdog {
+            _stop_sender: stop_sender,
+        }
+    }
+}
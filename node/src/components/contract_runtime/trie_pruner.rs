@@ -0,0 +1,272 @@
+//! Background pruning of tries which are unreachable from the global state of retained blocks.
+
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{RwLock, RwLockReadGuard};
+use tracing::{debug, error, info};
+
+use casper_execution_engine::{
+    core::engine_state::EngineState, storage::global_state::lmdb::LmdbGlobalState,
+};
+use casper_hashing::Digest;
+
+use super::{metrics::Metrics, run_intensive_task, Config};
+use crate::{
+    effect::{
+        requests::{StateStoreRequest, StorageRequest},
+        EffectBuilder,
+    },
+    types::SyncMode,
+};
+
+/// The key under which the progress of the current pruning run is persisted in the state store.
+const STATE_KEY: &[u8] = b"trie pruning progress";
+
+/// The progress of a pruning run, persisted after every batch so that a run interrupted by a
+/// restart is resumed rather than started over.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct PruningProgress {
+    /// Identifies the run; the tries found to be reachable are marked with it in global state.
+    generation: u64,
+    /// The height of the highest retained block whose tries have all been marked.
+    marked_height: Option<u64>,
+    /// The trie key from which sweeping the trie store continues.
+    sweep_from: Option<Digest>,
+    /// Whether the run has finished.
+    finished: bool,
+}
+
+impl PruningProgress {
+    /// Loads the progress of the run to resume, or starts a new run.
+    async fn load_or_start<REv>(effect_builder: EffectBuilder<REv>) -> Self
+    where
+        REv: From<StateStoreRequest>,
+    {
+        match effect_builder
+            .load_state::<PruningProgress>(STATE_KEY.into())
+            .await
+        {
+            Some(progress) if !progress.finished => progress,
+            previous => PruningProgress {
+                generation: previous.map_or(0, |progress| progress.generation) + 1,
+                ..PruningProgress::default()
+            },
+        }
+    }
+
+    /// Persists the progress, replacing any previous one.
+    async fn save<REv>(&self, effect_builder: EffectBuilder<REv>)
+    where
+        REv: From<StateStoreRequest>,
+    {
+        effect_builder
+            .save_state(STATE_KEY.into(), self.clone())
+            .await;
+    }
+}
+
+/// Deletes tries which are not reachable from the state roots of the blocks the node retains.
+///
+/// In recent sync mode, the node retains the blocks from the one it started syncing from onwards,
+/// i.e. its available block range, and must be able to serve their global state to peers syncing
+/// from any of them.  A pruning run first marks every trie reachable from the state roots of these
+/// blocks, then sweeps the trie store, deleting the tries which are not marked.  Both happen in
+/// batches of short-lived transactions with a pause after each, and the marks along with the
+/// run's progress are persisted, so a run is resumed after a restart.  Writing tries to global
+/// state and sweeping batches are mutually exclusive, and every trie written during a run is
+/// marked before the next batch is swept.
+pub(super) struct TriePruner {
+    enabled: bool,
+    batch_size: usize,
+    batch_delay: Duration,
+    /// Held for reading while writing to global state, and for writing while sweeping a batch.
+    commit_lock: RwLock<()>,
+    /// The keys of the tries written since the current run started, i.e. committed state roots
+    /// and tries stored by trie sync, `None` if no run is in progress.
+    new_trie_keys: Mutex<Option<Vec<Digest>>>,
+}
+
+impl TriePruner {
+    pub(super) fn new(config: &Config, sync_mode: SyncMode) -> Self {
+        TriePruner {
+            enabled: config.trie_pruning_enabled(sync_mode),
+            batch_size: config.trie_pruning_batch_size(),
+            batch_delay: config.trie_pruning_batch_delay().into(),
+            commit_lock: RwLock::new(()),
+            new_trie_keys: Mutex::new(None),
+        }
+    }
+
+    /// Returns a guard which must be held while writing to global state.
+    pub(super) async fn commit_guard(&self) -> RwLockReadGuard<'_, ()> {
+        self.commit_lock.read().await
+    }
+
+    /// Records the key of a trie written while holding a commit guard.
+    pub(super) fn record_new_trie_key(&self, trie_key: Digest) {
+        if let Some(new_trie_keys) = self.new_trie_keys.lock().expect("mutex poisoned").as_mut() {
+            new_trie_keys.push(trie_key);
+        }
+    }
+
+    /// Starts recording written tries for a pruning run.
+    ///
+    /// Must be called while holding a commit guard.  Returns `false` if pruning is disabled or a
+    /// run is already in progress.
+    pub(super) fn try_begin(&self) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let mut new_trie_keys = self.new_trie_keys.lock().expect("mutex poisoned");
+        if new_trie_keys.is_some() {
+            debug!("trie pruning already in progress");
+            return false;
+        }
+        *new_trie_keys = Some(Vec::new());
+        true
+    }
+
+    /// Prunes all tries not reachable from `known_roots` or from the state roots of the blocks in
+    /// the available block range, resuming the previous run if it was interrupted.
+    ///
+    /// Must only be called after a successful call to `try_begin`.
+    pub(super) async fn run<REv>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        engine_state: Arc<EngineState<LmdbGlobalState>>,
+        metrics: Arc<Metrics>,
+        known_roots: Vec<Digest>,
+    ) where
+        REv: From<StorageRequest> + From<StateStoreRequest>,
+    {
+        let start = Instant::now();
+        let mut progress = PruningProgress::load_or_start(effect_builder).await;
+        info!(
+            generation = progress.generation,
+            marked_height = ?progress.marked_height,
+            "starting trie pruning"
+        );
+
+        // The known roots are marked first, followed by the state roots of the retained blocks not
+        // marked yet, in ascending order of height.
+        let mut heights = vec![None; known_roots.len()];
+        let mut trie_keys = known_roots;
+        for (height, state_root_hash) in effect_builder
+            .get_available_state_root_hashes_from_storage()
+            .await
+        {
+            if progress
+                .marked_height
+                .map_or(true, |marked_height| height > marked_height)
+            {
+                heights.push(Some(height));
+                trie_keys.push(state_root_hash);
+            }
+        }
+
+        let mut marked_count = 0;
+        while marked_count < trie_keys.len() {
+            let batch_engine_state = Arc::clone(&engine_state);
+            let generation = progress.generation;
+            let batch_size = self.batch_size;
+            let (result, returned_trie_keys) = run_intensive_task(move || {
+                let result = batch_engine_state.get_state().mark_reachable_tries(
+                    generation,
+                    &trie_keys[marked_count..],
+                    batch_size,
+                );
+                (result, trie_keys)
+            })
+            .await;
+            trie_keys = returned_trie_keys;
+            match result {
+                Ok(count) => marked_count += count,
+                Err(error) => {
+                    error!(%error, "failed to mark reachable tries");
+                    self.finish();
+                    return;
+                }
+            }
+
+            if let Some(&Some(height)) = marked_count
+                .checked_sub(1)
+                .and_then(|index| heights.get(index))
+            {
+                progress.marked_height = Some(height);
+            }
+            progress.save(effect_builder).await;
+            tokio::time::sleep(self.batch_delay).await;
+        }
+        debug!(roots = trie_keys.len(), "marked reachable tries");
+
+        let mut deleted_count = 0;
+        loop {
+            let sweep_guard = self.commit_lock.write().await;
+            let new_trie_keys = mem::take(
+                self.new_trie_keys
+                    .lock()
+                    .expect("mutex poisoned")
+                    .get_or_insert_with(Vec::new),
+            );
+            let batch_engine_state = Arc::clone(&engine_state);
+            let generation = progress.generation;
+            let start_at = progress.sweep_from;
+            let batch_size = self.batch_size;
+            let result = run_intensive_task(move || {
+                batch_engine_state
+                    .get_state()
+                    .sweep_unmarked_tries(generation, &new_trie_keys, start_at, batch_size)
+                    .map_err(|error| error.to_string())
+                    .and_then(|count_and_next_key| {
+                        batch_engine_state
+                            .flush_environment()
+                            .map(|()| count_and_next_key)
+                            .map_err(|error| error.to_string())
+                    })
+            })
+            .await;
+            drop(sweep_guard);
+
+            let next_key = match result {
+                Ok((count, next_key)) => {
+                    deleted_count += count;
+                    metrics.pruned_tries.inc_by(count as u64);
+                    next_key
+                }
+                Err(error) => {
+                    error!(%error, deleted_count, "failed to delete unreachable tries");
+                    self.finish();
+                    return;
+                }
+            };
+            if next_key.is_none() {
+                break;
+            }
+            progress.sweep_from = next_key;
+            progress.save(effect_builder).await;
+            tokio::time::sleep(self.batch_delay).await;
+        }
+
+        progress.finished = true;
+        progress.save(effect_builder).await;
+        self.finish();
+        metrics
+            .latest_trie_pruning
+            .set(start.elapsed().as_secs_f64());
+        info!(
+            deleted_count,
+            elapsed = ?start.elapsed(),
+            "finished trie pruning"
+        );
+    }
+
+    /// Stops recording written tries, allowing the next run to begin.
+    fn finish(&self) {
+        *self.new_trie_keys.lock().expect("mutex poisoned") = None;
+    }
+}
@@ -31,7 +31,10 @@ use crate::{
         requests::{ContractRuntimeRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects, Responder,
     },
-    types::{chainspec::DeployConfig, BlockHeader, Chainspec, Deploy, DeployConfigurationFailure},
+    types::{
+        chainspec::{DeployConfig, PricingConfig},
+        BlockHeader, Chainspec, Deploy, DeployConfigurationFailure,
+    },
     utils::Source,
     NodeRng,
 };
@@ -145,6 +148,7 @@ pub struct DeployAcceptor {
     chain_name: String,
     protocol_version: ProtocolVersion,
     deploy_config: DeployConfig,
+    pricing_config: PricingConfig,
     max_associated_keys: u32,
     metrics: metrics::Metrics,
 }
@@ -158,6 +162,7 @@ impl DeployAcceptor {
             chain_name: chainspec.network_config.name.clone(),
             protocol_version: chainspec.protocol_version(),
             deploy_config: chainspec.deploy_config,
+            pricing_config: chainspec.pricing_config,
             max_associated_keys: chainspec.core_config.max_associated_keys,
             metrics: metrics::Metrics::new(registry)?,
         })
@@ -175,11 +180,13 @@ impl DeployAcceptor {
         maybe_responder: Option<Responder<Result<(), Error>>>,
     ) -> Effects<Event> {
         let verification_start_timestamp = Timestamp::now();
-        let acceptable_result = deploy.is_config_compliant(
-            &self.chain_name,
-            &self.deploy_config,
-            self.max_associated_keys,
-        );
+        let acceptable_result = deploy
+            .is_config_compliant(
+                &self.chain_name,
+                &self.deploy_config,
+                self.max_associated_keys,
+            )
+            .and_then(|()| deploy.is_pricing_compliant(&self.pricing_config));
         // checks chainspec values
         if let Err(error) = acceptable_result {
             debug!(%deploy, %error, "deploy is incorrectly configured");
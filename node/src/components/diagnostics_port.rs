@@ -22,7 +22,9 @@ use tracing::{debug, warn};
 use super::Component;
 use crate::{
     effect::{
-        announcements::ControlAnnouncement, diagnostics_port::DumpConsensusStateRequest,
+        announcements::ControlAnnouncement,
+        diagnostics_port::{DumpConsensusStateRequest, DumpMemoryRequest},
+        requests::{ConsensusRequest, NetworkInfoRequest},
         EffectBuilder, EffectExt, Effects,
     },
     reactor::EventQueueHandle,
@@ -44,6 +46,7 @@ pub(crate) struct DiagnosticsPort {
 
 /// Diagnostics port configuration.
 #[derive(Clone, DataSize, Debug, Deserialize)]
+#[serde(default)]
 pub(crate) struct Config {
     /// Whether or not the diagnostics port is enabled.
     enabled: bool,
@@ -70,7 +73,12 @@ impl DiagnosticsPort {
         event_queue: EventQueueHandle<REv>,
     ) -> Result<(Self, Effects<Event>), Error>
     where
-        REv: From<DumpConsensusStateRequest> + From<ControlAnnouncement> + Send,
+        REv: From<DumpConsensusStateRequest>
+            + From<ControlAnnouncement>
+            + From<NetworkInfoRequest>
+            + From<ConsensusRequest>
+            + From<DumpMemoryRequest>
+            + Send,
     {
         let config = cfg.value();
         let (shutdown_sender, shutdown_receiver) = watch::channel(());
@@ -86,6 +86,23 @@ pub(super) enum Action {
     },
     /// Dump the event queues.
     DumpQueues,
+    /// Show the estimated heap memory usage of each component and its major collections.
+    DumpMemory,
+    /// Show the number of events in each of the event queues.
+    QueueDepths,
+    /// Show the connection table, i.e. all connected peers and their addresses.
+    Peers,
+    /// Show this node's consensus status.
+    ConsensusStatus,
+    /// Replace the log filter until the next config reload or restart.
+    SetLogFilter {
+        /// Log filter directives, e.g. "warn,casper_node::components::consensus=debug".
+        directives: String,
+    },
+    /// Reload the configuration file, applying the options which can be changed at runtime.
+    ///
+    /// Equivalent to sending `SIGHUP` to the node.
+    ReloadConfig,
     /// Close connection server-side.
     Quit,
 }
@@ -117,5 +134,18 @@ mod tests {
 
         let cmd = Command::from_line("dump-queues").expect("command parsing failed");
         assert!(matches!(cmd.action, Action::DumpQueues));
+
+        let cmd = Command::from_line("reload-config").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::ReloadConfig));
+
+        let cmd = Command::from_line("queue-depths").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::QueueDepths));
+
+        let cmd = Command::from_line("set-log-filter 'warn,casper_node=debug'")
+            .expect("command parsing failed");
+        match cmd.action {
+            Action::SetLogFilter { directives } => assert_eq!(directives, "warn,casper_node=debug"),
+            other => panic!("unexpected action {:?}", other),
+        }
     }
 }
@@ -1,9 +1,11 @@
 use std::{
     borrow::Cow,
+    collections::BTreeMap,
     fmt::{self, Debug, Display, Formatter},
     fs::{self, File},
     io,
     path::PathBuf,
+    sync::atomic::Ordering,
 };
 
 use bincode::{
@@ -21,7 +23,7 @@ use tokio::{
 };
 use tracing::{debug, info, info_span, warn, Instrument};
 
-use casper_types::EraId;
+use casper_types::{EraId, PublicKey, TimeDiff};
 
 use super::{
     command::{Action, Command, OutputFormat},
@@ -31,10 +33,15 @@ use crate::{
     components::consensus::EraDump,
     effect::{
         announcements::{ControlAnnouncement, QueueDumpFormat},
-        diagnostics_port::DumpConsensusStateRequest,
+        diagnostics_port::{DumpConsensusStateRequest, DumpMemoryRequest},
+        requests::{ConsensusRequest, NetworkInfoRequest},
         EffectBuilder,
     },
+    logging,
+    reactor::QueueKind,
+    types::NodeId,
     utils::display_error,
+    CONFIG_RELOAD_REQUESTED,
 };
 
 /// Success or failure response.
@@ -84,6 +91,81 @@ impl Display for Outcome {
     }
 }
 
+/// The number of events in each of the reactor's queues.
+#[derive(Debug, Serialize)]
+struct QueueDepths {
+    queues: BTreeMap<QueueKind, usize>,
+    total: usize,
+}
+
+impl Display for QueueDepths {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (queue, count) in &self.queues {
+            writeln!(f, "{:<20}{}", queue, count)?;
+        }
+        write!(f, "{:<20}{}", "total", self.total)
+    }
+}
+
+/// A connected peer, as listed in the connection table.
+#[derive(Debug, Serialize)]
+struct PeerEntry {
+    node_id: NodeId,
+    address: String,
+    /// Whether we have both an incoming and outgoing connection to the peer.
+    fully_connected: bool,
+}
+
+/// The table of connected peers.
+#[derive(Debug, Serialize)]
+struct Peers {
+    peers: Vec<PeerEntry>,
+}
+
+impl Display for Peers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for entry in &self.peers {
+            writeln!(
+                f,
+                "{} {:<24}{}",
+                entry.node_id,
+                entry.address,
+                if entry.fully_connected {
+                    "fully connected"
+                } else {
+                    "connecting"
+                }
+            )?;
+        }
+        write!(f, "{} peers", self.peers.len())
+    }
+}
+
+/// The consensus status of this node.
+#[derive(Debug, Serialize)]
+struct ConsensusStatus {
+    /// Our public key, or `None` if consensus is not running.
+    our_public_key: Option<PublicKey>,
+    /// The next round length, if we are a validator in the current era.
+    next_round_length: Option<TimeDiff>,
+}
+
+impl Display for ConsensusStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (&self.our_public_key, &self.next_round_length) {
+            (None, _) => f.write_str("consensus is not running"),
+            (Some(public_key), None) => {
+                write!(f, "{} is not a validator in the current era", public_key)
+            }
+            (Some(public_key), Some(round_length)) => write!(
+                f,
+                "{} is a validator, next round length {}",
+                public_key, round_length
+            ),
+        }
+    }
+}
+
 /// Configuration for a connection diagnostics port session.
 #[derive(Copy, Clone, Debug, Default, Serialize)]
 struct Session {
@@ -195,7 +277,12 @@ impl Session {
         line: &str,
     ) -> io::Result<bool>
     where
-        REv: From<DumpConsensusStateRequest> + From<ControlAnnouncement> + Send,
+        REv: From<DumpConsensusStateRequest>
+            + From<ControlAnnouncement>
+            + From<NetworkInfoRequest>
+            + From<ConsensusRequest>
+            + From<DumpMemoryRequest>
+            + Send,
     {
         debug!(%line, "line received");
         match Command::from_line(line) {
@@ -276,6 +363,80 @@ impl Session {
                             }
                         };
                     }
+                    Action::DumpMemory => {
+                        let report = effect_builder.diagnostics_port_dump_memory().await;
+                        self.send_outcome(writer, &Outcome::success("dumping memory usage"))
+                            .await?;
+                        self.send_to_client(writer, &report).await?;
+                    }
+                    Action::QueueDepths => {
+                        let queues: BTreeMap<_, _> =
+                            effect_builder.event_queues_counts().into_iter().collect();
+                        let total = queues.values().sum();
+                        self.send_outcome(writer, &Outcome::success("showing queue depths"))
+                            .await?;
+                        self.send_to_client(writer, &QueueDepths { queues, total })
+                            .await?;
+                    }
+                    Action::Peers => {
+                        let addresses = effect_builder.network_peers().await;
+                        let fully_connected = effect_builder.get_fully_connected_peers().await;
+                        let peers = addresses
+                            .into_iter()
+                            .map(|(node_id, address)| PeerEntry {
+                                fully_connected: fully_connected.contains(&node_id),
+                                node_id,
+                                address,
+                            })
+                            .collect();
+                        self.send_outcome(writer, &Outcome::success("showing peers"))
+                            .await?;
+                        self.send_to_client(writer, &Peers { peers }).await?;
+                    }
+                    Action::ConsensusStatus => {
+                        let (our_public_key, next_round_length) = match effect_builder
+                            .consensus_status()
+                            .await
+                        {
+                            Some((public_key, round_length)) => (Some(public_key), round_length),
+                            None => (None, None),
+                        };
+                        self.send_outcome(writer, &Outcome::success("showing consensus status"))
+                            .await?;
+                        self.send_to_client(
+                            writer,
+                            &ConsensusStatus {
+                                our_public_key,
+                                next_round_length,
+                            },
+                        )
+                        .await?;
+                    }
+                    Action::SetLogFilter { ref directives } => {
+                        match logging::set_filter(directives) {
+                            Ok(()) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::success("log filter replaced until next reload"),
+                                )
+                                .await?;
+                            }
+                            Err(err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!("failed to set log filter: {}", err)),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
+                    Action::ReloadConfig => {
+                        // The reload itself is performed by the reactor runner, exactly as if the
+                        // node had received a `SIGHUP`.
+                        CONFIG_RELOAD_REQUESTED.store(true, Ordering::SeqCst);
+                        self.send_outcome(writer, &Outcome::success("config reload requested"))
+                            .await?;
+                    }
                     Action::Quit => {
                         self.send_outcome(writer, &Outcome::success("goodbye!"))
                             .await?;
@@ -393,7 +554,12 @@ async fn handler<REv>(
     mut shutdown_receiver: watch::Receiver<()>,
 ) -> io::Result<()>
 where
-    REv: From<DumpConsensusStateRequest> + From<ControlAnnouncement> + Send,
+    REv: From<DumpConsensusStateRequest>
+        + From<ControlAnnouncement>
+        + From<NetworkInfoRequest>
+        + From<ConsensusRequest>
+        + From<DumpMemoryRequest>
+        + Send,
 {
     debug!("accepted new connection on diagnostics port");
 
@@ -433,7 +599,12 @@ pub(super) async fn server<REv>(
     listener: UnixListener,
     mut shutdown_receiver: watch::Receiver<()>,
 ) where
-    REv: From<DumpConsensusStateRequest> + From<ControlAnnouncement> + Send,
+    REv: From<DumpConsensusStateRequest>
+        + From<ControlAnnouncement>
+        + From<NetworkInfoRequest>
+        + From<ConsensusRequest>
+        + From<DumpMemoryRequest>
+        + Send,
 {
     let handling_shutdown_receiver = shutdown_receiver.clone();
     let mut next_client_id: u64 = 0;
@@ -544,6 +715,9 @@ mod tests {
 
         requests: {
             DumpConsensusStateRequest -> !;
+            NetworkInfoRequest -> !;
+            ConsensusRequest -> !;
+            DumpMemoryRequest -> !;
         }
 
         announcements: {}
@@ -554,6 +728,13 @@ mod tests {
     /// Runs a single mini-node with a diagnostics console and requests a dump of the (empty)
     /// event queue, then returns it.
     async fn run_single_node_console_and_dump_events(dump_format: &'static str) -> String {
+        let commands = format!("set -o {} -q true\ndump-queues\nquit\n", dump_format);
+        run_single_node_console(commands).await
+    }
+
+    /// Runs a single mini-node with a diagnostics console, sends it the given commands and returns
+    /// the console output.
+    async fn run_single_node_console(commands: String) -> String {
         let mut network = Network::<Reactor>::new();
         let mut rng = TestRng::new();
 
@@ -578,7 +759,6 @@ mod tests {
                 .await
                 .expect("could not connect to socket path of node");
 
-            let commands = format!("set -o {} -q true\ndump-queues\nquit\n", dump_format);
             stream
                 .write_all(commands.as_bytes())
                 .await
@@ -629,6 +809,19 @@ mod tests {
         assert!(output.starts_with(r#"QueueDump { queues: {"#));
     }
 
+    #[tokio::test]
+    async fn ensure_diagnostics_port_can_show_queue_depths() {
+        testing::init_logging();
+
+        let output =
+            run_single_node_console("set -o json -q true\nqueue-depths\nquit\n".to_string()).await;
+
+        let depths: serde_json::Value =
+            serde_json::from_str(&output).expect("output should be a single JSON object");
+        assert!(depths["queues"]["Consensus"].is_u64());
+        assert!(depths["total"].is_u64());
+    }
+
     #[tokio::test]
     async fn can_dump_actual_events_from_scheduler() {
         // Create a scheduler with a few synthetic events.
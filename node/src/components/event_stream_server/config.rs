@@ -15,7 +15,7 @@ const DEFAULT_MAX_CONCURRENT_SUBSCRIBERS: u32 = 100;
 /// SSE HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Setting to enable the HTTP server.
     pub enable_server: bool,
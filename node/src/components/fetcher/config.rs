@@ -9,6 +9,7 @@ const DEFAULT_GET_FROM_PEER_TIMEOUT: &str = "3sec";
 
 /// Configuration options for fetching.
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Config {
     get_from_peer_timeout: TimeDiff,
 }
@@ -21,6 +21,7 @@ use crate::{
     components::{fetcher::FetchedOrNotFound, Component},
     effect::{
         announcements::GossiperAnnouncement,
+        diagnostics_port::ComponentMemory,
         incoming::GossiperIncoming,
         requests::{BeginGossipRequest, NetworkRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects,
@@ -170,6 +171,13 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
         })
     }
 
+    /// Applies a reloaded configuration, including to items which are currently being gossiped.
+    pub(crate) fn update_config(&mut self, config: Config) {
+        self.table.update_config(config);
+        self.gossip_timeout = config.gossip_request_timeout().into();
+        self.get_from_peer_timeout = config.get_remainder_timeout().into();
+    }
+
     /// Handles a new item received from a peer or client for which we should begin gossiping.
     ///
     /// Note that this doesn't include items gossiped to us; those are handled in `handle_gossip()`.
@@ -512,6 +520,14 @@ impl<T: Item + 'static, REv: ReactorEventT<T>> Gossiper<T, REv> {
             .table_items_finished
             .set(self.table.items_finished() as i64);
     }
+
+    /// Breaks down the component's estimated heap usage by its major collections.
+    pub(crate) fn report_memory(&self, memory: &mut ComponentMemory)
+    where
+        T::Id: DataSize,
+    {
+        memory.add_collection("table", &self.table);
+    }
 }
 
 impl<T, REv> Component<REv> for Gossiper<T, REv>
@@ -27,6 +27,7 @@ const SMALL_TIMEOUTS_GET_REMAINDER_TIMEOUT: &str = "1sec";
 
 /// Configuration options for gossiping.
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Config {
     /// Target number of peers to infect with a given piece of data.
     infection_target: u8,
@@ -203,18 +203,30 @@ impl<T> GossipTable<T> {
 impl<T: Copy + Eq + Hash + Display> GossipTable<T> {
     /// Returns a new `GossipTable` using the provided configuration.
     pub(crate) fn new(config: Config) -> Self {
-        let holders_limit = (100 * usize::from(config.infection_target()))
-            / (100 - usize::from(config.saturation_limit_percent()));
         GossipTable {
             current: HashMap::new(),
             finished: HashSet::new(),
             timeouts: Timeouts::new(),
             infection_target: usize::from(config.infection_target()),
-            holders_limit,
+            holders_limit: Self::holders_limit(config),
             finished_entry_duration: config.finished_entry_duration().into(),
         }
     }
 
+    /// Updates the gossiping parameters from a reloaded configuration.
+    ///
+    /// The new parameters apply to all subsequent gossip actions.
+    pub(crate) fn update_config(&mut self, config: Config) {
+        self.infection_target = usize::from(config.infection_target());
+        self.holders_limit = Self::holders_limit(config);
+        self.finished_entry_duration = config.finished_entry_duration().into();
+    }
+
+    fn holders_limit(config: Config) -> usize {
+        (100 * usize::from(config.infection_target()))
+            / (100 - usize::from(config.saturation_limit_percent()))
+    }
+
     /// We received knowledge about potentially new data with given ID from the given peer.  This
     /// should only be called where we don't already hold everything locally we need to be able to
     /// gossip it onwards.  If we are able to gossip the data already, call `new_data` instead.
@@ -832,6 +844,35 @@ mod tests {
         assert_eq!(expected, action);
     }
 
+    #[test]
+    fn should_apply_updated_infection_target() {
+        let _ = logging::init();
+        let mut rng = crate::new_rng();
+        let data_id: u64 = rng.gen();
+
+        let mut gossip_table = GossipTable::new(Config::default());
+
+        let infection_target = EXPECTED_DEFAULT_INFECTION_TARGET + 2;
+        let finished_entry_duration = TimeDiff::from_str(DEFAULT_FINISHED_ENTRY_DURATION).unwrap();
+        let config = Config::new(
+            infection_target as u8,
+            80,
+            finished_entry_duration,
+            TimeDiff::from_seconds(10),
+            TimeDiff::from_seconds(60),
+        )
+        .unwrap();
+        gossip_table.update_config(config);
+
+        let action = gossip_table.new_complete_data(&data_id, None);
+        let expected = GossipAction::ShouldGossip(ShouldGossip {
+            count: infection_target,
+            exclude_peers: HashSet::new(),
+            is_already_held: false,
+        });
+        assert_eq!(expected, action);
+    }
+
     #[test]
     fn check_timeout_should_detect_holder() {
         let _ = logging::init();
@@ -25,7 +25,7 @@ use casper_execution_engine::{
     },
     shared::{system_config::SystemConfig, wasm_config::WasmConfig},
 };
-use casper_types::{testing::TestRng, ProtocolVersion};
+use casper_types::{testing::TestRng, ProtocolVersion, TimeDiff};
 
 use super::*;
 use crate::{
@@ -46,7 +46,9 @@ use crate::{
             ConsensusMessageIncoming, FinalitySignatureIncoming, NetRequestIncoming, NetResponse,
             NetResponseIncoming, TrieDemand, TrieRequestIncoming, TrieResponseIncoming,
         },
-        requests::{ConsensusRequest, ContractRuntimeRequest, MarkBlockCompletedRequest},
+        requests::{
+            ConsensusRequest, ContractRuntimeRequest, MarkBlockCompletedRequest, StateStoreRequest,
+        },
         Responder,
     },
     fatal,
@@ -54,10 +56,10 @@ use crate::{
     reactor::{self, EventQueueHandle, Runner},
     testing::{
         self,
-        network::{Network, NetworkedReactor},
+        network::{Network, NetworkedReactor, Nodes},
         ConditionCheckReactor,
     },
-    types::{Deploy, NodeId},
+    types::{chainspec::PricingConfig, Deploy, NodeId, SyncMode},
     utils::WithDir,
     NodeRng,
 };
@@ -83,6 +85,8 @@ enum Event {
     #[from]
     MarkBlockCompletedRequest(MarkBlockCompletedRequest),
     #[from]
+    StateStoreRequest(StateStoreRequest),
+    #[from]
     ControlAnnouncement(ControlAnnouncement),
     #[from]
     RpcServerAnnouncement(#[serde(skip_serializing)] RpcServerAnnouncement),
@@ -161,6 +165,7 @@ impl Display for Event {
             Event::MarkBlockCompletedRequest(req) => {
                 write!(formatter, "mark block completed: {}", req)
             }
+            Event::StateStoreRequest(req) => write!(formatter, "state store request: {}", req),
             Event::NetworkRequest(req) => write!(formatter, "network request: {}", req),
             Event::ContractRuntimeRequest(req) => write!(formatter, "incoming: {}", req),
             Event::ControlAnnouncement(ctrl_ann) => write!(formatter, "control: {}", ctrl_ann),
@@ -245,6 +250,7 @@ impl reactor::Reactor for Reactor {
             ProtocolVersion::from_parts(1, 0, 0),
             storage.root_path(),
             &contract_runtime_config,
+            SyncMode::Recent,
             WasmConfig::default(),
             SystemConfig::default(),
             MAX_ASSOCIATED_KEYS,
@@ -253,6 +259,8 @@ impl reactor::Reactor for Reactor {
             DEFAULT_STRICT_ARGUMENT_CHECKING,
             registry,
             verifiable_chunked_hash_activation.into(),
+            TimeDiff::from_seconds(1),
+            PricingConfig::default(),
         )
         .unwrap();
 
@@ -312,6 +320,9 @@ impl reactor::Reactor for Reactor {
             Event::MarkBlockCompletedRequest(_) => {
                 panic!("gossiper tests should never mark blocks completed")
             }
+            Event::StateStoreRequest(_) => {
+                panic!("gossiper tests should never access the state store")
+            }
             Event::ControlAnnouncement(ctrl_ann) => {
                 unreachable!("unhandled control announcement: {}", ctrl_ann)
             }
@@ -650,3 +661,55 @@ async fn should_timeout_gossip_response() {
 
     NetworkController::<NodeMessage>::remove_active();
 }
+
+#[tokio::test]
+async fn should_not_gossip_across_partitions() {
+    const NETWORK_SIZE: usize = 4;
+    const QUIET_FOR: Duration = Duration::from_millis(50);
+    const TIMEOUT: Duration = Duration::from_secs(120);
+
+    NetworkController::<NodeMessage>::create_active();
+    let mut network = Network::<Reactor>::new_with_simulated_time();
+    let mut rng = crate::new_rng();
+
+    let node_ids = network.add_nodes(&mut rng, NETWORK_SIZE).await;
+    let is_held_by = |deploy: &Deploy, nodes: &Nodes<Reactor>, node_id: &NodeId| {
+        nodes[node_id]
+            .reactor()
+            .inner()
+            .storage
+            .get_deploy_by_hash(*deploy.id())
+            .map_or(false, |retrieved_deploy| retrieved_deploy == *deploy)
+    };
+
+    // Split the network in two and give a deploy to node 0.
+    NetworkController::<NodeMessage>::partition(&[&node_ids[..2], &node_ids[2..]]);
+    let deploy = Box::new(Deploy::random_valid_native_transfer(&mut rng));
+    network
+        .process_injected_effect_on(&node_ids[0], announce_deploy_received(deploy.clone(), None))
+        .await;
+
+    // Run for long enough for every gossip request to node 2 or 3 to time out.
+    network.run_for(&mut rng, Duration::from_secs(60)).await;
+    assert!(is_held_by(&deploy, network.nodes(), &node_ids[1]));
+    assert!(!is_held_by(&deploy, network.nodes(), &node_ids[2]));
+    assert!(!is_held_by(&deploy, network.nodes(), &node_ids[3]));
+
+    // Once healed, a deploy given to node 2 reaches every node.
+    NetworkController::<NodeMessage>::heal();
+    let deploy = Box::new(Deploy::random_valid_native_transfer(&mut rng));
+    network
+        .process_injected_effect_on(&node_ids[2], announce_deploy_received(deploy.clone(), None))
+        .await;
+    let deploy_held = |nodes: &Nodes<Reactor>| {
+        node_ids
+            .iter()
+            .all(|node_id| is_held_by(&deploy, nodes, node_id))
+    };
+    network.settle_on(&mut rng, deploy_held, TIMEOUT).await;
+
+    // Ensure all responders are called before dropping the network.
+    network.settle(&mut rng, QUIET_FOR, TIMEOUT).await;
+
+    NetworkController::<NodeMessage>::remove_active();
+}
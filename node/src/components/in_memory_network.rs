@@ -275,11 +275,17 @@
 //!
 //! # }); // end of tokio::block_on
 //! ```
+//!
+//! # Partitions
+//!
+//! The active network can be split into partitions using `NetworkController::partition`, after
+//! which messages between nodes in different partitions are silently dropped, until the network is
+//! healed again using `NetworkController::heal`.
 
 use std::{
     any::Any,
     cell::RefCell,
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     convert::Infallible,
     fmt::{self, Display, Formatter},
     sync::{Arc, RwLock},
@@ -305,6 +311,11 @@ use super::small_network::FromIncoming;
 /// A network.
 type Network<P> = Arc<RwLock<HashMap<NodeId, mpsc::UnboundedSender<(NodeId, P)>>>>;
 
+/// The partition of each node, by index.  Nodes without an entry form a partition of their own.
+///
+/// While empty, the network is fully connected.
+type Partitions = Arc<RwLock<HashMap<NodeId, usize>>>;
+
 /// An in-memory network events.
 #[derive(Debug, Serialize)]
 pub(crate) struct Event<P>(NetworkRequest<P>);
@@ -334,6 +345,8 @@ thread_local! {
 pub(crate) struct NetworkController<P> {
     /// Channels for network communication.
     nodes: Network<P>,
+    /// The current network partitions.
+    partitions: Partitions,
 }
 
 impl<P> NetworkController<P>
@@ -345,6 +358,7 @@ where
         let _ = logging::init();
         NetworkController {
             nodes: Default::default(),
+            partitions: Default::default(),
         }
     }
 
@@ -423,6 +437,49 @@ where
         })
     }
 
+    /// Splits the active network into the given partitions.
+    ///
+    /// Messages between nodes in different partitions are dropped until the network is healed.
+    /// Nodes which are not in any of the given partitions form a partition of their own.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal lock has been poisoned, there is no active network or the active
+    /// network is not of the correct message type.
+    pub(crate) fn partition(partitions: &[&[NodeId]]) {
+        Self::with_active(|network| {
+            let mut assigned = network.partitions.write().expect("poisoned lock");
+            assigned.clear();
+            for (index, partition) in partitions.iter().enumerate() {
+                for node_id in partition.iter() {
+                    assigned.insert(*node_id, index);
+                }
+            }
+        })
+    }
+
+    /// Removes all partitions from the active network, reconnecting all nodes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the internal lock has been poisoned, there is no active network or the active
+    /// network is not of the correct message type.
+    pub(crate) fn heal() {
+        Self::with_active(|network| network.partitions.write().expect("poisoned lock").clear())
+    }
+
+    /// Runs `f` on the active network.
+    fn with_active<F: FnOnce(&Self)>(f: F) {
+        ACTIVE_NETWORK.with(|active_network| {
+            f(active_network
+                .borrow()
+                .as_ref()
+                .expect("no active network set")
+                .downcast_ref::<Self>()
+                .expect("active network has wrong message type"))
+        })
+    }
+
     /// Creates a new networking node with a random node ID.
     ///
     /// Returns the already connected new networking component for new node.
@@ -434,7 +491,12 @@ where
     where
         REv: Send + FromIncoming<P>,
     {
-        InMemoryNetwork::new_with_data(event_queue, NodeId::random(rng), self.nodes.clone())
+        InMemoryNetwork::new_with_data(
+            event_queue,
+            NodeId::random(rng),
+            self.nodes.clone(),
+            self.partitions.clone(),
+        )
     }
 }
 
@@ -446,6 +508,9 @@ pub(crate) struct InMemoryNetwork<P> {
 
     /// The nodes map, contains the incoming channel for each virtual node.
     nodes: Network<P>,
+
+    /// The current network partitions, shared by all nodes.
+    partitions: Partitions,
 }
 
 impl<P> InMemoryNetwork<P>
@@ -467,6 +532,7 @@ where
         event_queue: EventQueueHandle<REv>,
         node_id: NodeId,
         nodes: Network<P>,
+        partitions: Partitions,
     ) -> Self
     where
         REv: Send + FromIncoming<P>,
@@ -482,7 +548,11 @@ where
 
         tokio::spawn(receiver_task(event_queue, receiver));
 
-        InMemoryNetwork { node_id, nodes }
+        InMemoryNetwork {
+            node_id,
+            nodes,
+            partitions,
+        }
     }
 
     /// Returns this node's ID.
@@ -496,6 +566,17 @@ impl<P> InMemoryNetwork<P>
 where
     P: Display,
 {
+    /// Returns whether `dest` is in the same partition as this node.
+    fn can_reach(&self, dest: &NodeId) -> bool {
+        match self.partitions.read() {
+            Ok(partitions) => partitions.get(&self.node_id) == partitions.get(dest),
+            Err(_) => {
+                error!("partitions lock has been poisoned");
+                true
+            }
+        }
+    }
+
     /// Internal helper, sends a payload to a node, ignoring but logging all errors.
     fn send(
         &self,
@@ -507,6 +588,11 @@ where
             panic!("can't send message to self");
         }
 
+        if !self.can_reach(&dest) {
+            debug!(%dest, %payload, "dropping message to node in another partition");
+            return;
+        }
+
         match nodes.get(&dest) {
             Some(sender) => {
                 if let Err(SendError((_, msg))) = sender.send((self.node_id, payload)) {
@@ -573,10 +659,15 @@ where
                 auto_closing_responder,
             } => {
                 if let Ok(guard) = self.nodes.read() {
-                    let chosen: HashSet<_> = guard
+                    // Choose from the peers in a fixed order, so that runs with the same seed
+                    // gossip to the same nodes.
+                    let peers: BTreeSet<_> = guard
                         .keys()
                         .filter(|&node_id| !exclude.contains(node_id) && node_id != &self.node_id)
                         .cloned()
+                        .collect();
+                    let chosen: HashSet<_> = peers
+                        .into_iter()
                         .choose_multiple(rng, count)
                         .into_iter()
                         .collect();
@@ -94,9 +94,30 @@ where
     outcomes
         .into_iter()
         .map(|outcome| match outcome {
-            Outcome::StoreBlockSignatures(block_signatures, should_upgrade) => effect_builder
-                .put_signatures_to_storage(block_signatures)
-                .events(move |_| should_upgrade.then(|| Event::Upgrade).into_iter()),
+            Outcome::StoreBlockSignatures(block_signatures, should_upgrade) => {
+                let block_hash = block_signatures.block_hash;
+                async move {
+                    effect_builder
+                        .put_signatures_to_storage(block_signatures)
+                        .await;
+                    if !should_upgrade {
+                        return;
+                    }
+                    // Record where we stopped, for the next protocol version to check against.
+                    match effect_builder
+                        .get_block_header_from_storage(block_hash, false)
+                        .await
+                    {
+                        Some(block_header) => {
+                            effect_builder
+                                .write_upgrade_handoff(block_hash, Box::new(block_header))
+                                .await
+                        }
+                        None => error!(%block_hash, "last block before upgrade not in storage"),
+                    }
+                }
+                .events(move |_| should_upgrade.then(|| Event::Upgrade).into_iter())
+            }
             Outcome::StoreBlock(block, execution_results) => async move {
                 let block_hash = *block.hash();
                 effect_builder.put_block_to_storage(block.clone()).await;
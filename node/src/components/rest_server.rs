@@ -12,9 +12,12 @@
 //! reactor, and an external facing http server that exposes various uri routes and converts
 //! HTTP requests into the appropriate component events.
 //!
-//! Currently this component supports two endpoints, each of which takes no arguments:
+//! Currently this component supports the following endpoints, each of which takes no arguments:
 //! /status : a human readable JSON equivalent of the info-get-status rpc method.
 //!     example: curl -X GET 'http://<ip>:8888/status'
+//! /ready : the health of the node's components, with status 200 if the node is participating and
+//!     healthy enough to serve requests, or 503 otherwise.
+//!     example: curl -X GET 'http://<ip>:8888/ready'
 //! /metrics : time series data collected from the internals of the node being queried.
 //!     example: curl -X GET 'http://<ip>:8888/metrics'
 
@@ -37,8 +40,9 @@ use crate::{
     components::rpc_server::rpcs::docs::OPEN_RPC_SCHEMA,
     effect::{
         requests::{
-            ChainspecLoaderRequest, ConsensusRequest, MetricsRequest, NetworkInfoRequest,
-            NodeStateRequest, RestRequest, StorageRequest,
+            ChainspecLoaderRequest, ConsensusRequest, HealthRequest, MetricsRequest,
+            NetworkInfoRequest, NodeStateRequest, RestRequest, StorageRequest,
+            SyncStatisticsRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
@@ -60,6 +64,8 @@ pub(crate) trait ReactorEventT:
     + From<ConsensusRequest>
     + From<MetricsRequest>
     + From<NodeStateRequest>
+    + From<SyncStatisticsRequest>
+    + From<HealthRequest>
     + Send
 {
 }
@@ -73,6 +79,8 @@ impl<REv> ReactorEventT for REv where
         + From<ConsensusRequest>
         + From<MetricsRequest>
         + From<NodeStateRequest>
+        + From<SyncStatisticsRequest>
+        + From<HealthRequest>
         + Send
         + 'static
 {
@@ -161,12 +169,16 @@ where
                         chainspec_info,
                         consensus_status,
                         node_state,
+                        sync_statistics,
+                        health,
                     ) = join!(
                         effect_builder.get_highest_block_from_storage(),
                         effect_builder.network_peers(),
                         effect_builder.get_chainspec_info(),
                         effect_builder.consensus_status(),
-                        effect_builder.get_node_state()
+                        effect_builder.get_node_state(),
+                        effect_builder.get_sync_statistics(),
+                        effect_builder.get_health_report()
                     );
 
                     let status_feed = StatusFeed::new(
@@ -176,6 +188,8 @@ where
                         consensus_status,
                         node_uptime,
                         node_state,
+                        sync_statistics,
+                        health,
                     );
                     responder.respond(status_feed).await;
                 }
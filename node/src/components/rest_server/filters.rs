@@ -1,4 +1,4 @@
-use futures::FutureExt;
+use futures::{join, FutureExt};
 use http::Response;
 use hyper::Body;
 use tracing::warn;
@@ -17,12 +17,15 @@ use crate::{
     effect::{requests::RestRequest, EffectBuilder},
     reactor::QueueKind,
     rpcs::info::{GetChainspecResult, GetValidatorChangesResult},
-    types::GetStatusResult,
+    types::{GetStatusResult, HealthStatus, NodeState},
 };
 
 /// The status URL path.
 pub const STATUS_API_PATH: &str = "status";
 
+/// The readiness URL path.
+pub const READY_API_PATH: &str = "ready";
+
 /// The metrics URL path.
 pub const METRICS_API_PATH: &str = "metrics";
 
@@ -55,6 +58,33 @@ pub(super) fn create_status_filter<REv: ReactorEventT>(
         .boxed()
 }
 
+/// Creates the readiness filter, responding with the node's health report.
+///
+/// The status code is 200 if the node is participating and none of its components has failed, or
+/// 503 otherwise.
+pub(super) fn create_ready_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(READY_API_PATH))
+        .and_then(move || async move {
+            let (node_state, health_report) = join!(
+                effect_builder.get_node_state(),
+                effect_builder.get_health_report()
+            );
+            let participating = !matches!(node_state, NodeState::Joining(_));
+            let status_code = if participating && health_report.status != HealthStatus::Failed {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            Ok::<_, Rejection>(
+                reply::with_status(reply::json(&health_report), status_code).into_response(),
+            )
+        })
+        .boxed()
+}
+
 pub(super) fn create_metrics_filter<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
 ) -> BoxedFilter<(Response<Body>,)> {
@@ -24,6 +24,7 @@ pub(super) async fn run<REv: ReactorEventT>(
 ) {
     // REST filters.
     let rest_status = filters::create_status_filter(effect_builder, api_version);
+    let rest_ready = filters::create_ready_filter(effect_builder);
     let rest_metrics = filters::create_metrics_filter(effect_builder);
     let rest_open_rpc = filters::create_rpc_schema_filter(effect_builder);
     let rest_validator_changes =
@@ -32,6 +33,7 @@ pub(super) async fn run<REv: ReactorEventT>(
 
     let service = warp::service(
         rest_status
+            .or(rest_ready)
             .or(rest_metrics)
             .or(rest_open_rpc)
             .or(rest_validator_changes)
@@ -17,6 +17,7 @@
 mod config;
 mod event;
 mod http_server;
+mod qps_limiter;
 pub mod rpcs;
 mod speculative_exec_config;
 mod speculative_exec_server;
@@ -25,7 +26,7 @@ use std::{convert::Infallible, fmt::Debug, time::Instant};
 
 use datasize::DataSize;
 use futures::join;
-use tracing::error;
+use tracing::{error, info};
 
 use casper_execution_engine::core::engine_state::{
     self, BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, QueryRequest,
@@ -34,16 +35,17 @@ use casper_execution_engine::core::engine_state::{
 use casper_hashing::Digest;
 use casper_types::{system::auction::EraValidators, ExecutionResult, Key, ProtocolVersion, URef};
 
-use self::rpcs::chain::BlockIdentifier;
+use self::{qps_limiter::QpsLimiter, rpcs::chain::BlockIdentifier};
 use super::Component;
 use crate::{
-    components::contract_runtime::EraValidatorsRequest,
+    components::contract_runtime::{EraValidatorsRequest, SpeculativeExecutionError},
     contract_runtime::SpeculativeExecutionState,
     effect::{
         announcements::RpcServerAnnouncement,
         requests::{
-            ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, MetricsRequest,
-            NetworkInfoRequest, NodeStateRequest, RpcRequest, StorageRequest,
+            ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, HealthRequest,
+            MetricsRequest, NetworkInfoRequest, NodeStateRequest, RpcRequest, StorageRequest,
+            SyncStatisticsRequest,
         },
         EffectBuilder, EffectExt, Effects, Responder,
     },
@@ -67,6 +69,8 @@ pub(crate) trait ReactorEventT:
     + From<NetworkInfoRequest>
     + From<StorageRequest>
     + From<NodeStateRequest>
+    + From<SyncStatisticsRequest>
+    + From<HealthRequest>
     + Send
 {
 }
@@ -82,6 +86,8 @@ impl<REv> ReactorEventT for REv where
         + From<NetworkInfoRequest>
         + From<StorageRequest>
         + From<NodeStateRequest>
+        + From<SyncStatisticsRequest>
+        + From<HealthRequest>
         + Send
         + 'static
 {
@@ -91,6 +97,9 @@ impl<REv> ReactorEventT for REv where
 pub(crate) struct InnerRpcServer {
     /// The instant at which the node has started.
     node_startup_instant: Instant,
+    /// The rate limiter of the running server.
+    #[data_size(skip)]
+    qps_limiter: QpsLimiter,
 }
 
 impl InnerRpcServer {
@@ -106,10 +115,10 @@ pub(crate) struct RpcServer {
     inner_rpc: Option<InnerRpcServer>,
     /// Inner speculative execution JSON-RPC server is present only when enabled
     /// in the speculative execution JSON-RPC server config.
-    /// The inner speculative execution JSON-RPC server as a struct would have
-    /// no fields and no methods because all that is needed to operate it is the
-    /// spawned tokio task, so a unit struct will suffice here.
-    speculative_exec: Option<()>,
+    /// Apart from the spawned tokio task, all that is needed to operate the
+    /// speculative execution JSON-RPC server is its rate limiter.
+    #[data_size(skip)]
+    speculative_exec: Option<QpsLimiter>,
 }
 
 impl RpcServer {
@@ -128,14 +137,15 @@ impl RpcServer {
         // so we save its state before we construct the `RpcServer`.
         let speculative_exec = if speculative_exec_config.enable_server {
             let builder = utils::start_listening(&speculative_exec_config.address)?;
+            let qps_limiter = QpsLimiter::new(speculative_exec_config.qps_limit);
             tokio::spawn(speculative_exec_server::run(
                 builder,
                 effect_builder,
                 api_version,
-                speculative_exec_config.qps_limit,
+                qps_limiter.clone(),
                 speculative_exec_config.max_body_bytes,
             ));
-            Some(())
+            Some(qps_limiter)
         } else {
             None
         };
@@ -148,16 +158,18 @@ impl RpcServer {
         }
 
         let builder = utils::start_listening(&config.address)?;
+        let qps_limiter = QpsLimiter::new(config.qps_limit);
         tokio::spawn(http_server::run(
             builder,
             effect_builder,
             api_version,
-            config.qps_limit,
+            qps_limiter.clone(),
             config.max_body_bytes,
         ));
 
         let inner_rpc = Some(InnerRpcServer {
             node_startup_instant,
+            qps_limiter,
         });
 
         Ok(RpcServer {
@@ -165,6 +177,42 @@ impl RpcServer {
             speculative_exec,
         })
     }
+
+    /// Applies the rate limits from a reloaded configuration to the running servers.
+    ///
+    /// Enabling or disabling a server, or changing any of its other settings, only takes effect
+    /// when the node is restarted.
+    pub(crate) fn update_config(
+        &self,
+        config: &Config,
+        speculative_exec_config: &SpeculativeExecConfig,
+    ) {
+        if let Some(inner_rpc) = &self.inner_rpc {
+            update_qps_limit(
+                &inner_rpc.qps_limiter,
+                config.qps_limit,
+                http_server::RPC_API_SERVER_NAME,
+            );
+        }
+        if let Some(qps_limiter) = &self.speculative_exec {
+            update_qps_limit(
+                qps_limiter,
+                speculative_exec_config.qps_limit,
+                speculative_exec_server::SPECULATIVE_EXEC_SERVER_NAME,
+            );
+        }
+    }
+}
+
+fn update_qps_limit(qps_limiter: &QpsLimiter, qps_limit: u64, server_name: &str) {
+    let old_qps_limit = qps_limiter.qps_limit();
+    if old_qps_limit != qps_limit {
+        qps_limiter.set_qps_limit(qps_limit);
+        info!(
+            old_qps_limit,
+            qps_limit, "updated rate limit of {} server", server_name
+        );
+    }
 }
 
 impl RpcServer {
@@ -222,7 +270,7 @@ impl RpcServer {
         effect_builder: EffectBuilder<REv>,
         block_header: BlockHeader,
         deploy: Deploy,
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        responder: Responder<Result<Option<ExecutionResult>, SpeculativeExecutionError>>,
     ) -> Effects<Event> {
         async move {
             let execution_prestate = SpeculativeExecutionState {
@@ -399,12 +447,22 @@ where
             Event::RpcRequest(RpcRequest::GetStatus { responder }) => {
                 let node_uptime = rpc_server.node_startup_instant().elapsed();
                 async move {
-                    let (last_added_block, peers, chainspec_info, consensus_status, node_state) = join!(
+                    let (
+                        last_added_block,
+                        peers,
+                        chainspec_info,
+                        consensus_status,
+                        node_state,
+                        sync_statistics,
+                        health,
+                    ) = join!(
                         effect_builder.get_highest_block_from_storage(),
                         effect_builder.network_peers(),
                         effect_builder.get_chainspec_info(),
                         effect_builder.consensus_status(),
-                        effect_builder.get_node_state()
+                        effect_builder.get_node_state(),
+                        effect_builder.get_sync_statistics(),
+                        effect_builder.get_health_report()
                     );
                     let status_feed = StatusFeed::new(
                         last_added_block,
@@ -413,6 +471,8 @@ where
                         consensus_status,
                         node_uptime,
                         node_state,
+                        sync_statistics,
+                        health,
                     );
                     responder.respond(status_feed).await;
                 }
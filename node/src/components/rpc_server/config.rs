@@ -1,6 +1,8 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use crate::utils::byte_size;
+
 /// Default binding address for the JSON-RPC HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
@@ -14,7 +16,7 @@ const DEFAULT_MAX_BODY_BYTES: u32 = 2_621_440;
 /// JSON-RPC HTTP server configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Setting to enable the HTTP server.
     pub enable_server: bool,
@@ -23,6 +25,7 @@ pub struct Config {
     /// Maximum rate limit in queries per second.
     pub qps_limit: u64,
     /// Maximum number of bytes to accept in a single request body.
+    #[serde(deserialize_with = "byte_size::deserialize")]
     pub max_body_bytes: u32,
 }
 
@@ -4,11 +4,14 @@ use casper_json_rpc::RequestHandlersBuilder;
 use casper_types::ProtocolVersion;
 
 use super::{
+    qps_limiter::QpsLimiter,
     rpcs::{
         account::PutDeploy,
         chain::{GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetStateRootHash},
         docs::ListRpcs,
-        info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
+        info::{
+            GetChainspec, GetDeploy, GetDeployStateDiff, GetPeers, GetStatus, GetValidatorChanges,
+        },
         state::{
             GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, GetTrie,
             QueryBalance, QueryGlobalState,
@@ -29,7 +32,7 @@ pub(super) async fn run<REv: ReactorEventT>(
     builder: Builder<AddrIncoming>,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
-    qps_limit: u64,
+    qps_limiter: QpsLimiter,
     max_body_bytes: u32,
 ) {
     let mut handlers = RequestHandlersBuilder::new();
@@ -42,6 +45,7 @@ pub(super) async fn run<REv: ReactorEventT>(
     GetBalance::register_as_handler(effect_builder, api_version, &mut handlers);
     GetAccountInfo::register_as_handler(effect_builder, api_version, &mut handlers);
     GetDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
+    GetDeployStateDiff::register_as_handler(effect_builder, api_version, &mut handlers);
     GetPeers::register_as_handler(effect_builder, api_version, &mut handlers);
     GetStatus::register_as_handler(effect_builder, api_version, &mut handlers);
     GetEraInfoBySwitchBlock::register_as_handler(effect_builder, api_version, &mut handlers);
@@ -57,7 +61,7 @@ pub(super) async fn run<REv: ReactorEventT>(
     super::rpcs::run(
         builder,
         handlers,
-        qps_limit,
+        qps_limiter,
         max_body_bytes,
         RPC_API_PATH,
         RPC_API_SERVER_NAME,
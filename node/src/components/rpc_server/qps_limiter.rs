@@ -0,0 +1,106 @@
+//! Rate limiting of incoming connections with a limit which can be changed at runtime.
+
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use tokio::time::{self, Duration, Instant};
+
+/// The period over which the number of accepted connections is limited.
+const PERIOD: Duration = Duration::from_secs(1);
+
+/// Limits the number of connections accepted per second.
+///
+/// Clones share the same limit and state, so the limit of a running server can be updated via a
+/// clone retained by the component.
+#[derive(Clone, Debug)]
+pub(super) struct QpsLimiter {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    qps_limit: AtomicU64,
+    window: Mutex<Window>,
+}
+
+/// The current rate limiting period.
+#[derive(Debug)]
+struct Window {
+    end: Instant,
+    remaining: u64,
+}
+
+impl QpsLimiter {
+    pub(super) fn new(qps_limit: u64) -> Self {
+        QpsLimiter {
+            inner: Arc::new(Inner {
+                qps_limit: AtomicU64::new(qps_limit),
+                window: Mutex::new(Window {
+                    end: Instant::now(),
+                    remaining: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Returns the current limit.
+    pub(super) fn qps_limit(&self) -> u64 {
+        self.inner.qps_limit.load(Ordering::SeqCst)
+    }
+
+    /// Sets a new limit, which takes effect from the start of the next period.
+    pub(super) fn set_qps_limit(&self, qps_limit: u64) {
+        self.inner.qps_limit.store(qps_limit, Ordering::SeqCst);
+    }
+
+    /// Waits until another connection may be accepted.
+    pub(super) async fn acquire(&self) {
+        loop {
+            let window_end = {
+                let mut window = self.inner.window.lock().expect("mutex poisoned");
+                let now = Instant::now();
+                if now >= window.end {
+                    window.end = now + PERIOD;
+                    window.remaining = self.qps_limit();
+                }
+                if window.remaining > 0 {
+                    window.remaining -= 1;
+                    return;
+                }
+                window.end
+            };
+            time::sleep_until(window_end).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn should_limit_and_apply_updated_limit() {
+        let limiter = QpsLimiter::new(2);
+
+        // The first two connections within a period are accepted immediately, the third has to
+        // wait for the next period.
+        for _ in 0..2 {
+            assert!(limiter.acquire().now_or_never().is_some());
+        }
+        assert!(limiter.acquire().now_or_never().is_none());
+
+        // Once raised, the new limit applies from the next period onwards.
+        limiter.set_qps_limit(4);
+        assert_eq!(limiter.qps_limit(), 4);
+        assert!(limiter.acquire().now_or_never().is_none());
+        time::advance(PERIOD).await;
+        for _ in 0..4 {
+            assert!(limiter.acquire().now_or_never().is_some());
+        }
+        assert!(limiter.acquire().now_or_never().is_none());
+    }
+}
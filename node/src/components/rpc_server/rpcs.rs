@@ -11,7 +11,7 @@ pub mod info;
 pub mod speculative_exec;
 pub mod state;
 
-use std::{convert::Infallible, str, sync::Arc, time::Duration};
+use std::{convert::Infallible, str, sync::Arc};
 
 use async_trait::async_trait;
 use http::header::ACCEPT_ENCODING;
@@ -20,14 +20,13 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tokio::sync::oneshot;
-use tower::ServiceBuilder;
 use tracing::info;
 use warp::Filter;
 
 use casper_json_rpc::{Error, Params, RequestHandlers, RequestHandlersBuilder, ReservedErrorCode};
 use casper_types::ProtocolVersion;
 
-use super::{ReactorEventT, RpcRequest};
+use super::{qps_limiter::QpsLimiter, ReactorEventT, RpcRequest};
 use crate::effect::EffectBuilder;
 pub use common::ErrorData;
 use docs::DocExample;
@@ -256,12 +255,13 @@ pub(super) trait RpcWithOptionalParams {
 pub(super) async fn run(
     builder: Builder<AddrIncoming>,
     handlers: RequestHandlers,
-    qps_limit: u64,
+    qps_limiter: QpsLimiter,
     max_body_bytes: u32,
     api_path: &'static str,
     server_name: &'static str,
 ) {
     let make_svc = hyper::service::make_service_fn(move |_| {
+        let qps_limiter = qps_limiter.clone();
         let service_routes = casper_json_rpc::route(
             api_path,
             max_body_bytes,
@@ -276,13 +276,12 @@ pub(super) async fn run(
             .with(warp::compression::gzip());
 
         let service = warp::service(service_routes_gzip.or(service_routes));
-        async move { Ok::<_, Infallible>(service.clone()) }
+        async move {
+            qps_limiter.acquire().await;
+            Ok::<_, Infallible>(service.clone())
+        }
     });
 
-    let make_svc = ServiceBuilder::new()
-        .rate_limit(qps_limit, Duration::from_secs(1))
-        .service(make_svc);
-
     let server = builder.serve(make_svc);
     info!(address = %server.local_addr(), "started {} server", server_name);
 
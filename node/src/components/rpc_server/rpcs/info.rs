@@ -11,7 +11,9 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey};
+use casper_types::{
+    CLValue, EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, Transform, TransformEntry,
+};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
@@ -22,8 +24,8 @@ use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
     types::{
-        Block, BlockHash, BlockHashAndHeight, ChainspecRawBytes, Deploy, DeployHash,
-        DeployMetadataExt, GetStatusResult, PeersMap,
+        json_compatibility::StoredValue, Block, BlockHash, BlockHashAndHeight, ChainspecRawBytes,
+        Deploy, DeployHash, DeployMetadataExt, GetStatusResult, PeersMap,
     },
 };
 
@@ -40,6 +42,22 @@ static GET_DEPLOY_RESULT: Lazy<GetDeployResult> = Lazy::new(|| GetDeployResult {
     }],
     block_hash_and_height: None,
 });
+static GET_DEPLOY_STATE_DIFF_PARAMS: Lazy<GetDeployStateDiffParams> =
+    Lazy::new(|| GetDeployStateDiffParams {
+        deploy_hash: *Deploy::doc_example().id(),
+    });
+static GET_DEPLOY_STATE_DIFF_RESULT: Lazy<GetDeployStateDiffResult> = Lazy::new(|| {
+    let cl_value = CLValue::from_t(1u64).unwrap();
+    GetDeployStateDiffResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        block_hash: *Block::doc_example().hash(),
+        state_diff: vec![StateDiffEntry {
+            key: Key::Hash([1; 32]).to_formatted_string(),
+            transform: Transform::WriteCLValue(cl_value.clone()),
+            value: Some(StoredValue::CLValue(cl_value)),
+        }],
+    }
+});
 static GET_PEERS_RESULT: Lazy<GetPeersResult> = Lazy::new(|| GetPeersResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     peers: GetStatusResult::doc_example().peers.clone(),
@@ -177,6 +195,148 @@ impl RpcWithParams for GetDeploy {
     }
 }
 
+/// Params for "info_get_deploy_state_diff" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDeployStateDiffParams {
+    /// The hash of an executed deploy.
+    pub deploy_hash: DeployHash,
+}
+
+impl DocExample for GetDeployStateDiffParams {
+    fn doc_example() -> &'static Self {
+        &*GET_DEPLOY_STATE_DIFF_PARAMS
+    }
+}
+
+/// A change to global state made by executing a deploy.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StateDiffEntry {
+    /// The formatted string of the `Key` under which the change was made.
+    pub key: String,
+    /// The transform applied under the key.
+    pub transform: Transform,
+    /// The value written under the key, if the transform records it in full.
+    pub value: Option<StoredValue>,
+}
+
+impl From<TransformEntry> for StateDiffEntry {
+    fn from(TransformEntry { key, transform }: TransformEntry) -> Self {
+        let value = match &transform {
+            Transform::WriteCLValue(cl_value) => Some(StoredValue::CLValue(cl_value.clone())),
+            Transform::WriteDeployInfo(deploy_info) => {
+                Some(StoredValue::DeployInfo(deploy_info.clone()))
+            }
+            Transform::WriteEraInfo(era_info) => Some(StoredValue::EraInfo(era_info.clone())),
+            Transform::WriteTransfer(transfer) => Some(StoredValue::Transfer(*transfer)),
+            Transform::WriteBid(bid) => Some(StoredValue::Bid(bid.clone())),
+            Transform::WriteWithdraw(unbonding_purses) => {
+                Some(StoredValue::Unbonding(unbonding_purses.clone()))
+            }
+            // Accounts, contracts and their Wasm are only recorded by their hash or not at all,
+            // and the remaining transforms don't write a value.
+            Transform::WriteAccount(_)
+            | Transform::WriteContractWasm
+            | Transform::WriteContract
+            | Transform::WriteContractPackage
+            | Transform::Identity
+            | Transform::AddInt32(_)
+            | Transform::AddUInt64(_)
+            | Transform::AddUInt128(_)
+            | Transform::AddUInt256(_)
+            | Transform::AddUInt512(_)
+            | Transform::AddKeys(_)
+            | Transform::Failure(_) => None,
+        };
+        StateDiffEntry {
+            key,
+            transform,
+            value,
+        }
+    }
+}
+
+/// Result for "info_get_deploy_state_diff" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDeployStateDiffResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// Hash of the block in which the deploy was executed.
+    pub block_hash: BlockHash,
+    /// The changes to global state made by the deploy, in the order they were recorded.
+    pub state_diff: Vec<StateDiffEntry>,
+}
+
+impl DocExample for GetDeployStateDiffResult {
+    fn doc_example() -> &'static Self {
+        &*GET_DEPLOY_STATE_DIFF_RESULT
+    }
+}
+
+/// "info_get_deploy_state_diff" RPC.
+pub struct GetDeployStateDiff {}
+
+#[async_trait]
+impl RpcWithParams for GetDeployStateDiff {
+    const METHOD: &'static str = "info_get_deploy_state_diff";
+    type RequestParams = GetDeployStateDiffParams;
+    type ResponseResult = GetDeployStateDiffResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let deploy_hash = params.deploy_hash;
+        let maybe_deploy_and_metadata = effect_builder
+            .make_request(
+                |responder| RpcRequest::GetDeploy {
+                    hash: deploy_hash,
+                    finalized_approvals: false,
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+
+        let maybe_execution_result = match maybe_deploy_and_metadata {
+            Some((_, DeployMetadataExt::Metadata(metadata))) => {
+                metadata.execution_results.into_iter().next()
+            }
+            Some((_, DeployMetadataExt::BlockInfo(_)))
+            | Some((_, DeployMetadataExt::Empty))
+            | None => None,
+        };
+        let (block_hash, execution_result) = match maybe_execution_result {
+            Some(block_hash_and_execution_result) => block_hash_and_execution_result,
+            None => {
+                let message = format!("no execution result of {} is stored", deploy_hash);
+                info!("{}", message);
+                return Err(Error::new(ErrorCode::NoSuchDeploy, message));
+            }
+        };
+
+        let effect = match execution_result {
+            ExecutionResult::Success { effect, .. } | ExecutionResult::Failure { effect, .. } => {
+                effect
+            }
+        };
+        let result = Self::ResponseResult {
+            api_version,
+            block_hash,
+            state_diff: effect
+                .transforms
+                .into_iter()
+                .map(StateDiffEntry::from)
+                .collect(),
+        };
+        Ok(result)
+    }
+}
+
 /// Result for "info_get_peers" RPC response.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
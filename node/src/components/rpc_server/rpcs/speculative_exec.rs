@@ -21,6 +21,7 @@ use super::{
     Error, ErrorCode, ReactorEventT, RpcWithParams,
 };
 use crate::{
+    components::contract_runtime::SpeculativeExecutionError,
     effect::{requests::RpcRequest, EffectBuilder},
     reactor::QueueKind,
     types::{Block, BlockHash, Deploy},
@@ -123,50 +124,55 @@ impl RpcWithParams for SpeculativeExec {
                 ErrorCode::NoSuchBlock,
                 "block hash not found".to_string(),
             )),
-            Err(error) => {
-                let rpc_error = match error {
-                    EngineStateError::RootNotFound(_) => Error::new(ErrorCode::NoSuchStateRoot, ""),
-                    EngineStateError::WasmPreprocessing(error) => {
-                        Error::new(ErrorCode::InvalidDeploy, &format!("{}", error))
-                    }
-                    EngineStateError::InvalidDeployItemVariant(error) => {
-                        Error::new(ErrorCode::InvalidDeploy, &error)
-                    }
-                    EngineStateError::InvalidProtocolVersion(_) => Error::new(
-                        ErrorCode::InvalidDeploy,
-                        &format!("deploy used invalid protocol version {}", error),
-                    ),
-                    EngineStateError::Deploy => Error::new(ErrorCode::InvalidDeploy, ""),
-                    EngineStateError::Genesis(_)
-                    | EngineStateError::WasmSerialization(_)
-                    | EngineStateError::Exec(_)
-                    | EngineStateError::Storage(_)
-                    | EngineStateError::Authorization
-                    | EngineStateError::InsufficientPayment
-                    | EngineStateError::GasConversionOverflow
-                    | EngineStateError::Finalization
-                    | EngineStateError::Bytesrepr(_)
-                    | EngineStateError::Mint(_)
-                    | EngineStateError::InvalidKeyVariant
-                    | EngineStateError::ProtocolUpgrade(_)
-                    | EngineStateError::CommitError(_)
-                    | EngineStateError::MissingSystemContractRegistry
-                    | EngineStateError::MissingSystemContractHash(_)
-                    | EngineStateError::RuntimeStackOverflow
-                    | EngineStateError::FailedToGetWithdrawKeys
-                    | EngineStateError::FailedToGetStoredWithdraws
-                    | EngineStateError::FailedToGetWithdrawPurses
-                    | EngineStateError::FailedToRetrieveUnbondingDelay
-                    | EngineStateError::FailedToRetrieveEraId => {
-                        Error::new(ReservedErrorCode::InternalError, &format!("{}", error))
-                    }
-                    _ => Error::new(
-                        ReservedErrorCode::InternalError,
-                        &format!("Unhandled engine state error: {}", error),
-                    ),
-                };
-                Err(rpc_error)
+            Err(SpeculativeExecutionError::InvalidDeploy(error)) => {
+                Err(Error::new(ErrorCode::InvalidDeploy, error.to_string()))
             }
+            Err(SpeculativeExecutionError::EngineState(error)) => Err(engine_state_error(error)),
+        }
+    }
+}
+
+/// Maps an execution engine error to the corresponding JSON-RPC error.
+fn engine_state_error(error: EngineStateError) -> Error {
+    match error {
+        EngineStateError::RootNotFound(_) => Error::new(ErrorCode::NoSuchStateRoot, ""),
+        EngineStateError::WasmPreprocessing(error) => {
+            Error::new(ErrorCode::InvalidDeploy, &format!("{}", error))
+        }
+        EngineStateError::InvalidDeployItemVariant(error) => {
+            Error::new(ErrorCode::InvalidDeploy, &error)
+        }
+        EngineStateError::InvalidProtocolVersion(_) => Error::new(
+            ErrorCode::InvalidDeploy,
+            &format!("deploy used invalid protocol version {}", error),
+        ),
+        EngineStateError::Deploy => Error::new(ErrorCode::InvalidDeploy, ""),
+        EngineStateError::Genesis(_)
+        | EngineStateError::WasmSerialization(_)
+        | EngineStateError::Exec(_)
+        | EngineStateError::Storage(_)
+        | EngineStateError::Authorization
+        | EngineStateError::InsufficientPayment
+        | EngineStateError::GasConversionOverflow
+        | EngineStateError::Finalization
+        | EngineStateError::Bytesrepr(_)
+        | EngineStateError::Mint(_)
+        | EngineStateError::InvalidKeyVariant
+        | EngineStateError::ProtocolUpgrade(_)
+        | EngineStateError::CommitError(_)
+        | EngineStateError::MissingSystemContractRegistry
+        | EngineStateError::MissingSystemContractHash(_)
+        | EngineStateError::RuntimeStackOverflow
+        | EngineStateError::FailedToGetWithdrawKeys
+        | EngineStateError::FailedToGetStoredWithdraws
+        | EngineStateError::FailedToGetWithdrawPurses
+        | EngineStateError::FailedToRetrieveUnbondingDelay
+        | EngineStateError::FailedToRetrieveEraId => {
+            Error::new(ReservedErrorCode::InternalError, &format!("{}", error))
         }
+        _ => Error::new(
+            ReservedErrorCode::InternalError,
+            &format!("Unhandled engine state error: {}", error),
+        ),
     }
 }
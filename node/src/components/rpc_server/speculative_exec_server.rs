@@ -3,7 +3,7 @@ use hyper::server::{conn::AddrIncoming, Builder};
 use casper_json_rpc::RequestHandlersBuilder;
 use casper_types::ProtocolVersion;
 
-use super::ReactorEventT;
+use super::{qps_limiter::QpsLimiter, ReactorEventT};
 use crate::{
     effect::EffectBuilder,
     rpcs::{speculative_exec::SpeculativeExec, RpcWithParams},
@@ -19,7 +19,7 @@ pub(super) async fn run<REv: ReactorEventT>(
     builder: Builder<AddrIncoming>,
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
-    qps_limit: u64,
+    qps_limiter: QpsLimiter,
     max_body_bytes: u32,
 ) {
     let mut handlers = RequestHandlersBuilder::new();
@@ -29,7 +29,7 @@ pub(super) async fn run<REv: ReactorEventT>(
     super::rpcs::run(
         builder,
         handlers,
-        qps_limit,
+        qps_limiter,
         max_body_bytes,
         SPECULATIVE_EXEC_API_PATH,
         SPECULATIVE_EXEC_SERVER_NAME,
@@ -98,17 +98,18 @@ use self::{
 };
 
 use crate::{
-    components::{consensus, Component},
+    components::{consensus, Component, HealthCheck},
     effect::{
         announcements::{
             BlocklistAnnouncement, ChainSynchronizerAnnouncement, ContractRuntimeAnnouncement,
         },
+        diagnostics_port::ComponentMemory,
         requests::{BeginGossipRequest, NetworkInfoRequest, NetworkRequest, StorageRequest},
         AutoClosingResponder, EffectBuilder, EffectExt, Effects,
     },
     reactor::{EventQueueHandle, Finalize, ReactorEvent},
     tls::{self, TlsCert, ValidationError},
-    types::NodeId,
+    types::{ComponentHealth, NodeId, SyncMode},
     utils::{self, display_error, Source, WithDir},
     NodeRng,
 };
@@ -141,6 +142,24 @@ impl<P> Display for OutgoingHandle<P> {
     }
 }
 
+/// Resolves the given known addresses, skipping any that fail to resolve.
+fn resolve_known_addresses(addresses: &[String]) -> HashSet<SocketAddr> {
+    let mut known_addresses = HashSet::new();
+    for address in addresses {
+        match utils::resolve_address(address) {
+            Ok(known_address) => {
+                if !known_addresses.insert(known_address) {
+                    warn!(%address, resolved=%known_address, "ignoring duplicated known address");
+                };
+            }
+            Err(ref err) => {
+                warn!(%address, err=display_error(err), "failed to resolve known address");
+            }
+        }
+    }
+    known_addresses
+}
+
 #[derive(DataSize)]
 pub(crate) struct SmallNetwork<REv, P>
 where
@@ -160,6 +179,10 @@ where
     /// Tracks nodes that have announced themselves as nodes that are syncing.
     syncing_nodes: HashSet<NodeId>,
 
+    /// Tracks nodes that have announced themselves as running in recent sync mode, i.e. as not
+    /// retaining history older than their trusted block.
+    recent_mode_nodes: HashSet<NodeId>,
+
     /// Channel signaling a shutdown of the small network.
     // Note: This channel is closed when `SmallNetwork` is dropped, signalling the receivers that
     // they should cease operation.
@@ -216,20 +239,9 @@ where
         registry: &Registry,
         small_network_identity: SmallNetworkIdentity,
         chain_info_source: C,
+        sync_mode: SyncMode,
     ) -> Result<(SmallNetwork<REv, P>, Effects<Event<P>>)> {
-        let mut known_addresses = HashSet::new();
-        for address in &cfg.known_addresses {
-            match utils::resolve_address(address) {
-                Ok(known_address) => {
-                    if !known_addresses.insert(known_address) {
-                        warn!(%address, resolved=%known_address, "ignoring duplicated known address");
-                    };
-                }
-                Err(ref err) => {
-                    warn!(%address, err=display_error(err), "failed to resolve known address");
-                }
-            }
-        }
+        let known_addresses = resolve_known_addresses(&cfg.known_addresses);
 
         // Assert we have at least one known address in the config.
         if known_addresses.is_empty() {
@@ -303,6 +315,12 @@ where
         } else {
             cfg.max_in_flight_demands as usize
         };
+        // Likewise for the incoming queue depth.
+        let incoming_queue_depth_max = if cfg.max_incoming_queue_depth == 0 {
+            usize::MAX
+        } else {
+            cfg.max_incoming_queue_depth as usize
+        };
         let context = Arc::new(NetworkContext {
             event_queue,
             our_id: NodeId::from(&small_network_identity),
@@ -318,7 +336,9 @@ where
             tarpit_duration: cfg.tarpit_duration,
             tarpit_chance: cfg.tarpit_chance,
             max_in_flight_demands: demand_max,
+            max_incoming_queue_depth: incoming_queue_depth_max,
             is_syncing: AtomicBool::new(true),
+            sync_mode,
         });
 
         // Run the server task.
@@ -344,6 +364,7 @@ where
             outgoing_manager,
             connection_symmetries: HashMap::new(),
             syncing_nodes: HashSet::new(),
+            recent_mode_nodes: HashSet::new(),
             shutdown_sender: Some(server_shutdown_sender),
             close_incoming_sender: Some(close_incoming_sender),
             close_incoming_receiver,
@@ -383,6 +404,34 @@ where
         Ok((component, effects))
     }
 
+    /// Learns the known addresses from a reloaded configuration and connects to any new ones.
+    ///
+    /// Existing connections are kept, including those to known addresses which have been removed
+    /// from the configuration.
+    pub(crate) fn update_known_addresses(
+        &mut self,
+        known_addresses: Vec<String>,
+    ) -> Effects<Event<P>> {
+        let resolved_addresses = resolve_known_addresses(&known_addresses);
+        if resolved_addresses.is_empty() {
+            warn!("no known addresses provided via reloaded config or all failed DNS resolution");
+            return Effects::new();
+        }
+        self.cfg.known_addresses = known_addresses;
+
+        let now = Instant::now();
+        let dial_requests: Vec<_> = resolved_addresses
+            .into_iter()
+            .filter_map(|addr| self.outgoing_manager.learn_addr(addr, true, now))
+            .collect();
+        info!(
+            new_addresses = dial_requests.len(),
+            "updated known addresses"
+        );
+
+        self.process_dial_requests(dial_requests)
+    }
+
     fn close_incoming_connections(&mut self) {
         info!("disconnecting incoming connections");
         let (close_incoming_sender, close_incoming_receiver) = watch::channel(());
@@ -687,6 +736,7 @@ where
                 peer_consensus_public_key,
                 sink,
                 is_syncing,
+                sync_mode,
             } => {
                 info!("new outgoing connection established");
 
@@ -712,6 +762,7 @@ where
                 {
                     self.connection_completed(peer_id);
                     self.update_syncing_nodes_set(peer_id, is_syncing);
+                    self.update_recent_mode_nodes_set(peer_id, sync_mode);
                 }
 
                 effects.extend(
@@ -825,6 +876,17 @@ where
         }
     }
 
+    /// Updates the set of known recent-mode nodes.
+    /// Peers not advertising a sync mode are assumed to retain their full history.
+    fn update_recent_mode_nodes_set(&mut self, peer_id: NodeId, sync_mode: Option<SyncMode>) {
+        if sync_mode == Some(SyncMode::Recent) {
+            debug!(%peer_id, "is running in recent sync mode");
+            self.recent_mode_nodes.insert(peer_id);
+        } else {
+            self.recent_mode_nodes.remove(&peer_id);
+        }
+    }
+
     /// Returns the set of connected nodes.
     pub(crate) fn peers(&self) -> BTreeMap<NodeId, String> {
         let mut ret = BTreeMap::new();
@@ -848,11 +910,25 @@ where
         ret
     }
 
+    /// Returns the number of messages queued for sending to peers.
+    pub(crate) fn queued_messages(&self) -> i64 {
+        self.net_metrics.queued_messages.get()
+    }
+
     /// Returns the node id of this network node.
     #[cfg(test)]
     pub(crate) fn node_id(&self) -> NodeId {
         self.context.our_id
     }
+
+    /// Breaks down the component's estimated heap usage by its major collections.
+    pub(crate) fn report_memory(&self, memory: &mut ComponentMemory) {
+        memory
+            .add_collection("outgoing_manager", &self.outgoing_manager)
+            .add_collection("connection_symmetries", &self.connection_symmetries)
+            .add_collection("syncing_nodes", &self.syncing_nodes)
+            .add_collection("recent_mode_nodes", &self.recent_mode_nodes);
+    }
 }
 
 impl<REv, P> Finalize for SmallNetwork<REv, P>
@@ -883,6 +959,24 @@ where
     }
 }
 
+impl<REv, P> HealthCheck for SmallNetwork<REv, P>
+where
+    P: Payload + 'static,
+    REv: ReactorEvent
+        + From<Event<P>>
+        + FromIncoming<P>
+        + From<StorageRequest>
+        + From<NetworkRequest<P>>,
+{
+    fn health(&self) -> ComponentHealth {
+        if self.peers().is_empty() {
+            ComponentHealth::degraded("no connected peers")
+        } else {
+            ComponentHealth::healthy()
+        }
+    }
+}
+
 impl<REv, P> Component<REv> for SmallNetwork<REv, P>
 where
     REv: ReactorEvent
@@ -1002,6 +1096,20 @@ where
 
                     responder.respond(symmetric_validator_peers).ignore()
                 }
+                NetworkInfoRequest::FullyConnectedArchivalPeers { responder } => {
+                    let mut symmetric_archival_peers: Vec<NodeId> = self
+                        .connection_symmetries
+                        .iter()
+                        .filter_map(|(node_id, sym)| {
+                            matches!(sym, ConnectionSymmetry::Symmetric { .. }).then(|| *node_id)
+                        })
+                        .filter(|node_id| !self.recent_mode_nodes.contains(node_id))
+                        .collect();
+
+                    symmetric_archival_peers.shuffle(rng);
+
+                    responder.respond(symmetric_archival_peers).ignore()
+                }
             },
             Event::PeerAddressReceived(gossiped_address) => {
                 let requests = self.outgoing_manager.learn_addr(
@@ -8,13 +8,14 @@ use std::net::SocketAddr;
 use casper_hashing::Digest;
 use casper_types::ProtocolVersion;
 use datasize::DataSize;
+use tracing::warn;
 
 use super::{
     counting_format::ConnectionId,
     message::{ConsensusCertificate, ConsensusKeyPair},
     Message,
 };
-use crate::types::Chainspec;
+use crate::types::{Chainspec, SyncMode};
 
 /// Data retained from the chainspec by the small networking component.
 ///
@@ -52,15 +53,22 @@ impl ChainInfo {
         consensus_keys: Option<&ConsensusKeyPair>,
         connection_id: ConnectionId,
         is_syncing: bool,
+        sync_mode: SyncMode,
     ) -> Message<P> {
         Message::Handshake {
             network_name: self.network_name.clone(),
             public_addr,
             protocol_version: self.protocol_version,
-            consensus_certificate: consensus_keys
-                .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
+            consensus_certificate: consensus_keys.and_then(|key_pair| {
+                ConsensusCertificate::create(connection_id, key_pair)
+                    .map_err(|error| {
+                        warn!(%error, "failed to sign consensus certificate, handshaking without it")
+                    })
+                    .ok()
+            }),
             is_syncing,
             chainspec_hash: Some(self.chainspec_hash),
+            sync_mode: Some(sync_mode),
         }
     }
 }
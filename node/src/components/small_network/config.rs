@@ -6,6 +6,7 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 use super::EstimatorWeights;
+use crate::utils::byte_size;
 
 /// Default binding address.
 ///
@@ -48,6 +49,7 @@ impl Default for Config {
             tarpit_duration: TimeDiff::from_seconds(600),
             tarpit_chance: 0.2,
             max_in_flight_demands: 50,
+            max_incoming_queue_depth: 5000,
             blocklist_retain_duration: TimeDiff::from_seconds(600),
         }
     }
@@ -56,7 +58,7 @@ impl Default for Config {
 /// Small network configuration.
 #[derive(DataSize, Debug, Clone, Deserialize, Serialize)]
 // Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Address to bind to.
     pub bind_address: String,
@@ -77,6 +79,7 @@ pub struct Config {
     /// Maximum number of incoming connections per unique peer. Unlimited if `0`.
     pub max_incoming_peer_connections: u16,
     /// Maximum number of bytes per second allowed for non-validating peers. Unlimited if 0.
+    #[serde(deserialize_with = "byte_size::deserialize")]
     pub max_outgoing_byte_rate_non_validators: u32,
     /// Maximum of requests answered from non-validating peers. Unlimited if 0.
     pub max_incoming_message_rate_non_validators: u32,
@@ -90,6 +93,9 @@ pub struct Config {
     pub tarpit_chance: f32,
     /// Maximum number of demands for objects that can be in-flight.
     pub max_in_flight_demands: u32,
+    /// Maximum number of events from peers waiting in the reactor's queues before reading from
+    /// peers is paused. Unlimited if 0.
+    pub max_incoming_queue_depth: u32,
     /// Duration peers are kept on the block list, before being redeemed.
     pub blocklist_retain_duration: TimeDiff,
 }
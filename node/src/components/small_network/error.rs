@@ -1,15 +1,14 @@
-use std::{error, io, net::SocketAddr, result, sync::Arc};
+use std::{error, io, net::SocketAddr, result};
 
 use casper_hashing::Digest;
-use casper_types::{crypto, ProtocolVersion, SecretKey};
+use casper_types::{crypto, ProtocolVersion};
 use datasize::DataSize;
 use openssl::{error::ErrorStack, ssl};
 use serde::Serialize;
 use thiserror::Error;
 
 use crate::{
-    tls::ValidationError,
-    utils::{LoadError, Loadable, ResolveAddressError},
+    components::consensus::LoadSigningKeyError, tls::ValidationError, utils::ResolveAddressError,
 };
 
 pub(super) type Result<T> = result::Result<T, Error>;
@@ -25,7 +24,7 @@ pub enum Error {
     LoadConsensusKeys(
         #[serde(skip_serializing)]
         #[source]
-        LoadError<<Arc<SecretKey> as Loadable>::Error>,
+        LoadSigningKeyError,
     ),
     /// Failed to create a TCP listener.
     #[error("failed to create listener on {1}")]
@@ -21,6 +21,8 @@ use crate::{
         requests::{NetworkInfoRequest, NetworkRequest},
     },
     protocol::Message as ProtocolMessage,
+    types::SyncMode,
+    utils::opt_display::OptDisplay,
 };
 
 const _SMALL_NETWORK_EVENT_SIZE: usize = mem::size_of::<Event<ProtocolMessage>>();
@@ -263,6 +265,8 @@ pub(crate) enum OutgoingConnection<P> {
         sink: SplitSink<FullTransport<P>, Arc<Message<P>>>,
         /// Holds the information whether the remote node is syncing.
         is_syncing: bool,
+        /// The sync mode advertised by the remote node, if any.
+        sync_mode: Option<SyncMode>,
     },
 }
 
@@ -284,11 +288,15 @@ impl<P> Display for OutgoingConnection<P> {
                 peer_consensus_public_key,
                 sink: _,
                 is_syncing,
+                sync_mode,
             } => {
                 write!(
                     f,
-                    "connection established to {}/{}, is_syncing: {}",
-                    peer_addr, peer_id, is_syncing
+                    "connection established to {}/{}, is_syncing: {}, sync_mode: {}",
+                    peer_addr,
+                    peer_id,
+                    is_syncing,
+                    OptDisplay::new(sync_mode.as_ref(), "unknown")
                 )?;
 
                 if let Some(public_key) = peer_consensus_public_key {
@@ -1,13 +1,14 @@
+#[cfg(test)]
+use std::sync::Arc;
 use std::{
     fmt::{self, Debug, Display, Formatter},
     net::SocketAddr,
-    sync::Arc,
 };
 
 use casper_hashing::Digest;
+use casper_types::{crypto, AsymmetricType, ProtocolVersion, PublicKey, Signature};
 #[cfg(test)]
-use casper_types::testing::TestRng;
-use casper_types::{crypto, AsymmetricType, ProtocolVersion, PublicKey, SecretKey, Signature};
+use casper_types::{testing::TestRng, SecretKey};
 use datasize::DataSize;
 use futures::future::BoxFuture;
 use serde::{
@@ -15,7 +16,11 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
-use crate::{effect::EffectBuilder, types::NodeId, utils::opt_display::OptDisplay};
+use crate::{
+    effect::EffectBuilder,
+    types::{NodeId, SigningError, SigningKey, SyncMode},
+    utils::opt_display::OptDisplay,
+};
 
 use super::counting_format::ConnectionId;
 
@@ -45,6 +50,9 @@ pub(crate) enum Message<P> {
         /// Hash of the chainspec the node is running.
         #[serde(default)]
         chainspec_hash: Option<Digest>,
+        /// The sync mode of the node, indicating how much history it retains.
+        #[serde(default)]
+        sync_mode: Option<SyncMode>,
     },
     Payload(P),
 }
@@ -68,6 +76,15 @@ impl<P: Payload> Message<P> {
         }
     }
 
+    /// Determines whether or not a message is a consensus message.
+    #[inline]
+    pub(super) fn is_consensus(&self) -> bool {
+        match self {
+            Message::Handshake { .. } => false,
+            Message::Payload(payload) => payload.is_consensus(),
+        }
+    }
+
     /// Returns the incoming resource estimate of the payload.
     #[inline]
     pub(super) fn payload_incoming_resource_estimate(&self, weights: &EstimatorWeights) -> u32 {
@@ -110,13 +127,13 @@ impl<P: Payload> Message<P> {
 
 /// A pair of secret keys used by consensus.
 pub(super) struct ConsensusKeyPair {
-    secret_key: Arc<SecretKey>,
+    secret_key: SigningKey,
     public_key: PublicKey,
 }
 
 impl ConsensusKeyPair {
     /// Creates a new key pair for consensus signing.
-    pub(super) fn new(secret_key: Arc<SecretKey>, public_key: PublicKey) -> Self {
+    pub(super) fn new(secret_key: SigningKey, public_key: PublicKey) -> Self {
         Self {
             secret_key,
             public_key,
@@ -124,8 +141,8 @@ impl ConsensusKeyPair {
     }
 
     /// Sign a value using this keypair.
-    fn sign<T: AsRef<[u8]>>(&self, value: T) -> Signature {
-        crypto::sign(value, &self.secret_key, &self.public_key)
+    fn sign<T: AsRef<[u8]>>(&self, value: T) -> Result<Signature, SigningError> {
+        self.secret_key.sign(value, &self.public_key)
     }
 }
 
@@ -142,12 +159,15 @@ pub(crate) struct ConsensusCertificate {
 
 impl ConsensusCertificate {
     /// Creates a new consensus certificate from a connection ID and key pair.
-    pub(super) fn create(connection_id: ConnectionId, key_pair: &ConsensusKeyPair) -> Self {
-        let signature = key_pair.sign(connection_id.as_bytes());
-        ConsensusCertificate {
+    pub(super) fn create(
+        connection_id: ConnectionId,
+        key_pair: &ConsensusKeyPair,
+    ) -> Result<Self, SigningError> {
+        let signature = key_pair.sign(connection_id.as_bytes())?;
+        Ok(ConsensusCertificate {
             public_key: key_pair.public_key.clone(),
             signature,
-        }
+        })
     }
 
     /// Validates a certificate, returning a `PublicKey` if valid.
@@ -163,8 +183,9 @@ impl ConsensusCertificate {
         let public_key = PublicKey::from(&secret_key);
         ConsensusCertificate::create(
             ConnectionId::random(rng),
-            &ConsensusKeyPair::new(Arc::new(secret_key), public_key),
+            &ConsensusKeyPair::new(SigningKey::from(Arc::new(secret_key)), public_key),
         )
+        .unwrap()
     }
 }
 
@@ -265,16 +286,18 @@ impl<P: Display> Display for Message<P> {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                sync_mode,
             } => {
                 write!(
                     f,
-                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}",
+                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}, sync_mode: {}",
                     network_name,
                     public_addr,
                     protocol_version,
                     OptDisplay::new(consensus_certificate.as_ref(), "none"),
                     is_syncing,
-                    OptDisplay::new(chainspec_hash.as_ref(), "none")
+                    OptDisplay::new(chainspec_hash.as_ref(), "none"),
+                    OptDisplay::new(sync_mode.as_ref(), "none")
                 )
             }
             Message::Payload(payload) => write!(f, "payload: {}", payload),
@@ -339,6 +362,12 @@ pub(crate) trait Payload:
         false
     }
 
+    /// Determines if the payload is a consensus message, to be scheduled on the consensus queue
+    /// rather than behind other incoming messages.
+    fn is_consensus(&self) -> bool {
+        false
+    }
+
     /// Indicates a message is not safe to send to a syncing node.
     ///
     /// This functionality should be removed once multiplexed networking lands.
@@ -562,6 +591,7 @@ mod tests {
             consensus_certificate: Some(ConsensusCertificate::random(&mut rng)),
             is_syncing: false,
             chainspec_hash: Some(Digest::hash("example-chainspec")),
+            sync_mode: Some(SyncMode::Recent),
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -597,13 +627,15 @@ mod tests {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                sync_mode,
             } => {
                 assert_eq!(network_name, "example-handshake");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
                 assert!(!is_syncing);
-                assert!(chainspec_hash.is_none())
+                assert!(chainspec_hash.is_none());
+                assert!(sync_mode.is_none())
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
@@ -623,6 +655,7 @@ mod tests {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                sync_mode,
             } => {
                 assert!(!is_syncing);
                 assert_eq!(network_name, "serialization-test");
@@ -630,7 +663,8 @@ mod tests {
                 assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
                 assert!(consensus_certificate.is_none());
                 assert!(!is_syncing);
-                assert!(chainspec_hash.is_none())
+                assert!(chainspec_hash.is_none());
+                assert!(sync_mode.is_none())
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
@@ -650,6 +684,7 @@ mod tests {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                sync_mode,
             } => {
                 assert_eq!(network_name, "example-handshake");
                 assert_eq!(public_addr, ([12, 34, 56, 78], 12346).into());
@@ -676,7 +711,8 @@ mod tests {
                     .unwrap()
                 );
                 assert!(!is_syncing);
-                assert!(chainspec_hash.is_none())
+                assert!(chainspec_hash.is_none());
+                assert!(sync_mode.is_none())
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
@@ -696,6 +732,7 @@ mod tests {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                sync_mode,
             } => {
                 assert!(!is_syncing);
                 assert_eq!(network_name, "example-handshake");
@@ -722,7 +759,8 @@ mod tests {
                     .unwrap()
                 );
                 assert!(!is_syncing);
-                assert!(chainspec_hash.is_none())
+                assert!(chainspec_hash.is_none());
+                assert!(sync_mode.is_none())
             }
             Message::Payload(_) => {
                 panic!("did not expect modern handshake to deserialize to payload")
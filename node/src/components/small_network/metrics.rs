@@ -1,4 +1,4 @@
-use std::sync::Weak;
+use std::{sync::Weak, time::Duration};
 
 use prometheus::{Counter, IntCounter, IntGauge, Registry};
 use tracing::debug;
@@ -116,6 +116,8 @@ pub(super) struct Metrics {
     pub(super) accumulated_outgoing_limiter_delay: Counter,
     /// Total time spent delaying incoming traffic from non-validators due to limiter, in seconds.
     pub(super) accumulated_incoming_limiter_delay: Counter,
+    /// Total time spent not reading from peers due to full reactor queues, in seconds.
+    pub(super) accumulated_incoming_backpressure_delay: Counter,
 
     /// Registry instance.
     registry: Registry,
@@ -324,6 +326,11 @@ impl Metrics {
             "accumulated_incoming_limiter_delay",
             "seconds spent delaying incoming traffic from non-validators due to limiter, in seconds."
         )?;
+        let accumulated_incoming_backpressure_delay = Counter::new(
+            "accumulated_incoming_backpressure_delay",
+            "seconds spent not reading from peers due to full reactor queues, summed over all \
+            connections",
+        )?;
 
         registry.register(Box::new(broadcast_requests.clone()))?;
         registry.register(Box::new(direct_message_requests.clone()))?;
@@ -382,6 +389,7 @@ impl Metrics {
 
         registry.register(Box::new(accumulated_outgoing_limiter_delay.clone()))?;
         registry.register(Box::new(accumulated_incoming_limiter_delay.clone()))?;
+        registry.register(Box::new(accumulated_incoming_backpressure_delay.clone()))?;
 
         Ok(Metrics {
             broadcast_requests,
@@ -434,6 +442,7 @@ impl Metrics {
             requests_for_trie_finished,
             accumulated_outgoing_limiter_delay,
             accumulated_incoming_limiter_delay,
+            accumulated_incoming_backpressure_delay,
             registry: registry.clone(),
         })
     }
@@ -558,6 +567,17 @@ impl Metrics {
             debug!("not recording metrics, component already shut down");
         }
     }
+
+    /// Records time spent not reading from a peer due to full reactor queues.
+    pub(super) fn record_incoming_backpressure_delay(this: &Weak<Self>, delay: Duration) {
+        if let Some(metrics) = this.upgrade() {
+            metrics
+                .accumulated_incoming_backpressure_delay
+                .inc_by(delay.as_secs_f64());
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
 }
 
 impl Drop for Metrics {
@@ -619,5 +639,6 @@ impl Drop for Metrics {
 
         unregister_metric!(self.registry, self.accumulated_outgoing_limiter_delay);
         unregister_metric!(self.registry, self.accumulated_incoming_limiter_delay);
+        unregister_metric!(self.registry, self.accumulated_incoming_backpressure_delay);
     }
 }
@@ -10,7 +10,7 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc, Weak,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use bincode::Options;
@@ -56,7 +56,7 @@ use crate::{
     effect::{requests::NetworkRequest, AutoClosingResponder, EffectBuilder},
     reactor::{EventQueueHandle, QueueKind},
     tls::{self, TlsCert},
-    types::NodeId,
+    types::{NodeId, SyncMode},
     utils::display_error,
 };
 
@@ -76,6 +76,8 @@ struct HandshakeOutcome {
     peer_consensus_public_key: Option<PublicKey>,
     /// Holds the information whether the remote node is syncing.
     is_peer_syncing: bool,
+    /// The sync mode advertised by the peer, if any.
+    peer_sync_mode: Option<SyncMode>,
 }
 
 /// Low-level TLS connection function.
@@ -158,6 +160,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_sync_mode: sync_mode,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
@@ -183,6 +186,7 @@ where
                 peer_consensus_public_key,
                 sink,
                 is_syncing,
+                sync_mode,
             }
         }
         Err(error) => OutgoingConnection::Failed {
@@ -226,8 +230,12 @@ where
     pub(super) tarpit_chance: f32,
     /// Maximum number of demands allowed to be running at once. If 0, no limit is enforced.
     pub(super) max_in_flight_demands: usize,
+    /// Maximum number of events from peers in the reactor's queues before reading is paused.
+    pub(super) max_incoming_queue_depth: usize,
     /// Flag indicating whether this node is syncing.
     pub(super) is_syncing: AtomicBool,
+    /// The sync mode of this node, advertised during handshake.
+    pub(super) sync_mode: SyncMode,
 }
 
 /// Handles an incoming connection.
@@ -273,6 +281,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: _,
+            peer_sync_mode: _,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("validator_id", &field::display(public_key));
@@ -383,6 +392,7 @@ where
         context.consensus_keys.as_ref(),
         connection_id,
         context.is_syncing.load(Ordering::SeqCst),
+        context.sync_mode,
     );
 
     let serialized_handshake_message = Pin::new(&mut encoder)
@@ -422,6 +432,7 @@ where
         consensus_certificate,
         is_syncing,
         chainspec_hash,
+        sync_mode,
     } = remote_message
     {
         debug!(%protocol_version, "handshake received");
@@ -480,6 +491,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_sync_mode: sync_mode,
         })
     } else {
         // Received a non-handshake, this is an error.
@@ -560,9 +572,42 @@ pub(super) async fn server<P, REv>(
     }
 }
 
+/// Interval at which the reactor's queues are checked while reading from peers is paused.
+const INCOMING_BACKPRESSURE_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Returns the number of events from peers waiting in the reactor's queues.
+fn incoming_queue_depth<REv>(context: &NetworkContext<REv>) -> usize {
+    [
+        QueueKind::NetworkIncoming,
+        QueueKind::NetworkLowPriority,
+        QueueKind::NetworkDemand,
+    ]
+    .iter()
+    .map(|queue_kind| context.event_queue.event_queue_count(*queue_kind))
+    .sum()
+}
+
+/// Waits until the number of events from peers in the reactor's queues is within the limit.
+async fn wait_for_incoming_capacity<REv>(context: &NetworkContext<REv>) {
+    if incoming_queue_depth(context) <= context.max_incoming_queue_depth {
+        return;
+    }
+
+    let start = Instant::now();
+    trace!("reactor queues full, pausing reading from peer");
+    while incoming_queue_depth(context) > context.max_incoming_queue_depth {
+        tokio::time::sleep(INCOMING_BACKPRESSURE_POLL_INTERVAL).await;
+    }
+    let delay = start.elapsed();
+    trace!(?delay, "resuming reading from peer");
+    Metrics::record_incoming_backpressure_delay(&context.net_metrics, delay);
+}
+
 /// Network message reader.
 ///
-/// Schedules all received messages until the stream is closed or an error occurs.
+/// Schedules all received messages until the stream is closed or an error occurs.  Although the
+/// reader runs as an effect, each message starts a new correlation rather than belonging to that
+/// of the event which set up the connection.
 pub(super) async fn message_reader<REv, P>(
     context: Arc<NetworkContext<REv>>,
     mut stream: SplitStream<FullTransport<P>>,
@@ -583,6 +628,11 @@ where
                 Ok(msg) => {
                     trace!(%msg, "message received");
 
+                    // Before handing the message to the reactor, wait for it to catch up if it is
+                    // falling behind. Not reading any further lets TCP flow control push back on
+                    // the peer instead of us buffering an unbounded number of events.
+                    wait_for_incoming_capacity(&context).await;
+
                     let effect_builder = EffectBuilder::new(context.event_queue);
 
                     match msg.try_into_demand(effect_builder, peer_id) {
@@ -639,7 +689,7 @@ where
                             // Schedule the created event.
                             context
                                 .event_queue
-                                .schedule::<REv>(event, QueueKind::NetworkDemand)
+                                .schedule_external::<REv>(event, QueueKind::NetworkDemand)
                                 .await;
                         }
                         Err(msg) => {
@@ -653,7 +703,9 @@ where
                                 )
                                 .await;
 
-                            let queue_kind = if msg.is_low_priority() {
+                            let queue_kind = if msg.is_consensus() {
+                                QueueKind::Consensus
+                            } else if msg.is_low_priority() {
                                 QueueKind::NetworkLowPriority
                             } else {
                                 QueueKind::NetworkIncoming
@@ -661,7 +713,7 @@ where
 
                             context
                                 .event_queue
-                                .schedule(
+                                .schedule_external(
                                     Event::IncomingMessage {
                                         peer_id: Box::new(peer_id),
                                         msg: Box::new(msg),
@@ -41,7 +41,7 @@ use crate::{
         network::{Network, NetworkedReactor, Nodes},
         ConditionCheckReactor,
     },
-    types::NodeId,
+    types::{NodeId, SyncMode},
     NodeRng,
 };
 
@@ -193,6 +193,7 @@ impl Reactor for TestReactor {
             registry,
             small_network_identity,
             ChainInfo::create_for_testing(),
+            SyncMode::Recent,
         )?;
         let gossiper_config = gossiper::Config::new_with_small_timeouts();
         let address_gossiper =
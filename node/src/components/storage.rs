@@ -44,7 +44,8 @@ use std::{
     collections::{btree_map::Entry, BTreeMap, HashSet},
     convert::TryFrom,
     fmt::{self, Display, Formatter},
-    fs, mem,
+    fs,
+    mem::{self, MaybeUninit},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -72,7 +73,7 @@ use casper_types::{
 // The reactor! macro needs this in the fetcher tests
 pub(crate) use crate::effect::requests::StorageRequest;
 use crate::{
-    components::{consensus, fetcher::FetchedOrNotFound, Component},
+    components::{consensus, fetcher::FetchedOrNotFound, Component, HealthCheck},
     effect::{
         incoming::{NetRequest, NetRequestIncoming},
         requests::{MarkBlockCompletedRequest, NetworkRequest, StateStoreRequest},
@@ -84,12 +85,12 @@ use crate::{
     types::{
         AvailableBlockRange, Block, BlockAndDeploys, BlockBody, BlockHash, BlockHashAndHeight,
         BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId,
-        BlockSignatures, BlockWithMetadata, Deploy, DeployHash, DeployMetadata, DeployMetadataExt,
-        DeployWithFinalizedApprovals, FinalizedApprovals, FinalizedApprovalsWithId,
-        HashingAlgorithmVersion, Item, MerkleBlockBody, MerkleBlockBodyPart, MerkleLinkedListNode,
-        NodeId,
+        BlockSignatures, BlockWithMetadata, ComponentHealth, Deploy, DeployHash, DeployMetadata,
+        DeployMetadataExt, DeployWithFinalizedApprovals, FinalizedApprovals,
+        FinalizedApprovalsWithId, HashingAlgorithmVersion, Item, MerkleBlockBody,
+        MerkleBlockBodyPart, MerkleLinkedListNode, NodeId,
     },
-    utils::{display_error, WithDir},
+    utils::{byte_size, display_error, WithDir},
     NodeRng,
 };
 use disjoint_sequences::{DisjointSequences, Sequence};
@@ -100,6 +101,10 @@ use object_pool::ObjectPool;
 
 /// Filename for the LMDB database created by the Storage component.
 const STORAGE_DB_FILENAME: &str = "storage.lmdb";
+/// The percentage of the maximum database size in use at which storage reports itself degraded.
+const DEGRADED_USAGE_PERCENT: u64 = 80;
+/// The percentage of the maximum database size in use at which storage reports itself failed.
+const FAILED_USAGE_PERCENT: u64 = 95;
 
 /// We can set this very low, as there is only a single reader/writer accessing the component at any
 /// one time.
@@ -155,6 +160,8 @@ struct Databases {
 pub struct Storage {
     /// Storage location.
     root: PathBuf,
+    /// The maximum size of the database file in bytes.
+    max_size: usize,
     /// Environment holding LMDB databases.
     #[data_size(skip)]
     env: Environment,
@@ -300,6 +307,26 @@ where
     }
 }
 
+impl HealthCheck for Storage {
+    fn health(&self) -> ComponentHealth {
+        let used = match self.used_size() {
+            Ok(used) => used,
+            Err(error) => {
+                return ComponentHealth::failed(format!("cannot read database info: {}", error))
+            }
+        };
+        let max_size = self.max_size as u64;
+        let reason = || format!("database uses {} of {} bytes", used, max_size);
+        if used.saturating_mul(100) >= max_size.saturating_mul(FAILED_USAGE_PERCENT) {
+            ComponentHealth::failed(reason())
+        } else if used.saturating_mul(100) >= max_size.saturating_mul(DEGRADED_USAGE_PERCENT) {
+            ComponentHealth::degraded(reason())
+        } else {
+            ComponentHealth::healthy()
+        }
+    }
+}
+
 impl Storage {
     /// Creates a new storage component.
     #[allow(clippy::too_many_arguments)]
@@ -312,20 +339,79 @@ impl Storage {
         last_emergency_restart: Option<EraId>,
         verifiable_chunked_hash_activation: EraId,
     ) -> Result<Self, FatalStorageError> {
+        Self::open(
+            cfg,
+            false,
+            hard_reset_to_start_of_era.map(|era_id| (era_id, protocol_version)),
+            network_name,
+            finality_threshold_fraction,
+            last_emergency_restart,
+            verifiable_chunked_hash_activation,
+        )
+    }
+
+    /// Opens the existing storage of a node for reading only, e.g. to inspect the data of a
+    /// stopped node.
+    ///
+    /// Unlike [`Storage::new`], this never creates, moves or modifies any of the storage files,
+    /// and fails if there is no database in the network's subdirectory.  Note that LMDB still
+    /// creates the database's `-lock` file if it is missing, so the directory must be writable.
+    pub(crate) fn open_read_only(
+        cfg: &WithDir<Config>,
+        network_name: &str,
+        finality_threshold_fraction: Ratio<u64>,
+        last_emergency_restart: Option<EraId>,
+        verifiable_chunked_hash_activation: EraId,
+    ) -> Result<Self, FatalStorageError> {
+        Self::open(
+            cfg,
+            true,
+            None,
+            network_name,
+            finality_threshold_fraction,
+            last_emergency_restart,
+            verifiable_chunked_hash_activation,
+        )
+    }
+
+    /// Opens the storage, creating it first unless `read_only` is set.
+    ///
+    /// If `hard_reset` is `Some((era_id, protocol_version))`, blocks from `era_id` onwards with a
+    /// protocol version lower than `protocol_version` are purged.  It must be `None` if `read_only`
+    /// is set.
+    fn open(
+        cfg: &WithDir<Config>,
+        read_only: bool,
+        hard_reset: Option<(EraId, ProtocolVersion)>,
+        network_name: &str,
+        finality_threshold_fraction: Ratio<u64>,
+        last_emergency_restart: Option<EraId>,
+        verifiable_chunked_hash_activation: EraId,
+    ) -> Result<Self, FatalStorageError> {
+        debug_assert!(!read_only || hard_reset.is_none());
         let config = cfg.value();
 
-        // Create the database directory.
         let mut root = cfg.with_dir(config.path.clone());
         let network_subdir = root.join(network_name);
 
-        if !network_subdir.exists() {
-            fs::create_dir_all(&network_subdir).map_err(|err| {
-                FatalStorageError::CreateDatabaseDirectory(network_subdir.clone(), err)
-            })?;
-        }
+        if read_only {
+            let db_path = network_subdir.join(STORAGE_DB_FILENAME);
+            if !db_path.exists() {
+                return Err(FatalStorageError::MissingStorageFiles {
+                    missing_files: vec![db_path],
+                });
+            }
+        } else {
+            // Create the database directory.
+            if !network_subdir.exists() {
+                fs::create_dir_all(&network_subdir).map_err(|err| {
+                    FatalStorageError::CreateDatabaseDirectory(network_subdir.clone(), err)
+                })?;
+            }
 
-        if should_move_storage_files_to_network_subdir(&root, &STORAGE_FILES)? {
-            move_storage_files_to_network_subdir(&root, &network_subdir, &STORAGE_FILES)?;
+            if should_move_storage_files_to_network_subdir(&root, &STORAGE_FILES)? {
+                move_storage_files_to_network_subdir(&root, &network_subdir, &STORAGE_FILES)?;
+            }
         }
 
         root = network_subdir;
@@ -337,9 +423,14 @@ impl Storage {
             .saturating_add(config.max_deploy_metadata_store_size);
 
         // Creates the environment and databases.
+        let access_flags = if read_only {
+            EnvironmentFlags::READ_ONLY
+        } else {
+            OS_FLAGS
+        };
         let env = Environment::new()
             .set_flags(
-                OS_FLAGS
+                access_flags
                 // We manage our own directory.
                 | EnvironmentFlags::NO_SUB_DIR
                 // Disable thread local storage, strongly suggested for operation with tokio.
@@ -352,31 +443,25 @@ impl Storage {
             .set_map_size(total_size)
             .open(&root.join(STORAGE_DB_FILENAME))?;
 
-        let block_header_db = env.create_db(Some("block_header"), DatabaseFlags::empty())?;
-        let block_metadata_db = env.create_db(Some("block_metadata"), DatabaseFlags::empty())?;
-        let deploy_db = env.create_db(Some("deploys"), DatabaseFlags::empty())?;
-        let deploy_metadata_db = env.create_db(Some("deploy_metadata"), DatabaseFlags::empty())?;
-        let transfer_db = env.create_db(Some("transfer"), DatabaseFlags::empty())?;
-        let state_store_db = env.create_db(Some("state_store"), DatabaseFlags::empty())?;
-        let finalized_approvals_db =
-            env.create_db(Some("finalized_approvals"), DatabaseFlags::empty())?;
-        let block_body_v1_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
-        let block_body_v2_db = env.create_db(Some("block_body_merkle"), DatabaseFlags::empty())?;
-        let deploy_hashes_db = env.create_db(Some("deploy_hashes"), DatabaseFlags::empty())?;
-        let transfer_hashes_db = env.create_db(Some("transfer_hashes"), DatabaseFlags::empty())?;
-        let proposer_db = env.create_db(Some("proposers"), DatabaseFlags::empty())?;
-
-        // We now need to restore the block-height index. Log messages allow timing here.
-        info!("reindexing block store");
-        let mut block_height_index = BTreeMap::new();
-        let mut switch_block_era_id_index = BTreeMap::new();
-        let mut deploy_hash_index = BTreeMap::new();
-        let mut block_txn = env.begin_rw_txn()?;
-        let mut cursor = block_txn.open_rw_cursor(block_header_db)?;
-
-        let mut deleted_block_hashes = HashSet::new();
-        let mut deleted_block_body_hashes_v1 = HashSet::new();
-        let mut deleted_deploy_hashes = HashSet::<DeployHash>::new();
+        let open_db = |name: &str| {
+            if read_only {
+                env.open_db(Some(name))
+            } else {
+                env.create_db(Some(name), DatabaseFlags::empty())
+            }
+        };
+        let block_header_db = open_db("block_header")?;
+        let block_metadata_db = open_db("block_metadata")?;
+        let deploy_db = open_db("deploys")?;
+        let deploy_metadata_db = open_db("deploy_metadata")?;
+        let transfer_db = open_db("transfer")?;
+        let state_store_db = open_db("state_store")?;
+        let finalized_approvals_db = open_db("finalized_approvals")?;
+        let block_body_v1_db = open_db("block_body")?;
+        let block_body_v2_db = open_db("block_body_merkle")?;
+        let deploy_hashes_db = open_db("deploy_hashes")?;
+        let transfer_hashes_db = open_db("transfer_hashes")?;
+        let proposer_db = open_db("proposers")?;
 
         let databases = Databases {
             block_body_v1_db,
@@ -386,78 +471,114 @@ impl Storage {
             proposer_db,
         };
 
-        // Note: `iter_start` has an undocumented panic if called on an empty database. We rely on
-        //       the iterator being at the start when created.
-        for (_, raw_val) in cursor.iter() {
-            let mut body_txn = env.begin_ro_txn()?;
-            let block_header: BlockHeader = lmdb_ext::deserialize(raw_val)?;
-            let (maybe_block_body, is_v1) = get_body_for_block_header(
-                &mut body_txn,
-                &block_header,
-                &databases,
-                verifiable_chunked_hash_activation,
-            );
-            if let Some(invalid_era) = hard_reset_to_start_of_era {
+        let mut deleted_block_hashes = HashSet::new();
+        let mut deleted_block_body_hashes_v1 = HashSet::new();
+        let mut deleted_deploy_hashes = HashSet::<DeployHash>::new();
+
+        if let Some((invalid_era, protocol_version)) = hard_reset {
+            info!("purging blocks for hard reset");
+            let mut block_txn = env.begin_rw_txn()?;
+            let mut cursor = block_txn.open_rw_cursor(block_header_db)?;
+            // Note: `iter_start` has an undocumented panic if called on an empty database. We rely
+            //       on the iterator being at the start when created.
+            for (_, raw_val) in cursor.iter() {
+                let block_header: BlockHeader = lmdb_ext::deserialize(raw_val)?;
                 // Remove blocks that are in to-be-upgraded eras, but have obsolete protocol
                 // versions - they were most likely created before the upgrade and should be
                 // reverted.
-                if block_header.era_id() >= invalid_era
-                    && block_header.protocol_version() < protocol_version
+                if block_header.era_id() < invalid_era
+                    || block_header.protocol_version() >= protocol_version
                 {
-                    let _ = deleted_block_hashes
-                        .insert(block_header.hash(verifiable_chunked_hash_activation));
-
-                    if let Some(block_body) = maybe_block_body? {
-                        deleted_deploy_hashes.extend(block_body.deploy_hashes());
-                        deleted_deploy_hashes.extend(block_body.transfer_hashes());
-                    }
+                    continue;
+                }
+                let mut body_txn = env.begin_ro_txn()?;
+                let (maybe_block_body, is_v1) = get_body_for_block_header(
+                    &mut body_txn,
+                    &block_header,
+                    &databases,
+                    verifiable_chunked_hash_activation,
+                );
+                let _ = deleted_block_hashes
+                    .insert(block_header.hash(verifiable_chunked_hash_activation));
 
-                    if is_v1 {
-                        let _ = deleted_block_body_hashes_v1.insert(*block_header.body_hash());
-                    }
+                if let Some(block_body) = maybe_block_body? {
+                    deleted_deploy_hashes.extend(block_body.deploy_hashes());
+                    deleted_deploy_hashes.extend(block_body.transfer_hashes());
+                }
 
-                    cursor.del(WriteFlags::empty())?;
-                    continue;
+                if is_v1 {
+                    let _ = deleted_block_body_hashes_v1.insert(*block_header.body_hash());
                 }
-            }
 
-            insert_to_block_header_indices(
-                &mut block_height_index,
-                &mut switch_block_era_id_index,
-                &block_header,
-                verifiable_chunked_hash_activation,
-            )?;
+                cursor.del(WriteFlags::empty())?;
+            }
+            drop(cursor);
+            block_txn.commit()?;
+        }
 
-            if let Some(block_body) = maybe_block_body? {
-                insert_to_deploy_index(
-                    &mut deploy_hash_index,
-                    block_header.hash(verifiable_chunked_hash_activation),
-                    &block_body,
-                    block_header.height(),
+        // We now need to restore the block-height index. Log messages allow timing here.
+        info!("reindexing block store");
+        let mut block_height_index = BTreeMap::new();
+        let mut switch_block_era_id_index = BTreeMap::new();
+        let mut deploy_hash_index = BTreeMap::new();
+        {
+            // Only a single reader is allowed, so the headers are read before the bodies rather
+            // than iterating over both in separate transactions.
+            let mut txn = env.begin_ro_txn()?;
+            let mut block_headers = Vec::new();
+            {
+                let mut cursor = txn.open_ro_cursor(block_header_db)?;
+                for (_, raw_val) in cursor.iter() {
+                    block_headers.push(lmdb_ext::deserialize::<BlockHeader>(raw_val)?);
+                }
+            }
+            for block_header in block_headers {
+                insert_to_block_header_indices(
+                    &mut block_height_index,
+                    &mut switch_block_era_id_index,
+                    &block_header,
+                    verifiable_chunked_hash_activation,
                 )?;
+
+                let (maybe_block_body, _) = get_body_for_block_header(
+                    &mut txn,
+                    &block_header,
+                    &databases,
+                    verifiable_chunked_hash_activation,
+                );
+                if let Some(block_body) = maybe_block_body? {
+                    insert_to_deploy_index(
+                        &mut deploy_hash_index,
+                        block_header.hash(verifiable_chunked_hash_activation),
+                        &block_body,
+                        block_header.height(),
+                    )?;
+                }
             }
         }
         info!("block store reindexing complete");
-        drop(cursor);
-        block_txn.commit()?;
-
-        let deleted_block_hashes_raw = deleted_block_hashes.iter().map(BlockHash::as_ref).collect();
-
-        initialize_block_body_v1_db(
-            &env,
-            &block_header_db,
-            &block_body_v1_db,
-            &deleted_block_body_hashes_v1
-                .iter()
-                .map(Digest::as_ref)
-                .collect(),
-        )?;
 
-        initialize_block_metadata_db(&env, &block_metadata_db, &deleted_block_hashes_raw)?;
-        initialize_deploy_metadata_db(&env, &deploy_metadata_db, &deleted_deploy_hashes)?;
+        if !read_only {
+            let deleted_block_hashes_raw =
+                deleted_block_hashes.iter().map(BlockHash::as_ref).collect();
+
+            initialize_block_body_v1_db(
+                &env,
+                &block_header_db,
+                &block_body_v1_db,
+                &deleted_block_body_hashes_v1
+                    .iter()
+                    .map(Digest::as_ref)
+                    .collect(),
+            )?;
+
+            initialize_block_metadata_db(&env, &block_metadata_db, &deleted_block_hashes_raw)?;
+            initialize_deploy_metadata_db(&env, &deploy_metadata_db, &deleted_deploy_hashes)?;
+        }
 
         let mut component = Self {
             root,
+            max_size: total_size,
             env,
             block_header_db,
             block_body_v1_db,
@@ -510,7 +631,9 @@ impl Storage {
                 if let Some(&highest_block) = component.block_height_index.keys().last() {
                     component.completed_blocks =
                         DisjointSequences::new(Sequence::new(0, highest_block));
-                    component.persist_completed_blocks()?;
+                    if !read_only {
+                        component.persist_completed_blocks()?;
+                    }
                 } // the `else` case here would mean genesis, so no change.
             }
         }
@@ -582,6 +705,37 @@ impl Storage {
         &self.root
     }
 
+    /// Returns the number of bytes in use by the database.
+    ///
+    /// This is the size up to the last used page rather than the size of the database file, as
+    /// with `WRITE_MAP` LMDB grows the file to the full map size up front.
+    fn used_size(&self) -> Result<u64, lmdb::Error> {
+        let mut info: MaybeUninit<lmdb_sys::MDB_envinfo> = MaybeUninit::zeroed();
+        let mut stat: MaybeUninit<lmdb_sys::MDB_stat> = MaybeUninit::zeroed();
+
+        // The environment pointer is valid for the lifetime of `self.env`, and LMDB fills in both
+        // structs on success.
+        let outcome = unsafe { lmdb_sys::mdb_env_info(self.env.env(), info.as_mut_ptr()) };
+        if outcome != lmdb_sys::MDB_SUCCESS {
+            return Err(lmdb::Error::from_err_code(outcome));
+        }
+        let outcome = unsafe { lmdb_sys::mdb_env_stat(self.env.env(), stat.as_mut_ptr()) };
+        if outcome != lmdb_sys::MDB_SUCCESS {
+            return Err(lmdb::Error::from_err_code(outcome));
+        }
+        let (info, stat) = unsafe { (info.assume_init(), stat.assume_init()) };
+
+        Ok((info.me_last_pgno as u64 + 1).saturating_mul(u64::from(stat.ms_psize)))
+    }
+
+    /// Flushes the database to disk.
+    ///
+    /// Committed transactions are already durable, so this is only a safeguard run as the last
+    /// step of a graceful shutdown.
+    pub(crate) fn sync(&self) -> Result<(), lmdb::Error> {
+        self.env.sync(true)
+    }
+
     fn handle_net_request_incoming<REv>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -1125,6 +1279,9 @@ impl Storage {
             StorageRequest::GetAvailableBlockRange { responder } => {
                 responder.respond(self.get_available_block_range()).ignore()
             }
+            StorageRequest::GetAvailableStateRootHashes { responder } => responder
+                .respond(self.get_available_state_root_hashes()?)
+                .ignore(),
             StorageRequest::StoreFinalizedApprovals {
                 ref deploy_hash,
                 ref finalized_approvals,
@@ -1328,6 +1485,27 @@ impl Storage {
         self.get_block_by_height(&mut self.env.begin_ro_txn()?, height)
     }
 
+    /// Retrieves the headers of all switch blocks, in order of era.
+    pub(crate) fn read_switch_block_headers(&self) -> Result<Vec<BlockHeader>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        self.switch_block_era_id_index
+            .values()
+            .filter_map(|block_hash| {
+                self.get_single_block_header(&mut txn, block_hash)
+                    .transpose()
+            })
+            .collect()
+    }
+
+    /// Retrieves the highest block header from the storage, if one exists.
+    pub fn read_highest_block_header(&self) -> Result<Option<BlockHeader>, FatalStorageError> {
+        let highest_block_hash = match self.block_height_index.iter().last() {
+            Some((_, highest_block_hash)) => highest_block_hash,
+            None => return Ok(None),
+        };
+        self.read_block_header_by_hash(highest_block_hash)
+    }
+
     /// Retrieves single block and all of its deploys.
     /// If any of the deploys can't be found, returns `Ok(None)`.
     fn read_block_and_deploys_by_hash(
@@ -1394,7 +1572,7 @@ impl Storage {
 
     /// Retrieves the block hash and height for a deploy hash by looking it up in the index
     /// and returning it.
-    fn get_block_hash_and_height_by_deploy_hash(
+    pub(crate) fn get_block_hash_and_height_by_deploy_hash(
         &self,
         deploy_hash: DeployHash,
     ) -> Result<Option<BlockHashAndHeight>, FatalStorageError> {
@@ -1759,7 +1937,7 @@ impl Storage {
     }
 
     /// Retrieves finality signatures for a block with a given block hash.
-    fn read_finality_signatures(
+    pub(crate) fn read_finality_signatures(
         &self,
         block_hash: &BlockHash,
     ) -> Result<Option<BlockSignatures>, FatalStorageError> {
@@ -2047,6 +2225,24 @@ impl Storage {
             None => AvailableBlockRange::RANGE_0_0,
         }
     }
+
+    /// Returns the heights and state root hashes of the blocks in the available block range, read
+    /// within a single transaction.
+    fn get_available_state_root_hashes(&self) -> Result<Vec<(u64, Digest)>, FatalStorageError> {
+        let available_block_range = self.get_available_block_range();
+        let mut txn = self.env.begin_ro_txn()?;
+        let mut state_root_hashes = Vec::new();
+        for (height, block_hash) in self
+            .block_height_index
+            .range(available_block_range.low()..=available_block_range.high())
+        {
+            if let Some(block_header) = self.get_single_block_header(&mut txn, block_hash)? {
+                state_root_hashes.push((*height, *block_header.state_root_hash()));
+            }
+        }
+        txn.commit()?;
+        Ok(state_root_hashes)
+    }
 }
 
 /// Decodes an item's ID, typically from an incoming request.
@@ -2198,7 +2394,7 @@ fn move_storage_files_to_network_subdir(
 
 /// On-disk storage configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// The path to the folder where any files created or read by the storage component will exist.
     ///
@@ -2207,18 +2403,22 @@ pub struct Config {
     /// The maximum size of the database to use for the block store.
     ///
     /// The size should be a multiple of the OS page size.
+    #[serde(deserialize_with = "byte_size::deserialize")]
     max_block_store_size: usize,
     /// The maximum size of the database to use for the deploy store.
     ///
     /// The size should be a multiple of the OS page size.
+    #[serde(deserialize_with = "byte_size::deserialize")]
     max_deploy_store_size: usize,
     /// The maximum size of the database to use for the deploy metadata store.
     ///
     /// The size should be a multiple of the OS page size.
+    #[serde(deserialize_with = "byte_size::deserialize")]
     max_deploy_metadata_store_size: usize,
     /// The maximum size of the database to use for the component state store.
     ///
     /// The size should be a multiple of the OS page size.
+    #[serde(deserialize_with = "byte_size::deserialize")]
     max_state_store_size: usize,
     /// Whether or not memory deduplication is enabled.
     enable_mem_deduplication: bool,
@@ -2378,15 +2578,6 @@ impl Storage {
             })
             .transpose()
     }
-
-    /// Retrieves the highest block header from the storage, if one exists.
-    pub fn read_highest_block_header(&self) -> Result<Option<BlockHeader>, FatalStorageError> {
-        let highest_block_hash = match self.block_height_index.iter().last() {
-            Some((_, highest_block_hash)) => highest_block_hash,
-            None => return Ok(None),
-        };
-        self.read_block_header_by_hash(highest_block_hash)
-    }
 }
 
 fn construct_block_body_to_block_header_reverse_lookup(
@@ -28,6 +28,7 @@ use crate::{
     components::{
         consensus::EraReport,
         storage::lmdb_ext::{TransactionExt, WriteTransactionExt},
+        HealthCheck,
     },
     effect::{requests::StorageRequest, Multiple},
     storage::lmdb_ext::{deserialize_internal, serialize_internal},
@@ -35,7 +36,7 @@ use crate::{
     types::{
         Block, BlockHash, BlockHashAndHeight, BlockHeader, BlockPayload, BlockSignatures, Deploy,
         DeployHash, DeployMetadata, DeployMetadataExt, DeployWithFinalizedApprovals,
-        FinalitySignature, FinalizedBlock,
+        FinalitySignature, FinalizedBlock, HealthStatus,
     },
     utils::WithDir,
 };
@@ -1841,6 +1842,45 @@ fn should_restrict_returned_blocks() {
         .expect("should return block failed"));
 }
 
+#[test]
+fn should_get_state_root_hashes_of_available_blocks() {
+    let mut harness = ComponentHarness::default();
+    let verifiable_chunked_hash_activation = EraId::new(u64::MAX);
+
+    let mut storage = storage_fixture(&harness, verifiable_chunked_hash_activation);
+
+    // Create the following disjoint sequences: 1-2 4-5
+    let blocks: Vec<Block> = [1, 2, 4, 5]
+        .iter()
+        .map(|height| {
+            let block = Block::random_with_specifics(
+                &mut harness.rng,
+                EraId::from(1),
+                *height,
+                ProtocolVersion::from_parts(1, 5, 0),
+                false,
+                verifiable_chunked_hash_activation,
+                None,
+            );
+            storage.write_block(&block).unwrap();
+            storage.completed_blocks.insert(*height);
+            block
+        })
+        .collect();
+
+    let state_root_hashes = harness.send_request(&mut storage, |responder| {
+        StorageRequest::GetAvailableStateRootHashes { responder }.into()
+    });
+    assert!(harness.is_idle());
+
+    // Only the blocks on the highest disjoint sequence are available.
+    let expected: Vec<_> = blocks[2..]
+        .iter()
+        .map(|block| (block.height(), *block.header().state_root_hash()))
+        .collect();
+    assert_eq!(state_root_hashes, expected);
+}
+
 #[test]
 fn should_get_block_header_by_height() {
     let mut harness = ComponentHarness::default();
@@ -1913,3 +1953,17 @@ fn unbonding_purse_serialization_roundtrip() {
     // Explicitly assert that the `new_validator` is not `None`
     assert!(deserialized.new_validator().is_some())
 }
+
+#[test]
+fn should_report_health_by_database_usage() {
+    let harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness, EraId::from(0));
+    assert_eq!(storage.health().status, HealthStatus::Healthy);
+
+    // Shrink the limit until the pages in use exceed the thresholds.
+    let used = storage.used_size().unwrap() as usize;
+    storage.max_size = used * 100 / 90;
+    assert_eq!(storage.health().status, HealthStatus::Degraded);
+    storage.max_size = used;
+    assert_eq!(storage.health().status, HealthStatus::Failed);
+}
@@ -133,14 +133,16 @@ use casper_types::{
 use crate::{
     components::{
         block_validator::ValidatingBlock,
+        chain_synchronizer::SyncStatistics,
         chainspec_loader::{CurrentRunInfo, NextUpgrade},
         consensus::{BlockContext, ClContext, EraDump, ValidatorChange},
         contract_runtime::{
             BlockAndExecutionEffects, BlockExecutionError, EraValidatorsRequest, ExecutionPreState,
+            SpeculativeExecutionError,
         },
         deploy_acceptor,
         fetcher::FetchResult,
-        small_network::FromIncoming,
+        small_network::{FromIncoming, Payload},
     },
     contract_runtime::SpeculativeExecutionState,
     effect::announcements::ChainSynchronizerAnnouncement,
@@ -150,7 +152,8 @@ use crate::{
         BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockPayload,
         BlockSignatures, BlockWithMetadata, Chainspec, ChainspecInfo, ChainspecRawBytes, Deploy,
         DeployHash, DeployHeader, DeployMetadataExt, DeployWithFinalizedApprovals,
-        FinalitySignature, FinalizedApprovals, FinalizedBlock, Item, NodeId, NodeState,
+        FinalitySignature, FinalizedApprovals, FinalizedBlock, HealthReport, Item, NodeId,
+        NodeState,
     },
     utils::{SharedFlag, Source},
 };
@@ -160,12 +163,12 @@ use announcements::{
     DeployAcceptorAnnouncement, GossiperAnnouncement, LinearChainAnnouncement, QueueDumpFormat,
     RpcServerAnnouncement,
 };
-use diagnostics_port::DumpConsensusStateRequest;
+use diagnostics_port::{DumpConsensusStateRequest, DumpMemoryRequest, MemoryReport};
 use requests::{
     BeginGossipRequest, BlockPayloadRequest, BlockProposerRequest, BlockValidationRequest,
     ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, FetcherRequest,
-    MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest,
-    NodeStateRequest, StateStoreRequest, StorageRequest,
+    HealthRequest, MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest,
+    NodeStateRequest, StateStoreRequest, StorageRequest, SyncStatisticsRequest,
 };
 
 /// A resource that will never be available, thus trying to acquire it will wait forever.
@@ -528,6 +531,11 @@ impl<REv> EffectBuilder<REv> {
         self.event_queue
     }
 
+    /// Returns the number of events in each of the reactor's event queues.
+    pub(crate) fn event_queues_counts(&self) -> HashMap<QueueKind, usize> {
+        self.event_queue.event_queues_counts()
+    }
+
     /// Performs a request.
     ///
     /// Given a request `Q`, that when completed will yield a result of `T`, produces a future that
@@ -778,6 +786,19 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the current network peers not known to be running in recent sync mode, in random
+    /// order.
+    pub async fn get_fully_connected_archival_peers(self) -> Vec<NodeId>
+    where
+        REv: From<NetworkInfoRequest>,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::FullyConnectedArchivalPeers { responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Announces which deploys have expired.
     pub(crate) async fn announce_expired_deploys(self, hashes: Vec<DeployHash>)
     where
@@ -792,14 +813,23 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Announces an incoming network message.
+    ///
+    /// Consensus messages are scheduled on the consensus queue, all others on the incoming network
+    /// queue.
     pub(crate) async fn announce_incoming<P>(self, sender: NodeId, payload: P)
     where
         REv: FromIncoming<P>,
+        P: Payload,
     {
+        let queue_kind = if payload.is_consensus() {
+            QueueKind::Consensus
+        } else {
+            QueueKind::NetworkIncoming
+        };
         self.event_queue
             .schedule(
                 <REv as FromIncoming<P>>::from_incoming(sender, payload),
-                QueueKind::NetworkIncoming,
+                queue_kind,
             )
             .await
     }
@@ -1254,6 +1284,18 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the heights and state root hashes of the blocks in the available block range.
+    pub(crate) async fn get_available_state_root_hashes_from_storage(self) -> Vec<(u64, Digest)>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetAvailableStateRootHashes { responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Get a trie or chunk by its ID.
     pub(crate) async fn get_trie(
         self,
@@ -1650,6 +1692,22 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces that consensus received messages for a future era, so the node should catch up.
+    pub(crate) async fn announce_fell_behind(self, current_era: EraId, network_era: EraId)
+    where
+        REv: From<ConsensusAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                ConsensusAnnouncement::FellBehind {
+                    current_era,
+                    network_era,
+                },
+                QueueKind::Regular,
+            )
+            .await
+    }
+
     /// Announce the intent to disconnect from a specific peer, which consensus thinks is faulty.
     pub(crate) async fn announce_disconnect_from_peer(self, peer: NodeId)
     where
@@ -1761,6 +1819,26 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Writes the handoff for the next protocol version, recording the last block before the
+    /// upgrade.
+    pub(crate) async fn write_upgrade_handoff(
+        self,
+        last_block_hash: BlockHash,
+        last_block_header: Box<BlockHeader>,
+    ) where
+        REv: From<ChainspecLoaderRequest>,
+    {
+        self.make_request(
+            |responder| ChainspecLoaderRequest::WriteUpgradeHandoff {
+                last_block_hash,
+                last_block_header,
+                responder,
+            },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     pub(crate) async fn get_node_state(self) -> NodeState
     where
         REv: From<NodeStateRequest> + Send,
@@ -1768,6 +1846,23 @@ impl<REv> EffectBuilder<REv> {
         self.make_request(NodeStateRequest, QueueKind::Api).await
     }
 
+    /// Retrieves statistics about the blocks synced so far, if the node is syncing blocks.
+    pub(crate) async fn get_sync_statistics(self) -> Option<SyncStatistics>
+    where
+        REv: From<SyncStatisticsRequest> + Send,
+    {
+        self.make_request(SyncStatisticsRequest, QueueKind::Api)
+            .await
+    }
+
+    /// Retrieves the health of the node's components.
+    pub(crate) async fn get_health_report(self) -> HealthReport
+    where
+        REv: From<HealthRequest> + Send,
+    {
+        self.make_request(HealthRequest, QueueKind::Api).await
+    }
+
     /// Retrieves finalized blocks with timestamps no older than the maximum deploy TTL.
     ///
     /// These blocks contain all deploy and transfer hashes that are known to be finalized but
@@ -2097,6 +2192,18 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Estimate the heap memory used by each of the reactor's components.
+    pub(crate) async fn diagnostics_port_dump_memory(self) -> MemoryReport
+    where
+        REv: From<DumpMemoryRequest>,
+    {
+        self.make_request(
+            |responder| DumpMemoryRequest { responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
     /// Dump the event queue contents to the diagnostics port, using the given serializer.
     pub(crate) async fn diagnostics_port_dump_queue(self, dump_format: QueueDumpFormat)
     where
@@ -2190,7 +2297,7 @@ impl<REv> EffectBuilder<REv> {
         self,
         execution_prestate: SpeculativeExecutionState,
         deploy: Deploy,
-    ) -> Result<Option<ExecutionResult>, engine_state::Error>
+    ) -> Result<Option<ExecutionResult>, SpeculativeExecutionError>
     where
         REv: From<ContractRuntimeRequest>,
     {
@@ -197,6 +197,14 @@ pub(crate) enum ConsensusAnnouncement {
         /// The timestamp when the evidence of the equivocation was detected.
         timestamp: Timestamp,
     },
+    /// Consensus messages for an era we haven't reached yet were received, i.e. the node has
+    /// fallen behind the network and should catch up in the background.
+    FellBehind {
+        /// The current era of this node.
+        current_era: EraId,
+        /// The era the received messages belong to.
+        network_era: EraId,
+    },
 }
 
 impl Display for ConsensusAnnouncement {
@@ -217,6 +225,14 @@ impl Display for ConsensusAnnouncement {
                 "Validator fault with public key: {} has been identified at time: {} in era: {}",
                 public_key, timestamp, era_id,
             ),
+            ConsensusAnnouncement::FellBehind {
+                current_era,
+                network_era,
+            } => write!(
+                formatter,
+                "fell behind: in {} while the network is in {}",
+                current_era, network_era,
+            ),
         }
     }
 }
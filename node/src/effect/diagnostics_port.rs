@@ -1,6 +1,7 @@
 use std::{
     borrow::Cow,
-    fmt::{Debug, Display},
+    cmp::Reverse,
+    fmt::{self, Debug, Display, Formatter},
 };
 
 use casper_types::EraId;
@@ -58,3 +59,116 @@ impl Debug for DumpConsensusStateRequest {
             .finish_non_exhaustive()
     }
 }
+
+/// A request for an estimate of the heap memory used by each of the reactor's components.
+#[derive(DataSize, Serialize)]
+pub(crate) struct DumpMemoryRequest {
+    /// Responder to send the report into.
+    pub(crate) responder: Responder<MemoryReport>,
+}
+
+impl Display for DumpMemoryRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("dump memory usage")
+    }
+}
+
+impl Debug for DumpMemoryRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DumpMemoryRequest").finish_non_exhaustive()
+    }
+}
+
+/// Estimated heap memory usage of a reactor, broken down by component.
+///
+/// Estimates are based on the `DataSize` implementations and exclude anything marked as
+/// `#[data_size(skip)]`, so they are lower bounds.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct MemoryReport {
+    /// Estimated total heap usage of all components, in bytes.
+    total: usize,
+    /// Estimates per component.
+    components: Vec<ComponentMemory>,
+}
+
+impl MemoryReport {
+    /// Adds the estimated heap usage of a component to the report.
+    ///
+    /// The returned entry can be used to break the estimate down further.
+    pub(crate) fn add_component<T: DataSize>(
+        &mut self,
+        name: &'static str,
+        component: &T,
+    ) -> &mut ComponentMemory {
+        let heap_size = component.estimate_heap_size();
+        self.total += heap_size;
+        self.components.push(ComponentMemory {
+            name,
+            heap_size,
+            collections: Vec::new(),
+        });
+        self.components.last_mut().expect("just pushed an entry")
+    }
+}
+
+impl Display for MemoryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut components: Vec<_> = self.components.iter().collect();
+        components.sort_by_key(|component| Reverse(component.heap_size));
+        for component in components {
+            writeln!(f, "{:<56}{:>16}", component.name, component.heap_size)?;
+            for (name, heap_size) in &component.collections {
+                writeln!(f, "  {:<54}{:>16}", name, heap_size)?;
+            }
+        }
+        write!(f, "{:<56}{:>16}", "total", self.total)
+    }
+}
+
+/// Estimated heap memory usage of a single component.
+#[derive(Debug, Serialize)]
+pub(crate) struct ComponentMemory {
+    /// The component's name.
+    name: &'static str,
+    /// Estimated heap usage of the whole component, in bytes.
+    heap_size: usize,
+    /// Estimated heap usage of the component's major collections, in bytes.
+    collections: Vec<(String, usize)>,
+}
+
+impl ComponentMemory {
+    /// Adds the estimated heap usage of one of the component's collections.
+    pub(crate) fn add_collection<T: DataSize>(
+        &mut self,
+        name: impl Into<String>,
+        collection: &T,
+    ) -> &mut Self {
+        self.collections
+            .push((name.into(), collection.estimate_heap_size()));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_report_should_sum_components() {
+        let mut report = MemoryReport::default();
+        report
+            .add_component("first", &vec![0u64; 16])
+            .add_collection("half", &vec![0u64; 8]);
+        report.add_component("second", &vec![0u8; 32]);
+
+        assert_eq!(report.total, 16 * 8 + 32);
+        assert_eq!(
+            report.components[0].collections,
+            vec![("half".to_string(), 64)]
+        );
+
+        let display = report.to_string();
+        assert!(display.starts_with("first"));
+        assert!(display.ends_with(&format!("{:>16}", 160)));
+    }
+}
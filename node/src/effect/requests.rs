@@ -37,10 +37,12 @@ use casper_types::{
 use crate::{
     components::{
         block_validator::ValidatingBlock,
+        chain_synchronizer::SyncStatistics,
         chainspec_loader::CurrentRunInfo,
         consensus::{BlockContext, ClContext, ValidatorChange},
         contract_runtime::{
             BlockAndExecutionEffects, BlockExecutionError, EraValidatorsRequest, ExecutionPreState,
+            SpeculativeExecutionError,
         },
         deploy_acceptor::Error,
         fetcher::FetchResult,
@@ -53,14 +55,14 @@ use crate::{
         BlockHeaderWithMetadata, BlockHeadersBatch, BlockHeadersBatchId, BlockPayload,
         BlockSignatures, BlockWithMetadata, Chainspec, ChainspecInfo, ChainspecRawBytes, Deploy,
         DeployHash, DeployMetadataExt, DeployWithFinalizedApprovals, FinalizedApprovals,
-        FinalizedBlock, Item, NodeId, NodeState, StatusFeed,
+        FinalizedBlock, HealthReport, Item, NodeId, NodeState, StatusFeed,
     },
     utils::{DisplayIter, Source},
 };
 
 // Redirection for reactor macro.
 #[allow(unused_imports)]
-pub(crate) use super::diagnostics_port::DumpConsensusStateRequest;
+pub(crate) use super::diagnostics_port::{DumpConsensusStateRequest, DumpMemoryRequest};
 
 const _STORAGE_REQUEST_SIZE: usize = mem::size_of::<StorageRequest>();
 const_assert!(_STORAGE_REQUEST_SIZE < 89);
@@ -188,7 +190,7 @@ where
 }
 
 /// A networking info request.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub(crate) enum NetworkInfoRequest {
     /// Get incoming and outgoing peers.
     Peers {
@@ -206,6 +208,12 @@ pub(crate) enum NetworkInfoRequest {
         /// Responder to be called with all connected non-syncing peers in random order.
         responder: Responder<Vec<NodeId>>,
     },
+    /// Get only peers not known to be running in recent sync mode, in random order.
+    FullyConnectedArchivalPeers {
+        /// Responder to be called with all connected peers not known to be running in recent sync
+        /// mode, in random order.
+        responder: Responder<Vec<NodeId>>,
+    },
 }
 
 impl Display for NetworkInfoRequest {
@@ -220,6 +228,9 @@ impl Display for NetworkInfoRequest {
             NetworkInfoRequest::FullyConnectedNonSyncingPeers { responder: _ } => {
                 write!(formatter, "get fully connected non-syncing peers")
             }
+            NetworkInfoRequest::FullyConnectedArchivalPeers { responder: _ } => {
+                write!(formatter, "get fully connected archival peers")
+            }
         }
     }
 }
@@ -485,6 +496,12 @@ pub(crate) enum StorageRequest {
         /// Responder to call with the result.
         responder: Responder<AvailableBlockRange>,
     },
+    /// Retrieve the heights and state root hashes of the blocks in the available block range, in
+    /// ascending order of height.
+    GetAvailableStateRootHashes {
+        /// Responder to call with the result.
+        responder: Responder<Vec<(u64, Digest)>>,
+    },
     /// Store a set of finalized approvals for a specific deploy.
     StoreFinalizedApprovals {
         /// The deploy hash to store the finalized approvals for.
@@ -609,6 +626,9 @@ impl Display for StorageRequest {
             StorageRequest::GetAvailableBlockRange { .. } => {
                 write!(formatter, "get available block range",)
             }
+            StorageRequest::GetAvailableStateRootHashes { .. } => {
+                write!(formatter, "get available state root hashes")
+            }
             StorageRequest::StoreFinalizedApprovals { deploy_hash, .. } => {
                 write!(formatter, "finalized approvals for deploy {}", deploy_hash)
             }
@@ -837,7 +857,7 @@ pub(crate) enum RpcRequest {
         /// Deploy to execute.
         deploy: Box<Deploy>,
         /// Responder.
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        responder: Responder<Result<Option<ExecutionResult>, SpeculativeExecutionError>>,
     },
 }
 
@@ -1067,7 +1087,7 @@ pub(crate) enum ContractRuntimeRequest {
         /// Deploy to execute.
         deploy: Box<Deploy>,
         /// Results
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        responder: Responder<Result<Option<ExecutionResult>, SpeculativeExecutionError>>,
     },
 }
 
@@ -1196,7 +1216,7 @@ impl Display for BlockValidationRequest {
 
 type BlockHeight = u64;
 
-#[derive(DataSize, Debug)]
+#[derive(DataSize, Debug, Serialize)]
 #[must_use]
 /// Consensus component requests.
 pub(crate) enum ConsensusRequest {
@@ -1206,6 +1226,15 @@ pub(crate) enum ConsensusRequest {
     ValidatorChanges(Responder<BTreeMap<PublicKey, Vec<(EraId, ValidatorChange)>>>),
 }
 
+impl Display for ConsensusRequest {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsensusRequest::Status(_) => write!(formatter, "get consensus status"),
+            ConsensusRequest::ValidatorChanges(_) => write!(formatter, "get validator changes"),
+        }
+    }
+}
+
 /// ChainspecLoader component requests.
 #[derive(Debug, Serialize)]
 #[allow(clippy::enum_variant_names)]
@@ -1217,6 +1246,16 @@ pub(crate) enum ChainspecLoaderRequest {
     /// Request for the chainspec file bytes with the genesis_accounts and global_state bytes, if
     /// they are present.
     GetChainspecRawBytes(Responder<Arc<ChainspecRawBytes>>),
+    /// Request to record the state in which we are stopping for the next upgrade, for use by the
+    /// next protocol version.
+    WriteUpgradeHandoff {
+        /// The hash of the last block before the upgrade.
+        last_block_hash: BlockHash,
+        /// The header of the last block before the upgrade.
+        last_block_header: Box<BlockHeader>,
+        /// Responder to call once the handoff has been written.
+        responder: Responder<()>,
+    },
 }
 
 impl Display for ChainspecLoaderRequest {
@@ -1225,6 +1264,9 @@ impl Display for ChainspecLoaderRequest {
             ChainspecLoaderRequest::GetChainspecInfo(_) => write!(f, "get chainspec info"),
             ChainspecLoaderRequest::GetCurrentRunInfo(_) => write!(f, "get current run info"),
             ChainspecLoaderRequest::GetChainspecRawBytes(_) => write!(f, "get chainspec raw bytes"),
+            ChainspecLoaderRequest::WriteUpgradeHandoff {
+                last_block_hash, ..
+            } => write!(f, "write upgrade handoff after block {}", last_block_hash),
         }
     }
 }
@@ -1238,3 +1280,24 @@ impl Display for NodeStateRequest {
         write!(f, "node state request")
     }
 }
+
+/// A request for statistics about the blocks synced so far, handled by the ChainSynchronizer
+/// component.
+#[derive(Debug, Serialize)]
+pub(crate) struct SyncStatisticsRequest(pub(crate) Responder<Option<SyncStatistics>>);
+
+impl Display for SyncStatisticsRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "sync statistics request")
+    }
+}
+
+/// A request for the health of the reactor's components, handled by the reactor itself.
+#[derive(Debug, Serialize)]
+pub(crate) struct HealthRequest(pub(crate) Responder<HealthReport>);
+
+impl Display for HealthRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "health request")
+    }
+}
@@ -26,6 +26,8 @@ mod config_migration;
 mod data_migration;
 pub(crate) mod effect;
 pub(crate) mod logging;
+#[cfg(feature = "pkcs11")]
+pub(crate) mod pkcs11;
 pub(crate) mod protocol;
 pub(crate) mod reactor;
 #[cfg(test)]
@@ -43,13 +45,19 @@ pub use components::{
 };
 pub use utils::WithDir;
 
-use std::sync::{atomic::AtomicUsize, Arc};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize},
+    Arc,
+};
 
 use ansi_term::Color::Red;
 use once_cell::sync::Lazy;
 #[cfg(not(test))]
 use rand::SeedableRng;
-use signal_hook::{consts::TERM_SIGNALS, flag};
+use signal_hook::{
+    consts::{signal::SIGHUP, TERM_SIGNALS},
+    flag,
+};
 
 pub(crate) use components::{
     block_proposer::Config as BlockProposerConfig,
@@ -96,6 +104,11 @@ pub(crate) static VERSION_STRING: Lazy<String> = Lazy::new(|| version_string(fal
 pub(crate) static TERMINATION_REQUESTED: Lazy<Arc<AtomicUsize>> =
     Lazy::new(|| Arc::new(AtomicUsize::new(0)));
 
+/// Global value that indicates the currently running reactor should reload its configuration if
+/// it is set.
+pub(crate) static CONFIG_RELOAD_REQUESTED: Lazy<Arc<AtomicBool>> =
+    Lazy::new(|| Arc::new(AtomicBool::new(false)));
+
 /// Setup UNIX signal hooks for current application.
 pub(crate) fn setup_signal_hooks() {
     for signal in TERM_SIGNALS {
@@ -106,6 +119,8 @@ pub(crate) fn setup_signal_hooks() {
         )
         .unwrap_or_else(|error| panic!("failed to register signal {}: {}", signal, error));
     }
+    flag::register(SIGHUP, Arc::clone(&*CONFIG_RELOAD_REQUESTED))
+        .unwrap_or_else(|error| panic!("failed to register signal {}: {}", SIGHUP, error));
 }
 
 /// Constructs a new `NodeRng`.
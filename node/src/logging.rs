@@ -1,15 +1,24 @@
 //! Logging via the tracing crate.
 
-use std::{env, fmt, io};
+mod rotating_file;
+
+use std::{
+    env, fmt,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use ansi_term::{Color, Style};
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
+use casper_types::TimeDiff;
 use datasize::DataSize;
+use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use tracing::{
     field::{Field, Visit},
-    Event, Level, Subscriber,
+    info, warn, Event, Level, Subscriber,
 };
 use tracing_subscriber::{
     fmt::{
@@ -18,10 +27,15 @@ use tracing_subscriber::{
         FmtContext, FormatEvent, FormatFields, FormattedFields,
     },
     registry::LookupSpan,
-    EnvFilter,
+    reload, EnvFilter,
 };
 
+use crate::utils::byte_size;
+use rotating_file::{RotatingFileWriter, RotationPolicy};
+
 const LOG_CONFIGURATION_ENVVAR: &str = "RUST_LOG";
+const DEFAULT_LOG_FILTER: &str = "warn,casper_node=info";
+const DEFAULT_MAX_FILES: usize = 10;
 
 const LOG_FIELD_MESSAGE: &str = "message";
 const LOG_FIELD_TARGET: &str = "log.target";
@@ -30,8 +44,8 @@ const LOG_FIELD_FILE: &str = "log.file";
 const LOG_FIELD_LINE: &str = "log.line";
 
 /// Logging configuration.
-#[derive(DataSize, Debug, Default, Deserialize, Serialize)]
-#[serde(deny_unknown_fields)]
+#[derive(DataSize, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct LoggingConfig {
     /// Output format for log.
     format: LoggingFormat,
@@ -47,6 +61,42 @@ pub struct LoggingConfig {
     /// If set, human-readable formats will abbreviate module names, `foo::bar::baz::bizz` will
     /// turn into `f:b:b:bizz`.
     abbreviate_modules: bool,
+
+    /// Log filter directives, e.g. "warn,casper_node=info".
+    ///
+    /// Ignored if the `RUST_LOG` environment variable is set.  Can be changed without restarting
+    /// the node by reloading the configuration.  Defaults to "warn,casper_node=info".
+    filter: Option<String>,
+
+    /// Path of a file to write log messages to instead of stdout.
+    ///
+    /// Relative paths are resolved against the directory containing the config file.
+    file: Option<PathBuf>,
+
+    /// Rotate the log file once it reaches this many bytes.
+    #[serde(default, deserialize_with = "byte_size::deserialize_option")]
+    max_file_size: Option<u64>,
+
+    /// Rotate the log file once it has been written to for this long.
+    rotation_interval: Option<TimeDiff>,
+
+    /// The number of rotated log files to keep besides the active one.  Defaults to 10.
+    max_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        LoggingConfig {
+            format: LoggingFormat::default(),
+            color: false,
+            abbreviate_modules: false,
+            filter: None,
+            file: None,
+            max_file_size: None,
+            rotation_interval: None,
+            max_files: DEFAULT_MAX_FILES,
+        }
+    }
 }
 
 impl LoggingConfig {
@@ -57,6 +107,74 @@ impl LoggingConfig {
             format,
             color,
             abbreviate_modules,
+            ..Default::default()
+        }
+    }
+
+    /// Resolves a relative log file path against `root`.
+    pub(crate) fn resolve_file_path(&mut self, root: &Path) {
+        if let Some(file) = self.file.as_mut() {
+            if file.is_relative() {
+                *file = root.join(&*file);
+            }
+        }
+    }
+
+    /// Returns the log file path, if logging to a file is configured.
+    pub(crate) fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// Opens the writer log messages are written to.
+    fn make_writer(&self) -> anyhow::Result<LogWriter> {
+        let path = match self.file {
+            Some(ref path) => path,
+            None => return Ok(LogWriter::Stdout),
+        };
+        let policy = RotationPolicy {
+            max_size: self.max_file_size,
+            interval: self.rotation_interval.map(Duration::from),
+            max_files: self.max_files,
+        };
+        let writer = RotatingFileWriter::open(path.clone(), policy)
+            .with_context(|| format!("failed to open log file {}", path.display()))?;
+        Ok(LogWriter::File(writer))
+    }
+
+    /// Returns the filter to apply to log events.
+    fn env_filter(&self) -> EnvFilter {
+        match env::var(LOG_CONFIGURATION_ENVVAR) {
+            Ok(directives) => EnvFilter::new(directives),
+            Err(_) => EnvFilter::new(self.filter.as_deref().unwrap_or(DEFAULT_LOG_FILTER)),
+        }
+    }
+}
+
+/// Replaces the filter of the installed logger.
+type FilterReloader = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+/// The filter reloader of the installed logger, set once logging has been initialized.
+static FILTER_RELOADER: OnceCell<FilterReloader> = OnceCell::new();
+
+/// The destination of log messages.
+#[derive(Clone)]
+enum LogWriter {
+    Stdout,
+    File(RotatingFileWriter),
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            LogWriter::Stdout => io::stdout().write(buf),
+            LogWriter::File(writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            LogWriter::Stdout => io::stdout().flush(),
+            LogWriter::File(writer) => writer.flush(),
         }
     }
 }
@@ -273,26 +391,74 @@ pub fn init_with_config(config: &LoggingConfig) -> anyhow::Result<()> {
         _ => write!(writer, "; {}={:?}", field, value),
     });
 
-    let filter = EnvFilter::new(
-        env::var(LOG_CONFIGURATION_ENVVAR)
-            .as_deref()
-            .unwrap_or("warn,casper_node=info"),
-    );
-
-    match config.format {
-        // Setup a new tracing-subscriber writing to `stdout` for logging.
-        LoggingFormat::Text => tracing_subscriber::fmt()
-            .with_writer(io::stdout)
-            .with_env_filter(filter)
-            .fmt_fields(formatter)
-            .event_format(FmtEvent::new(config.color, config.abbreviate_modules))
-            .try_init(),
-        // JSON logging writes to `stdout` as well but uses the JSON format.
-        LoggingFormat::Json => tracing_subscriber::fmt()
-            .with_writer(io::stdout)
-            .with_env_filter(filter)
-            .json()
-            .try_init(),
+    let filter = config.env_filter();
+    let writer = config.make_writer()?;
+    let make_writer = move || writer.clone();
+
+    let (result, handle): (_, FilterReloader) = match config.format {
+        // Setup a new tracing-subscriber writing to `stdout` or the log file.
+        LoggingFormat::Text => {
+            let builder = tracing_subscriber::fmt()
+                .with_writer(make_writer)
+                .fmt_fields(formatter)
+                .event_format(FmtEvent::new(config.color, config.abbreviate_modules))
+                .with_env_filter(filter)
+                .with_filter_reloading();
+            let handle = builder.reload_handle();
+            (
+                builder.try_init(),
+                Box::new(move |filter| handle.reload(filter)),
+            )
+        }
+        // JSON logging writes to the same destination but uses the JSON format.
+        LoggingFormat::Json => {
+            let builder = tracing_subscriber::fmt()
+                .with_writer(make_writer)
+                .json()
+                .with_env_filter(filter)
+                .with_filter_reloading();
+            let handle = builder.reload_handle();
+            (
+                builder.try_init(),
+                Box::new(move |filter| handle.reload(filter)),
+            )
+        }
+    };
+    result.map_err(|error| anyhow!(error))?;
+
+    // Only the first successfully installed logger can be reloaded.
+    let _ = FILTER_RELOADER.set(handle);
+    Ok(())
+}
+
+/// Replaces the filter of the installed logger with the one specified in `config`.
+///
+/// All other logging options only take effect when the node is restarted.
+pub fn reload_filter(config: &LoggingConfig) -> anyhow::Result<()> {
+    let reloader = FILTER_RELOADER
+        .get()
+        .ok_or_else(|| anyhow!("logging has not been initialized"))?;
+    if env::var(LOG_CONFIGURATION_ENVVAR).is_ok() {
+        warn!(
+            "{} is set, ignoring log filter from config",
+            LOG_CONFIGURATION_ENVVAR
+        );
     }
-    .map_err(|error| anyhow!(error))
+    reloader(config.env_filter()).map_err(|error| anyhow!(error))?;
+    info!(filter = ?config.filter, "reloaded log filter");
+    Ok(())
+}
+
+/// Replaces the filter of the installed logger with the given directives.
+///
+/// Unlike [`reload_filter`], this takes precedence over `RUST_LOG`.  The filter from the config is
+/// restored on the next config reload.
+pub fn set_filter(directives: &str) -> anyhow::Result<()> {
+    let reloader = FILTER_RELOADER
+        .get()
+        .ok_or_else(|| anyhow!("logging has not been initialized"))?;
+    let filter = EnvFilter::try_new(directives).map_err(|error| anyhow!(error))?;
+    reloader(filter).map_err(|error| anyhow!(error))?;
+    info!(%directives, "replaced log filter");
+    Ok(())
 }
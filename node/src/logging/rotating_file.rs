@@ -0,0 +1,245 @@
+//! A log file which rotates itself by size and age.
+//!
+//! The active file is always written to the configured path.  On rotation it is renamed to
+//! `<path>.1`, any existing `<path>.1` to `<path>.2` and so on, with the oldest file beyond the
+//! retention limit being deleted.  Rotation happens between two log messages, so a single message
+//! is never split across files.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The limits controlling when a log file is rotated and how many old files are kept.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct RotationPolicy {
+    /// Rotate once the file has grown to this many bytes.
+    pub(super) max_size: Option<u64>,
+    /// Rotate once the file has been written to for this long.
+    pub(super) interval: Option<Duration>,
+    /// The number of rotated files to keep.
+    pub(super) max_files: usize,
+}
+
+/// A cheaply clonable handle to a shared, rotating log file.
+#[derive(Clone)]
+pub(super) struct RotatingFileWriter {
+    inner: Arc<Mutex<RotatingFile>>,
+}
+
+impl RotatingFileWriter {
+    /// Opens the log file at `path` for appending, creating it if necessary.
+    pub(super) fn open(path: PathBuf, policy: RotationPolicy) -> io::Result<Self> {
+        let file = open_for_append(&path)?;
+        let size = file.metadata()?.len();
+        let rotating_file = RotatingFile {
+            path,
+            policy,
+            file,
+            size,
+            opened_at: Instant::now(),
+        };
+        Ok(RotatingFileWriter {
+            inner: Arc::new(Mutex::new(rotating_file)),
+        })
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // A poisoned lock only means another thread panicked while logging; the file itself is
+        // still usable.
+        let mut rotating_file = self
+            .inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        rotating_file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .file
+            .flush()
+    }
+}
+
+struct RotatingFile {
+    /// The path of the active log file.
+    path: PathBuf,
+    policy: RotationPolicy,
+    /// The active log file.
+    file: File,
+    /// The current size of the active log file in bytes.
+    size: u64,
+    /// When the active log file was opened.
+    opened_at: Instant,
+}
+
+impl RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.is_due(buf.len()) {
+            if let Err(error) = self.rotate() {
+                // Logging is unavailable for reporting this, so fall back to stderr.  The active
+                // file keeps being written to and rotation is retried once it is due again.
+                eprintln!(
+                    "failed to rotate log file {}: {}",
+                    self.path.display(),
+                    error
+                );
+                self.size = 0;
+                self.opened_at = Instant::now();
+            }
+        }
+        self.file.write_all(buf)?;
+        self.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    /// Returns `true` if the active file should be rotated before writing `len` more bytes to it.
+    fn is_due(&self, len: usize) -> bool {
+        // An empty file is never rotated, even if a single message exceeds the size limit.
+        if self.size == 0 {
+            return false;
+        }
+        let too_large = self
+            .policy
+            .max_size
+            .map_or(false, |max_size| self.size + len as u64 > max_size);
+        let too_old = self
+            .policy
+            .interval
+            .map_or(false, |interval| self.opened_at.elapsed() >= interval);
+        too_large || too_old
+    }
+
+    /// Shifts the rotated files up by one, drops the oldest and starts a new active file.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        let max_files = self.policy.max_files;
+        if max_files == 0 {
+            remove_if_exists(&self.path)?;
+        } else {
+            remove_if_exists(&rotated_path(&self.path, max_files))?;
+            for index in (1..max_files).rev() {
+                rename_if_exists(
+                    &rotated_path(&self.path, index),
+                    &rotated_path(&self.path, index + 1),
+                )?;
+            }
+            fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        }
+
+        self.file = open_for_append(&self.path)?;
+        self.size = 0;
+        self.opened_at = Instant::now();
+        Ok(())
+    }
+}
+
+/// Returns the path of the `index`th most recently rotated file, e.g. `node.log.1`.
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", index));
+    PathBuf::from(rotated)
+}
+
+fn open_for_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn read(path: &Path) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn should_rotate_by_size_and_keep_max_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("node.log");
+        let policy = RotationPolicy {
+            max_size: Some(10),
+            interval: None,
+            max_files: 2,
+        };
+        let mut writer = RotatingFileWriter::open(path.clone(), policy).unwrap();
+
+        for line in &["first\n", "second\n", "third\n", "fourth\n"] {
+            writer.write_all(line.as_bytes()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        assert_eq!(read(&path), "fourth\n");
+        assert_eq!(read(&rotated_path(&path, 1)), "third\n");
+        assert_eq!(read(&rotated_path(&path, 2)), "second\n");
+        // The oldest file exceeded the retention limit.
+        assert!(!rotated_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn should_rotate_by_age() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("node.log");
+        let policy = RotationPolicy {
+            max_size: None,
+            interval: Some(Duration::from_millis(50)),
+            max_files: 1,
+        };
+        let mut writer = RotatingFileWriter::open(path.clone(), policy).unwrap();
+
+        writer.write_all(b"old\n").unwrap();
+        writer.write_all(b"still old\n").unwrap();
+        thread::sleep(Duration::from_millis(60));
+        writer.write_all(b"new\n").unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(read(&path), "new\n");
+        assert_eq!(read(&rotated_path(&path, 1)), "old\nstill old\n");
+    }
+
+    #[test]
+    fn should_append_to_existing_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("node.log");
+        fs::write(&path, "previous run\n").unwrap();
+        let policy = RotationPolicy {
+            max_size: Some(20),
+            interval: None,
+            max_files: 1,
+        };
+        let mut writer = RotatingFileWriter::open(path.clone(), policy).unwrap();
+
+        // The existing content counts towards the size limit.
+        writer.write_all(b"this run\n").unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(read(&path), "this run\n");
+        assert_eq!(read(&rotated_path(&path, 1)), "previous run\n");
+    }
+}
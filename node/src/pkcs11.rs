@@ -0,0 +1,373 @@
+//! Signing keys held in a PKCS#11 token, such as a hardware security module.
+//!
+//! The secret key never leaves the token: only the public key is read from it, and all signing is
+//! delegated to the device through the PKCS#11 module provided by its vendor.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+};
+use datasize::DataSize;
+use once_cell::sync::Lazy;
+use openssl::{bn::BigNum, error::ErrorStack, sha};
+use thiserror::Error;
+use tracing::info;
+
+use casper_types::{crypto, AsymmetricType, PublicKey, Signature};
+
+/// The DER-encoded OID of the secp256k1 curve, as found in the `CKA_EC_PARAMS` of its keys.
+const SECP256K1_EC_PARAMS: [u8; 7] = [0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+/// The order of the secp256k1 group, big-endian.
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// The tag of a DER-encoded octet string.
+const DER_OCTET_STRING_TAG: u8 = 0x04;
+
+/// The keys opened so far, by module path, token label and key label.
+///
+/// A PKCS#11 module may only be initialized once per process, and finalizing it closes all of its
+/// sessions, so each key is opened once and then shared for the lifetime of the process, e.g.
+/// between the networking components of the joiner and participating reactors.
+static OPEN_KEYS: Lazy<Mutex<HashMap<(PathBuf, String, String), Arc<Pkcs11Key>>>> =
+    Lazy::new(Default::default);
+
+/// Error opening or signing with a key held in a PKCS#11 token.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// A call into the PKCS#11 module failed.
+    #[error("PKCS#11 call failed: {0}")]
+    Pkcs11(#[from] cryptoki::error::Error),
+    /// No token with the configured label is present.
+    #[error("no PKCS#11 token labelled {0:?} found")]
+    TokenNotFound(String),
+    /// The token holds no key with the configured label.
+    #[error("no {class} labelled {label:?} found in PKCS#11 token")]
+    KeyNotFound {
+        /// The class of key looked for.
+        class: &'static str,
+        /// The label looked for.
+        label: String,
+    },
+    /// The token holds more than one key with the configured label.
+    #[error("more than one {class} labelled {label:?} found in PKCS#11 token")]
+    AmbiguousKey {
+        /// The class of key looked for.
+        class: &'static str,
+        /// The label looked for.
+        label: String,
+    },
+    /// The key is neither an Ed25519 nor a secp256k1 key.
+    #[error("PKCS#11 key labelled {0:?} is neither an Ed25519 nor a secp256k1 key")]
+    UnsupportedKeyType(String),
+    /// The public key read from the token could not be parsed.
+    #[error("invalid public key in PKCS#11 token: {0}")]
+    InvalidPublicKey(crypto::Error),
+    /// The token produced a signature which could not be parsed or does not verify.
+    #[error("invalid signature from PKCS#11 token: {0}")]
+    InvalidSignature(crypto::Error),
+    /// Normalizing a secp256k1 signature failed.
+    #[error("could not normalize secp256k1 signature: {0}")]
+    Normalization(#[from] ErrorStack),
+}
+
+/// The signature algorithm of a key.
+#[derive(Clone, Copy, Debug)]
+enum Algorithm {
+    Ed25519,
+    Secp256k1,
+}
+
+/// A key pair held in a PKCS#11 token.
+pub(crate) struct Pkcs11Key {
+    /// The logged-in session used for signing.  PKCS#11 sessions must not be used concurrently.
+    session: Mutex<Session>,
+    /// The handle of the private key within the session.
+    private_key: ObjectHandle,
+    /// The public key, as read from the token.
+    public_key: PublicKey,
+    /// The signature algorithm of the key.
+    algorithm: Algorithm,
+    /// The loaded PKCS#11 module.  Declared after `session`, so that it is dropped last.
+    _context: Pkcs11,
+}
+
+impl Pkcs11Key {
+    /// Returns the key labelled `key_label` in the token labelled `token_label`, opening it via the
+    /// PKCS#11 module at `module_path` unless it has been opened already.
+    pub(crate) fn open_shared(
+        module_path: &Path,
+        token_label: &str,
+        key_label: &str,
+        pin: &str,
+    ) -> Result<Arc<Self>, Error> {
+        let mut open_keys = OPEN_KEYS.lock().expect("PKCS#11 keys lock poisoned");
+        let id = (
+            module_path.to_path_buf(),
+            token_label.to_string(),
+            key_label.to_string(),
+        );
+        if let Some(key) = open_keys.get(&id) {
+            return Ok(Arc::clone(key));
+        }
+        let key = Arc::new(Self::open(module_path, token_label, key_label, pin)?);
+        open_keys.insert(id, Arc::clone(&key));
+        Ok(key)
+    }
+
+    /// Loads the PKCS#11 module at `module_path`, logs into the token labelled `token_label` and
+    /// looks up the private and public keys labelled `key_label`.
+    fn open(
+        module_path: &Path,
+        token_label: &str,
+        key_label: &str,
+        pin: &str,
+    ) -> Result<Self, Error> {
+        let context = Pkcs11::new(module_path)?;
+        context.initialize(CInitializeArgs::OsThreads)?;
+
+        let slot = context
+            .get_slots_with_token()?
+            .into_iter()
+            .find(|slot| {
+                context
+                    .get_token_info(*slot)
+                    .map(|token_info| token_info.label().trim() == token_label)
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| Error::TokenNotFound(token_label.to_string()))?;
+
+        let session = context.open_ro_session(slot)?;
+        session.login(UserType::User, Some(pin))?;
+
+        let private_key = find_key(&session, ObjectClass::PRIVATE_KEY, key_label)?;
+        let public_key_handle = find_key(&session, ObjectClass::PUBLIC_KEY, key_label)?;
+
+        let mut key_type = None;
+        let mut ec_params = None;
+        let mut ec_point = None;
+        for attribute in session.get_attributes(
+            public_key_handle,
+            &[
+                AttributeType::KeyType,
+                AttributeType::EcParams,
+                AttributeType::EcPoint,
+            ],
+        )? {
+            match attribute {
+                Attribute::KeyType(value) => key_type = Some(value),
+                Attribute::EcParams(value) => ec_params = Some(value),
+                Attribute::EcPoint(value) => ec_point = Some(value),
+                _ => {}
+            }
+        }
+
+        let unsupported = || Error::UnsupportedKeyType(key_label.to_string());
+        let ec_point = ec_point.ok_or_else(unsupported)?;
+        let (algorithm, public_key) = match key_type {
+            Some(KeyType::EC_EDWARDS) => (
+                Algorithm::Ed25519,
+                PublicKey::ed25519_from_bytes(unwrap_octet_string(
+                    &ec_point,
+                    &[PublicKey::ED25519_LENGTH],
+                )),
+            ),
+            Some(KeyType::EC) if ec_params.as_deref() == Some(&SECP256K1_EC_PARAMS[..]) => (
+                Algorithm::Secp256k1,
+                PublicKey::secp256k1_from_bytes(unwrap_octet_string(
+                    &ec_point,
+                    &[PublicKey::SECP256K1_LENGTH, 65],
+                )),
+            ),
+            _ => return Err(unsupported()),
+        };
+        let public_key = public_key.map_err(Error::InvalidPublicKey)?;
+
+        info!(%public_key, %token_label, %key_label, "opened PKCS#11 signing key");
+        Ok(Pkcs11Key {
+            session: Mutex::new(session),
+            private_key,
+            public_key,
+            algorithm,
+            _context: context,
+        })
+    }
+
+    /// Returns the public key of the key pair.
+    pub(crate) fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Signs `message` on the token, returning a signature equivalent to one created by
+    /// `crypto::sign` with the secret key.
+    pub(crate) fn sign(&self, message: &[u8]) -> Result<Signature, Error> {
+        let session = self.session.lock().expect("PKCS#11 session lock poisoned");
+        let signature = match self.algorithm {
+            Algorithm::Ed25519 => {
+                let bytes = session.sign(&Mechanism::Eddsa, self.private_key, message)?;
+                Signature::ed25519_from_bytes(bytes).map_err(Error::InvalidSignature)?
+            }
+            Algorithm::Secp256k1 => {
+                // Our secp256k1 signatures are ECDSA signatures over the SHA-256 digest of the
+                // message, with `s` normalized to the lower half of the group order.
+                let digest = sha::sha256(message);
+                let bytes = session.sign(&Mechanism::Ecdsa, self.private_key, &digest)?;
+                Signature::secp256k1(normalize_s(&bytes)?).map_err(Error::InvalidSignature)?
+            }
+        };
+        drop(session);
+
+        crypto::verify(message, &signature, &self.public_key).map_err(Error::InvalidSignature)?;
+        Ok(signature)
+    }
+}
+
+impl Debug for Pkcs11Key {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Pkcs11Key")
+            .field("public_key", &self.public_key)
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+// Manual implementation for `DataSize` - the session and module are FFI handles whose memory is
+// owned by the PKCS#11 module.
+impl DataSize for Pkcs11Key {
+    const IS_DYNAMIC: bool = false;
+
+    const STATIC_HEAP_SIZE: usize = 0;
+
+    fn estimate_heap_size(&self) -> usize {
+        0
+    }
+}
+
+/// Finds the single key of the given class with the given label.
+fn find_key(session: &Session, class: ObjectClass, label: &str) -> Result<ObjectHandle, Error> {
+    let class_name = if class == ObjectClass::PRIVATE_KEY {
+        "private key"
+    } else {
+        "public key"
+    };
+    let mut handles = session.find_objects(&[
+        Attribute::Class(class),
+        Attribute::Label(label.as_bytes().to_vec()),
+    ])?;
+    match handles.len() {
+        0 => Err(Error::KeyNotFound {
+            class: class_name,
+            label: label.to_string(),
+        }),
+        1 => Ok(handles.remove(0)),
+        _ => Err(Error::AmbiguousKey {
+            class: class_name,
+            label: label.to_string(),
+        }),
+    }
+}
+
+/// Returns the contents of the DER-encoded octet string `CKA_EC_POINT` is specified to hold, or
+/// `bytes` unchanged if it already has one of the `raw_lengths`, as returned by some tokens.
+fn unwrap_octet_string<'a>(bytes: &'a [u8], raw_lengths: &[usize]) -> &'a [u8] {
+    if raw_lengths.contains(&bytes.len()) {
+        return bytes;
+    }
+    match bytes {
+        [DER_OCTET_STRING_TAG, length, contents @ ..] if usize::from(*length) == contents.len() => {
+            contents
+        }
+        _ => bytes,
+    }
+}
+
+/// Converts a raw `r || s` secp256k1 signature into one whose `s` is in the lower half of the group
+/// order, which is the only form accepted when verifying.
+fn normalize_s(signature: &[u8]) -> Result<[u8; 64], Error> {
+    let mut normalized = [0; 64];
+    if signature.len() != normalized.len() {
+        return Err(Error::InvalidSignature(crypto::Error::AsymmetricKey(
+            format!(
+                "expected a {} byte secp256k1 signature, got {} bytes",
+                normalized.len(),
+                signature.len()
+            ),
+        )));
+    }
+    normalized.copy_from_slice(signature);
+
+    let order = BigNum::from_slice(&SECP256K1_ORDER)?;
+    let mut half_order = BigNum::new()?;
+    half_order.rshift1(&order)?;
+    let s = BigNum::from_slice(&signature[32..])?;
+    if s > half_order {
+        let mut low_s = BigNum::new()?;
+        low_s.checked_sub(&order, &s)?;
+        normalized[32..].copy_from_slice(&low_s.to_vec_padded(32)?);
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::SecretKey;
+
+    use super::*;
+
+    #[test]
+    fn should_unwrap_ec_point() {
+        let point = [7; PublicKey::ED25519_LENGTH];
+        let mut wrapped = vec![DER_OCTET_STRING_TAG, point.len() as u8];
+        wrapped.extend_from_slice(&point);
+
+        assert_eq!(
+            unwrap_octet_string(&wrapped, &[PublicKey::ED25519_LENGTH]),
+            &point[..]
+        );
+        assert_eq!(
+            unwrap_octet_string(&point, &[PublicKey::ED25519_LENGTH]),
+            &point[..]
+        );
+    }
+
+    #[test]
+    fn should_normalize_high_s() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random_secp256k1(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let message = b"message";
+        let signature = crypto::sign(message, &secret_key, &public_key);
+        let low_s_bytes = match signature {
+            Signature::Secp256k1(signature) => signature.as_ref().to_vec(),
+            _ => unreachable!("expected secp256k1 signature"),
+        };
+
+        // A signature with `s` replaced by `n - s` is equally valid, but only accepted once
+        // normalized back.
+        let order = BigNum::from_slice(&SECP256K1_ORDER).unwrap();
+        let s = BigNum::from_slice(&low_s_bytes[32..]).unwrap();
+        let mut high_s = BigNum::new().unwrap();
+        high_s.checked_sub(&order, &s).unwrap();
+        let mut high_s_bytes = low_s_bytes.clone();
+        high_s_bytes[32..].copy_from_slice(&high_s.to_vec_padded(32).unwrap());
+
+        assert_eq!(&normalize_s(&high_s_bytes).unwrap()[..], &low_s_bytes[..]);
+        assert_eq!(&normalize_s(&low_s_bytes).unwrap()[..], &low_s_bytes[..]);
+
+        let normalized = Signature::secp256k1(normalize_s(&high_s_bytes).unwrap()).unwrap();
+        assert!(crypto::verify(message, &normalized, &public_key).is_ok());
+    }
+}
@@ -101,6 +101,10 @@ impl Payload for Message {
         }
     }
 
+    fn is_consensus(&self) -> bool {
+        matches!(self, Message::Consensus(_))
+    }
+
     #[inline]
     fn incoming_resource_estimate(&self, weights: &EstimatorWeights) -> u32 {
         match self {
@@ -392,3 +396,53 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use casper_types::EraId;
+
+    use super::*;
+    use crate::{
+        reactor::{EventQueueHandle, QueueKind, Scheduler},
+        utils,
+    };
+
+    #[derive(Debug)]
+    struct Incoming(Message);
+
+    impl FromIncoming<Message> for Incoming {
+        fn from_incoming(_sender: NodeId, payload: Message) -> Self {
+            Incoming(payload)
+        }
+    }
+
+    #[tokio::test]
+    async fn should_schedule_incoming_consensus_messages_on_consensus_queue() {
+        let mut rng = crate::new_rng();
+        let scheduler = utils::leak(Scheduler::<Incoming>::new(QueueKind::weights()));
+        let effect_builder = EffectBuilder::new(EventQueueHandle::without_shutdown(scheduler));
+        let sender = NodeId::random(&mut rng);
+
+        let consensus_message = Message::Consensus(consensus::ConsensusMessage::Protocol {
+            era_id: EraId::from(1),
+            payload: vec![],
+        });
+        let request = Message::GetRequest {
+            tag: Tag::Deploy,
+            serialized_id: vec![],
+        };
+        effect_builder
+            .announce_incoming(sender, consensus_message)
+            .await;
+        effect_builder.announce_incoming(sender, request).await;
+
+        for _ in 0..2 {
+            let ((_origin, Incoming(message)), queue_kind) = scheduler.pop().await;
+            match message {
+                Message::Consensus(_) => assert_eq!(queue_kind, QueueKind::Consensus),
+                Message::GetRequest { .. } => assert_eq!(queue_kind, QueueKind::NetworkIncoming),
+                other => panic!("unexpected message {}", other),
+            }
+        }
+    }
+}
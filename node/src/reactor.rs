@@ -27,37 +27,44 @@
 //! With all these set up, a reactor can be executed using a [`Runner`], either in a step-wise
 //! manner using [`Runner::crank`] or indefinitely using [`Runner::run`].
 
+mod correlation;
+pub(crate) mod crash_dump;
 mod event_queue_metrics;
+mod health_metrics;
 pub(crate) mod initializer;
 pub(crate) mod joiner;
 pub(crate) mod participating;
 mod queue_kind;
+mod runtime_metrics;
+mod shutdown;
+pub(crate) mod watchdog;
 
-#[cfg(test)]
-use std::sync::Arc;
 use std::{
     any,
     collections::HashMap,
     env,
-    fmt::{Debug, Display},
+    fmt::{self, Debug, Display, Formatter},
     io::Write,
     mem,
     num::NonZeroU64,
     str::FromStr,
-    sync::atomic::Ordering,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 use datasize::DataSize;
 use erased_serde::Serialize as ErasedSerialize;
 use futures::{future::BoxFuture, FutureExt};
 use once_cell::sync::Lazy;
-use prometheus::{self, Histogram, HistogramOpts, IntCounter, IntGauge, Registry};
+use prometheus::{self, Histogram, HistogramOpts, HistogramVec, IntCounter, IntGauge, Registry};
 use quanta::{Clock, IntoNanoseconds};
 use serde::Serialize;
 use signal_hook::consts::signal::{SIGINT, SIGQUIT, SIGTERM};
 use stats_alloc::{Stats, INSTRUMENTED_SYSTEM};
-use tokio::time::{Duration, Instant};
-use tracing::{debug, debug_span, error, info, instrument, trace, warn, Span};
+use tokio::time::{self, Duration, Instant};
+use tracing::{debug, debug_span, enabled, error, info, instrument, trace, warn, Level, Span};
 use tracing_futures::Instrument;
 
 use casper_types::EraId;
@@ -69,10 +76,11 @@ use crate::{
         incoming::NetResponse,
         Effect, EffectBuilder, EffectExt, Effects,
     },
+    logging,
     types::{
         Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
         BlockSignatures, BlockWithMetadata, Deploy, DeployHash, ExitCode, FinalizedApprovalsWithId,
-        Item, NodeId,
+        Item, NodeId, ShutdownConfig,
     },
     unregister_metric,
     utils::{
@@ -80,21 +88,61 @@ use crate::{
         rlimit::{Limit, OpenFiles, ResourceLimit},
         SharedFlag, Source, WeightedRoundRobin,
     },
-    NodeRng, TERMINATION_REQUESTED,
+    NodeRng, CONFIG_RELOAD_REQUESTED, TERMINATION_REQUESTED,
 };
 #[cfg(test)]
 use crate::{
     reactor::initializer::Reactor as InitializerReactor,
     types::{Chainspec, ChainspecRawBytes},
 };
+use correlation::CorrelationId;
 pub(crate) use queue_kind::QueueKind;
+use runtime_metrics::RuntimeMetrics;
+use shutdown::ShutdownStage;
+
+/// Loads the current version of the node's configuration, e.g. by re-reading the config file.
+#[derive(Clone)]
+pub(crate) struct ConfigLoader(
+    Arc<dyn Fn() -> anyhow::Result<participating::Config> + Send + Sync>,
+);
+
+impl ConfigLoader {
+    /// Creates a new config loader calling `load` to load the configuration.
+    pub(crate) fn new<F>(load: F) -> Self
+    where
+        F: Fn() -> anyhow::Result<participating::Config> + Send + Sync + 'static,
+    {
+        ConfigLoader(Arc::new(load))
+    }
+
+    /// Loads the current version of the configuration.
+    fn load(&self) -> anyhow::Result<participating::Config> {
+        (self.0)()
+    }
+}
 
-/// Default threshold for when an event is considered slow.  Can be overridden by setting the env
-/// var `CL_EVENT_MAX_MICROSECS=<MICROSECONDS>`.
+impl Debug for ConfigLoader {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter.write_str("ConfigLoader")
+    }
+}
+
+/// Default threshold for when an event is considered slow, until the one from the config is set.
 const DEFAULT_DISPATCH_EVENT_THRESHOLD: Duration = Duration::from_secs(1);
 const DISPATCH_EVENT_THRESHOLD_ENV_VAR: &str = "CL_EVENT_MAX_MICROSECS";
 
-static DISPATCH_EVENT_THRESHOLD: Lazy<Duration> = Lazy::new(|| {
+/// The maximum length of the debug representation of a slow event logged at debug level.
+const MAX_SLOW_EVENT_DEBUG_LEN: usize = 512;
+
+/// Number of spawned effect tasks which have not completed yet.
+static ACTIVE_EFFECT_TASKS: AtomicUsize = AtomicUsize::new(0);
+
+/// How often to check whether a shutdown stage has completed while there are no events to dispatch.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Threshold for when an event is considered slow, set via the env var
+/// `CL_EVENT_MAX_MICROSECS=<MICROSECONDS>`.  Takes precedence over the threshold from the config.
+static DISPATCH_EVENT_THRESHOLD_OVERRIDE: Lazy<Option<Duration>> = Lazy::new(|| {
     env::var(DISPATCH_EVENT_THRESHOLD_ENV_VAR)
         .map(|threshold_str| {
             let threshold_microsecs = u64::from_str(&threshold_str).unwrap_or_else(|error| {
@@ -105,7 +153,7 @@ static DISPATCH_EVENT_THRESHOLD: Lazy<Duration> = Lazy::new(|| {
             });
             Duration::from_micros(threshold_microsecs)
         })
-        .unwrap_or_else(|_| DEFAULT_DISPATCH_EVENT_THRESHOLD)
+        .ok()
 });
 
 /// The desired limit for open files.
@@ -165,9 +213,18 @@ pub(crate) enum ReactorExit {
 ///
 /// Components rarely use this, but use a bound `EventQueueHandle` instead.
 ///
-/// Schedule tuples contain an optional ancestor ID and the actual event. The ancestor ID indicates
-/// which potential previous event resulted in the event being created.
-pub(crate) type Scheduler<Ev> = WeightedRoundRobin<(Option<NonZeroU64>, Ev), QueueKind>;
+/// Schedule tuples contain the origin of the event and the actual event.
+pub(crate) type Scheduler<Ev> = WeightedRoundRobin<(EventOrigin, Ev), QueueKind>;
+
+/// The origin of a scheduled event.
+#[derive(Copy, Clone, Debug, Default, Serialize)]
+pub(crate) struct EventOrigin {
+    /// The ID of the previous event which resulted in the event being created, if any.
+    pub(crate) ancestor: Option<NonZeroU64>,
+    /// The correlation ID the event inherited, if any.  If `None`, the event starts a new
+    /// correlation when dispatched.
+    pub(crate) correlation_id: Option<CorrelationId>,
+}
 
 /// Event queue handle
 ///
@@ -215,7 +272,8 @@ impl<REv> EventQueueHandle<REv> {
 
     /// Schedule an event on a specific queue.
     ///
-    /// The scheduled event will not have an ancestor.
+    /// The scheduled event will not have an ancestor, but inherits the correlation ID of the effect
+    /// it is scheduled from, if any.
     pub(crate) async fn schedule<Ev>(self, event: Ev, queue_kind: QueueKind)
     where
         REv: From<Ev>,
@@ -224,6 +282,8 @@ impl<REv> EventQueueHandle<REv> {
     }
 
     /// Schedule an event on a specific queue.
+    ///
+    /// The scheduled event inherits the correlation ID of the effect it is scheduled from, if any.
     pub(crate) async fn schedule_with_ancestor<Ev>(
         self,
         ancestor: Option<NonZeroU64>,
@@ -232,8 +292,25 @@ impl<REv> EventQueueHandle<REv> {
     ) where
         REv: From<Ev>,
     {
+        let origin = EventOrigin {
+            ancestor,
+            correlation_id: correlation::current(),
+        };
         self.scheduler
-            .push((ancestor, event.into()), queue_kind)
+            .push((origin, event.into()), queue_kind)
+            .await
+    }
+
+    /// Schedule an event triggered from outside the node, such as a message received from a peer,
+    /// on a specific queue.
+    ///
+    /// The scheduled event starts a new correlation, even if scheduled from within an effect.
+    pub(crate) async fn schedule_external<Ev>(self, event: Ev, queue_kind: QueueKind)
+    where
+        REv: From<Ev>,
+    {
+        self.scheduler
+            .push((EventOrigin::default(), event.into()), queue_kind)
             .await
     }
 
@@ -242,6 +319,11 @@ impl<REv> EventQueueHandle<REv> {
         self.scheduler.event_queues_counts()
     }
 
+    /// Returns the number of events in the given queue of the scheduler.
+    pub(crate) fn event_queue_count(&self, queue_kind: QueueKind) -> usize {
+        self.scheduler.event_queue_count(queue_kind)
+    }
+
     /// Returns whether the associated reactor is currently shutting down.
     pub(crate) fn shutdown_flag(&self) -> SharedFlag {
         self.is_shutting_down
@@ -297,6 +379,39 @@ pub(crate) trait Reactor: Sized {
 
     /// Instructs the reactor to update performance metrics, if any.
     fn update_metrics(&mut self, _event_queue_handle: EventQueueHandle<Self::Event>) {}
+
+    /// Applies the reloadable subset of a freshly loaded configuration to the components, if any.
+    fn reload_config(
+        &mut self,
+        _effect_builder: EffectBuilder<Self::Event>,
+        _config: &participating::Config,
+    ) -> Effects<Self::Event> {
+        Effects::new()
+    }
+
+    /// Returns a summary of the reactor's state to include in crash dumps.
+    fn crash_dump_state(&self) -> crash_dump::ReactorState {
+        crash_dump::ReactorState::default()
+    }
+
+    /// Starts the given stage of a graceful shutdown.
+    fn begin_shutdown_stage(&mut self, _stage: ShutdownStage) {}
+
+    /// Returns the reactor's liveness, as monitored by the watchdog.
+    fn liveness(&self) -> watchdog::Liveness {
+        watchdog::Liveness::default()
+    }
+
+    /// Returns whether the given stage of a graceful shutdown has completed.
+    ///
+    /// Events keep being dispatched until it has, or until the stage times out.
+    fn is_shutdown_stage_complete(
+        &self,
+        _stage: ShutdownStage,
+        _event_queue_handle: EventQueueHandle<Self::Event>,
+    ) -> bool {
+        true
+    }
 }
 
 /// A reactor event type.
@@ -317,6 +432,18 @@ pub(crate) trait ReactorEvent: Send + Debug + From<ControlAnnouncement> + 'stati
     fn description(&self) -> &'static str {
         "anonymous event"
     }
+
+    /// Returns the queue the event should be scheduled on when it results from an effect.
+    ///
+    /// Control announcements are put on the control queue, all other events on the regular queue
+    /// unless overridden.
+    fn queue_kind(&self) -> QueueKind {
+        if self.as_control().is_some() {
+            QueueKind::Control
+        } else {
+            QueueKind::Regular
+        }
+    }
 }
 
 /// A drop-like trait for `async` compatible drop-and-wait.
@@ -363,9 +490,15 @@ where
     /// Timestamp of last reactor metrics update.
     last_metrics: Instant,
 
+    /// Timestamp of last liveness update for the watchdog.
+    last_liveness: Instant,
+
     /// Metrics for the runner.
     metrics: RunnerMetrics,
 
+    /// Metrics for the async runtime.
+    runtime_metrics: RuntimeMetrics,
+
     /// Check if we need to update reactor metrics every this many events.
     event_metrics_threshold: u64,
 
@@ -377,6 +510,15 @@ where
 
     /// Flag indicating the reactor is being shut down.
     is_shutting_down: SharedFlag,
+
+    /// Loader used to re-read the configuration when a reload is requested.
+    config_loader: Option<ConfigLoader>,
+
+    /// Dispatching an event taking longer than this is logged as a warning.
+    slow_event_threshold: Duration,
+
+    /// Timeouts for the stages of a graceful shutdown.
+    shutdown_config: ShutdownConfig,
 }
 
 /// Metric data for the Runner
@@ -386,6 +528,10 @@ struct RunnerMetrics {
     events: IntCounter,
     /// Histogram of how long it took to dispatch an event.
     event_dispatch_duration: Histogram,
+    /// Histograms of how long events spent queued before being dispatched, by event type.
+    event_queue_duration: HistogramVec,
+    /// Histograms of how long it took to dispatch an event, by event type.
+    event_handler_duration: HistogramVec,
     /// Total allocated RAM in bytes, as reported by stats_alloc.
     allocated_ram_bytes: IntGauge,
     /// Total consumed RAM in bytes, as reported by sys-info.
@@ -433,6 +579,25 @@ impl RunnerMetrics {
             ]),
         )?;
 
+        // Both per event type histograms range from 1 us to about 33 s.
+        let per_event_buckets = prometheus::exponential_buckets(1_000.0, 2.0, 26)?;
+        let event_queue_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "event_queue_duration",
+                "time in nanoseconds an event spent queued before being dispatched, by event type",
+            )
+            .buckets(per_event_buckets.clone()),
+            &["event"],
+        )?;
+        let event_handler_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "event_handler_duration",
+                "time in nanoseconds to dispatch an event, by event type",
+            )
+            .buckets(per_event_buckets),
+            &["event"],
+        )?;
+
         let allocated_ram_bytes =
             IntGauge::new("allocated_ram_bytes", "total allocated ram in bytes")?;
         let consumed_ram_bytes =
@@ -441,6 +606,8 @@ impl RunnerMetrics {
 
         registry.register(Box::new(events.clone()))?;
         registry.register(Box::new(event_dispatch_duration.clone()))?;
+        registry.register(Box::new(event_queue_duration.clone()))?;
+        registry.register(Box::new(event_handler_duration.clone()))?;
         registry.register(Box::new(allocated_ram_bytes.clone()))?;
         registry.register(Box::new(consumed_ram_bytes.clone()))?;
         registry.register(Box::new(total_ram_bytes.clone()))?;
@@ -448,6 +615,8 @@ impl RunnerMetrics {
         Ok(RunnerMetrics {
             events,
             event_dispatch_duration,
+            event_queue_duration,
+            event_handler_duration,
             registry: registry.clone(),
             allocated_ram_bytes,
             consumed_ram_bytes,
@@ -460,6 +629,8 @@ impl Drop for RunnerMetrics {
     fn drop(&mut self) {
         unregister_metric!(self.registry, self.events);
         unregister_metric!(self.registry, self.event_dispatch_duration);
+        unregister_metric!(self.registry, self.event_queue_duration);
+        unregister_metric!(self.registry, self.event_handler_duration);
         unregister_metric!(self.registry, self.allocated_ram_bytes);
         unregister_metric!(self.registry, self.consumed_ram_bytes);
         unregister_metric!(self.registry, self.total_ram_bytes);
@@ -498,9 +669,13 @@ where
 
         let event_queue = EventQueueHandle::new(scheduler, is_shutting_down);
         let (reactor, initial_effects) = R::new(cfg, registry, event_queue, rng)?;
+        crash_dump::start_recording(any::type_name::<R>(), move || {
+            scheduler.event_queues_counts()
+        });
+        watchdog::watch_reactor(any::type_name::<R>(), move || scheduler.item_count());
 
         // Run all effects from component instantiation.
-        process_effects(None, scheduler, initial_effects)
+        process_effects(EventOrigin::default(), scheduler, initial_effects)
             .instrument(debug_span!("process initial effects"))
             .await;
 
@@ -511,18 +686,84 @@ where
             reactor,
             current_event_id: 1,
             metrics: RunnerMetrics::new(registry)?,
+            runtime_metrics: RuntimeMetrics::new(registry)?,
             last_metrics: Instant::now(),
+            last_liveness: Instant::now(),
             event_metrics_min_delay: Duration::from_secs(30),
             event_metrics_threshold: 1000,
             clock: Clock::new(),
             is_shutting_down,
+            config_loader: None,
+            slow_event_threshold: DISPATCH_EVENT_THRESHOLD_OVERRIDE
+                .unwrap_or(DEFAULT_DISPATCH_EVENT_THRESHOLD),
+            shutdown_config: ShutdownConfig::default(),
         })
     }
 
+    /// Sets the loader used to re-read the configuration when a reload is requested via `SIGHUP`
+    /// or the diagnostics port.
+    pub(crate) fn set_config_loader(&mut self, config_loader: ConfigLoader) {
+        self.config_loader = Some(config_loader);
+    }
+
+    /// Sets the threshold above which dispatching an event is logged as slow.
+    ///
+    /// Has no effect if the `CL_EVENT_MAX_MICROSECS` env var is set.
+    pub(crate) fn set_slow_event_threshold(&mut self, threshold: Duration) {
+        self.slow_event_threshold = DISPATCH_EVENT_THRESHOLD_OVERRIDE.unwrap_or(threshold);
+    }
+
+    /// Sets the timeouts for the stages of a graceful shutdown.
+    pub(crate) fn set_shutdown_config(&mut self, shutdown_config: ShutdownConfig) {
+        self.shutdown_config = shutdown_config;
+    }
+
+    /// Reloads the configuration and applies its reloadable subset to the reactor.
+    ///
+    /// Failures are logged and leave the current configuration in place.
+    async fn reload_config(&mut self) {
+        let config = match self.config_loader.as_ref() {
+            Some(config_loader) => match config_loader.load() {
+                Ok(config) => config,
+                Err(error) => {
+                    warn!(%error, "failed to reload config, keeping current config");
+                    return;
+                }
+            },
+            None => {
+                warn!("config reload requested, but not supported while running this reactor");
+                return;
+            }
+        };
+        info!("reloading config");
+
+        if let Err(error) = logging::reload_filter(&config.logging) {
+            warn!(%error, "failed to reload log filter");
+        }
+        self.set_slow_event_threshold(config.node.slow_event_threshold.into());
+        self.set_shutdown_config(config.node.shutdown.clone());
+
+        let event_queue = EventQueueHandle::new(self.scheduler, self.is_shutting_down);
+        let effects = self
+            .reactor
+            .reload_config(EffectBuilder::new(event_queue), &config);
+        process_effects(EventOrigin::default(), self.scheduler, effects)
+            .instrument(debug_span!(
+                "process config reload effects",
+                ev = self.current_event_id
+            ))
+            .await;
+    }
+
     /// Processes a single event on the event queue.
     ///
     /// Returns `false` if processing should stop.
-    #[instrument("dispatch", level = "debug", fields(a, ev = self.current_event_id), skip(self, rng))]
+    #[instrument(
+        "dispatch",
+        level = "debug",
+        fields(a, ev = self.current_event_id, corr),
+        skip(self, rng)
+    )]
     pub(crate) async fn crank(&mut self, rng: &mut NodeRng) -> bool {
         self.metrics.events.inc();
 
@@ -534,6 +775,9 @@ where
             // We update metrics on the first very event as well to get a good baseline.
             if self.last_metrics.elapsed() >= self.event_metrics_min_delay {
                 self.reactor.update_metrics(event_queue);
+                if crash_dump::should_record_reactor_state() {
+                    crash_dump::record_reactor_state(self.reactor.crash_dump_state());
+                }
 
                 // Use a fresh timestamp. This skews the metrics collection interval a little bit,
                 // but ensures that if metrics collection time explodes, we are guaranteed a full
@@ -552,19 +796,54 @@ where
                 self.metrics.consumed_ram_bytes.set(consumed as i64);
                 self.metrics.total_ram_bytes.set(total as i64);
             }
+            self.runtime_metrics.update();
+        }
+
+        if watchdog::should_record_liveness()
+            && self.last_liveness.elapsed() >= watchdog::LIVENESS_UPDATE_INTERVAL
+        {
+            watchdog::record_liveness(self.reactor.liveness());
+            self.last_liveness = Instant::now();
         }
 
-        let ((ancestor, event), queue) = self.scheduler.pop().await;
+        let ((origin, event), queue, queue_duration) = self.scheduler.pop_timed().await;
         trace!(%event, %queue, "current");
         let event_desc = event.description();
 
+        // An event without a correlation ID starts a new one, identified by the event's own ID.
+        let correlation_id = origin
+            .correlation_id
+            .or_else(|| NonZeroU64::new(self.current_event_id).map(CorrelationId::new));
+        crash_dump::record_event(
+            self.current_event_id,
+            origin.ancestor,
+            correlation_id,
+            event_desc,
+            queue,
+        );
+        self.metrics
+            .event_queue_duration
+            .with_label_values(&[event_desc])
+            .observe(queue_duration.as_nanos() as f64);
+
         // Create another span for tracing the processing of one event.
         Span::current().record("ev", &self.current_event_id);
 
         // If we know the ancestor of an event, record it.
-        if let Some(ancestor) = ancestor {
+        if let Some(ancestor) = origin.ancestor {
             Span::current().record("a", &ancestor.get());
         }
+        if let Some(correlation_id) = correlation_id {
+            Span::current().record("corr", &correlation_id.get());
+        }
+
+        // Dispatching consumes the event, so capture its debug representation in case it is slow,
+        // but only if it would be logged, to keep formatting off the hot path.
+        let maybe_event_debug = if enabled!(Level::DEBUG) {
+            Some(utils::truncated_debug(&event, MAX_SLOW_EVENT_DEBUG_LEN))
+        } else {
+            None
+        };
 
         // Dispatch the event, then execute the resulting effect.
         let start = self.clock.start();
@@ -584,6 +863,7 @@ where
                 }
                 Some(ControlAnnouncement::FatalError { file, line, msg }) => {
                     error!(%file, %line, %msg, "fatal error via control announcement");
+                    self.write_crash_dump(&format!("fatal error at {}:{}: {}", file, line, msg));
                     (Default::default(), false)
                 }
                 Some(ControlAnnouncement::QueueDumpRequest {
@@ -642,19 +922,36 @@ where
         };
 
         let end = self.clock.end();
+        watchdog::record_dispatch();
 
         // Warn if processing took a long time, record to histogram.
         let delta = self.clock.delta(start, end);
-        if delta > *DISPATCH_EVENT_THRESHOLD {
-            warn!(%event_desc, ns = delta.into_nanos(), "event took very long to dispatch");
+        if delta > self.slow_event_threshold {
+            warn!(
+                %event_desc,
+                elapsed = ?delta,
+                ns = delta.into_nanos(),
+                "event took very long to dispatch"
+            );
+            if let Some(event_debug) = maybe_event_debug {
+                debug!(%event_desc, event = %event_debug, "slow event");
+            }
         }
         self.metrics
             .event_dispatch_duration
             .observe(delta.into_nanos() as f64);
+        self.metrics
+            .event_handler_duration
+            .with_label_values(&[event_desc])
+            .observe(delta.into_nanos() as f64);
 
-        // Run effects, with the current event ID as the ancestor for resulting set of events.
+        // Run effects, with the current event ID as the ancestor for resulting set of events, which
+        // inherit its correlation ID.
         process_effects(
-            NonZeroU64::new(self.current_event_id),
+            EventOrigin {
+                ancestor: NonZeroU64::new(self.current_event_id),
+                correlation_id,
+            },
             self.scheduler,
             effects,
         )
@@ -666,6 +963,15 @@ where
         keep_going
     }
 
+    /// Writes a crash dump including the reactor's current state, if crash dumps are enabled.
+    fn write_crash_dump(&self, reason: &str) {
+        match crash_dump::write_fatal_error(reason, self.reactor.crash_dump_state()) {
+            Ok(Some(path)) => error!(path = %path.display(), "wrote crash dump"),
+            Ok(None) => {}
+            Err(error) => error!(%error, "failed to write crash dump"),
+        }
+    }
+
     /// Gets both the allocated and total memory from sys-info + jemalloc
     fn get_allocated_memory() -> Option<AllocatedMem> {
         let mem_info = match sys_info::mem_info() {
@@ -698,10 +1004,15 @@ where
 
     /// Runs the reactor until `maybe_exit()` returns `Some` or we get interrupted by a termination
     /// signal.
+    ///
+    /// On `SIGINT` or `SIGTERM` the reactor is shut down gracefully, see the [`shutdown`] module.
     pub(crate) async fn run(&mut self, rng: &mut NodeRng) -> ReactorExit {
         loop {
             match TERMINATION_REQUESTED.load(Ordering::SeqCst) as i32 {
                 0 => {
+                    if CONFIG_RELOAD_REQUESTED.swap(false, Ordering::SeqCst) {
+                        self.reload_config().await;
+                    }
                     if let Some(reactor_exit) = self.reactor.maybe_exit() {
                         self.is_shutting_down.set();
 
@@ -715,13 +1026,16 @@ where
                         // since that workaround of making two attempts with the first wrapped in a
                         // timeout should no longer be required.
 
-                        for (ancestor, event) in
-                            self.scheduler.drain_queue(QueueKind::Control).await
+                        for (origin, event) in self.scheduler.drain_queue(QueueKind::Control).await
                         {
                             if let Some(ctrl_ann) = event.as_control() {
                                 match ctrl_ann {
                                     ControlAnnouncement::FatalError { file, line, msg } => {
                                         warn!(%file, line=*line, %msg, "exiting due to fatal error scheduled before reactor completion");
+                                        self.write_crash_dump(&format!(
+                                            "fatal error at {}:{}: {}",
+                                            file, line, msg
+                                        ));
                                         return ReactorExit::ProcessShouldExit(ExitCode::Abort);
                                     }
                                     ControlAnnouncement::QueueDumpRequest { .. } => {
@@ -731,7 +1045,7 @@ where
                                     }
                                 }
                             } else {
-                                debug!(?ancestor, %event, "found non-control announcement while draining queue")
+                                debug!(?origin, %event, "found non-control announcement while draining queue")
                             }
                         }
 
@@ -742,29 +1056,71 @@ where
                         break ReactorExit::ProcessShouldExit(ExitCode::Abort);
                     }
                 }
-                SIGINT => {
-                    self.is_shutting_down.set();
-                    break ReactorExit::ProcessShouldExit(ExitCode::SigInt);
-                }
+                SIGINT => break self.shutdown(rng, ExitCode::SigInt).await,
                 SIGQUIT => {
                     self.is_shutting_down.set();
                     break ReactorExit::ProcessShouldExit(ExitCode::SigQuit);
                 }
-                SIGTERM => {
+                SIGTERM => break self.shutdown(rng, ExitCode::SigTerm).await,
+                _ => error!("should be unreachable - bug in signal handler"),
+            }
+        }
+    }
+
+    /// Runs the stages of a graceful shutdown, then returns the exit code the process should exit
+    /// with.
+    ///
+    /// This is `exit_code` unless a stage timed out or dispatching an event failed fatally.
+    async fn shutdown(&mut self, rng: &mut NodeRng, exit_code: ExitCode) -> ReactorExit {
+        info!(
+            ?exit_code,
+            "termination requested, shutting down gracefully"
+        );
+        // Reset the flag, so that a second signal can be detected and skip the remaining stages.
+        TERMINATION_REQUESTED.store(0, Ordering::SeqCst);
+        let event_queue = EventQueueHandle::new(self.scheduler, self.is_shutting_down);
+        let mut exit_code = exit_code;
+
+        for &stage in &ShutdownStage::ALL {
+            let start = Instant::now();
+            let timeout = stage.timeout(&self.shutdown_config);
+            debug!(%stage, ?timeout, "starting shutdown stage");
+            self.reactor.begin_shutdown_stage(stage);
+
+            loop {
+                if TERMINATION_REQUESTED.load(Ordering::SeqCst) != 0 {
+                    warn!(%stage, "termination requested again, skipping remaining shutdown stages");
+                    self.is_shutting_down.set();
+                    return ReactorExit::ProcessShouldExit(exit_code);
+                }
+                if self.reactor.is_shutdown_stage_complete(stage, event_queue) {
+                    info!(%stage, elapsed = ?start.elapsed(), "shutdown stage complete");
+                    break;
+                }
+                if start.elapsed() >= timeout {
+                    warn!(%stage, ?timeout, "shutdown stage timed out");
+                    exit_code = ExitCode::ShutdownTimedOut;
+                    break;
+                }
+                if self.scheduler.item_count() == 0 {
+                    time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+                } else if !self.crank(rng).await {
                     self.is_shutting_down.set();
-                    break ReactorExit::ProcessShouldExit(ExitCode::SigTerm);
+                    return ReactorExit::ProcessShouldExit(ExitCode::Abort);
                 }
-                _ => error!("should be unreachable - bug in signal handler"),
             }
         }
+
+        self.is_shutting_down.set();
+        ReactorExit::ProcessShouldExit(exit_code)
     }
 
     /// Shuts down a reactor, sealing and draining the entire queue before returning it.
     pub(crate) async fn drain_into_inner(self) -> R {
         self.is_shutting_down.set();
         self.scheduler.seal();
-        for (ancestor, event) in self.scheduler.drain_queues().await {
-            debug!(?ancestor, %event, "drained event");
+        for (origin, event) in self.scheduler.drain_queues().await {
+            debug!(?origin, %event, "drained event");
         }
         self.reactor
     }
@@ -798,7 +1154,7 @@ where
 
         let effects = create_effects(effect_builder);
 
-        process_effects(None, self.scheduler, effects)
+        process_effects(EventOrigin::default(), self.scheduler, effects)
             .instrument(debug_span!(
                 "process injected effects",
                 ev = self.current_event_id
@@ -848,7 +1204,7 @@ impl Runner<InitializerReactor> {
 
         // Run all effects from component instantiation.
         let span = debug_span!("process initial effects");
-        process_effects(None, scheduler, initial_effects)
+        process_effects(EventOrigin::default(), scheduler, initial_effects)
             .instrument(span)
             .await;
 
@@ -863,39 +1219,57 @@ impl Runner<InitializerReactor> {
             // to mean "no ancestor".
             current_event_id: 1,
             metrics: RunnerMetrics::new(&registry)?,
+            runtime_metrics: RuntimeMetrics::new(&registry)?,
             // Calculate the `last_metrics` timestamp to be exactly one delay in the past. This will
             // cause the runner to collect metrics at the first opportunity.
             last_metrics: now.checked_sub(event_metrics_min_delay).unwrap_or(now),
+            last_liveness: now,
             event_metrics_min_delay,
             event_metrics_threshold: 1000,
             clock: Clock::new(),
             is_shutting_down,
+            config_loader: None,
+            slow_event_threshold: DISPATCH_EVENT_THRESHOLD_OVERRIDE
+                .unwrap_or(DEFAULT_DISPATCH_EVENT_THRESHOLD),
+            shutdown_config: ShutdownConfig::default(),
         })
     }
 }
 
 /// Spawns tasks that will process the given effects.
 ///
-/// Result events from processing the events will be scheduled with the given ancestor.
+/// Result events from processing the events will be scheduled with the given origin.  The effects
+/// run within the scope of its correlation ID, so events they schedule directly inherit it as well.
 async fn process_effects<Ev>(
-    ancestor: Option<NonZeroU64>,
+    origin: EventOrigin,
     scheduler: &'static Scheduler<Ev>,
     effects: Effects<Ev>,
 ) where
-    Ev: Send + 'static,
+    Ev: ReactorEvent,
 {
-    // TODO: Properly carry around priorities.
-    let queue_kind = QueueKind::default();
-
     for effect in effects {
+        ACTIVE_EFFECT_TASKS.fetch_add(1, Ordering::Relaxed);
         tokio::spawn(async move {
-            for event in effect.await {
-                scheduler.push((ancestor, event), queue_kind).await
+            let run_effect = async move {
+                for event in effect.await {
+                    let queue_kind = event.queue_kind();
+                    scheduler.push((origin, event), queue_kind).await
+                }
+            };
+            match origin.correlation_id {
+                Some(correlation_id) => correlation::scope(correlation_id, run_effect).await,
+                None => run_effect.await,
             }
+            ACTIVE_EFFECT_TASKS.fetch_sub(1, Ordering::Relaxed);
         });
     }
 }
 
+/// Returns the number of spawned effect tasks which have not completed yet.
+fn active_effect_tasks() -> usize {
+    ACTIVE_EFFECT_TASKS.load(Ordering::Relaxed)
+}
+
 /// Converts a single effect into another by wrapping it.
 fn wrap_effect<Ev, REv, F>(wrap: F, effect: Effect<Ev>) -> Effect<REv>
 where
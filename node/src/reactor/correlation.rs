@@ -0,0 +1,81 @@
+//! Correlation IDs.
+//!
+//! Every event dispatched by the runner carries a correlation ID, identifying the externally
+//! triggered piece of work it belongs to, such as a deploy received from a peer or a block being
+//! fetched.  An event scheduled from outside of any effect, e.g. by a network connection or an API
+//! server, starts a new correlation, whose ID is the ID of that event.  All events resulting from
+//! dispatching it inherit its correlation ID, including those scheduled by its effects, such as
+//! requests and their responses, and announcements, so that the whole lifecycle of an item can be
+//! followed through the logs by filtering on the `corr` field of the `dispatch` span.
+//!
+//! Effects run within the scope of the correlation ID of the event which created them.  Tasks
+//! spawned separately from an effect do not inherit it.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    num::NonZeroU64,
+};
+
+use futures::Future;
+use serde::Serialize;
+
+tokio::task_local! {
+    /// The correlation ID of the event whose effect is running on the current task.
+    static CURRENT: CorrelationId;
+}
+
+/// The ID of the externally triggered piece of work an event belongs to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub(crate) struct CorrelationId(NonZeroU64);
+
+impl CorrelationId {
+    /// Creates the correlation ID started by the event with the given ID.
+    pub(crate) fn new(root_event_id: NonZeroU64) -> Self {
+        CorrelationId(root_event_id)
+    }
+
+    /// Returns the ID as an integer, e.g. to record it in a span.
+    pub(crate) fn get(self) -> u64 {
+        self.0.get()
+    }
+}
+
+impl Display for CorrelationId {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.0)
+    }
+}
+
+/// Returns the correlation ID of the effect running on the current task, if any.
+pub(crate) fn current() -> Option<CorrelationId> {
+    CURRENT.try_with(|correlation_id| *correlation_id).ok()
+}
+
+/// Runs `future` with `correlation_id` as the current correlation ID.
+pub(crate) async fn scope<F>(correlation_id: CorrelationId, future: F) -> F::Output
+where
+    F: Future,
+{
+    CURRENT.scope(correlation_id, future).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_only_be_set_within_scope() {
+        let correlation_id = CorrelationId::new(NonZeroU64::new(7).unwrap());
+        assert_eq!(current(), None);
+        let inner = scope(correlation_id, async { current() }).await;
+        assert_eq!(inner, Some(correlation_id));
+        assert_eq!(current(), None);
+
+        // Tasks spawned from within a scope start without a correlation ID.
+        let spawned = scope(correlation_id, async {
+            tokio::spawn(async { current() }).await.unwrap()
+        })
+        .await;
+        assert_eq!(spawned, None);
+    }
+}
@@ -0,0 +1,333 @@
+//! Crash dumps.
+//!
+//! While crash dumps are enabled, the runner records the most recent events it dispatched, and
+//! periodically a summary of the reactor's state.  If the node panics or exits due to a fatal
+//! error, this is written to a JSON file in the configured directory before exiting, giving a
+//! post-mortem view of what the node was doing when it went down.
+//!
+//! Crash dumps are only enabled by the node's binary, never in tests.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    num::NonZeroU64,
+    panic::{self, PanicInfo},
+    path::{Path, PathBuf},
+    sync::{Mutex, TryLockError},
+};
+
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+
+use casper_types::{EraId, TimeDiff, Timestamp};
+
+use super::{correlation::CorrelationId, QueueKind};
+use crate::types::NodeId;
+
+/// The number of events kept per event type.
+const RECENT_EVENTS_PER_TYPE: usize = 16;
+
+/// The directory crash dumps are written to, set once crash dumps are enabled.
+static CRASH_DUMP_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// The recorder of the currently running reactor.
+static RECORDER: Lazy<Mutex<Option<FlightRecorder>>> = Lazy::new(|| Mutex::new(None));
+
+/// A summary of the reactor's state, as included in crash dumps.
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ReactorState {
+    /// The state of consensus, if the reactor runs it.
+    pub(crate) consensus: Option<ConsensusState>,
+    /// The network connections, if the reactor has any.
+    pub(crate) connections: Option<ConnectionSummary>,
+}
+
+/// The consensus round info included in crash dumps.
+#[derive(Debug, Serialize)]
+pub(crate) struct ConsensusState {
+    /// The most recent era.
+    pub(crate) current_era: EraId,
+    /// All eras which are still open.
+    pub(crate) open_eras: Vec<EraId>,
+    /// The round length in the current era, if this node is an active validator in it.
+    pub(crate) next_round_length: Option<TimeDiff>,
+}
+
+/// The network connections included in crash dumps.
+#[derive(Debug, Serialize)]
+pub(crate) struct ConnectionSummary {
+    /// The number of connected peers.
+    pub(crate) connected_peers: usize,
+    /// The connected peers and their addresses.
+    pub(crate) peers: BTreeMap<NodeId, String>,
+}
+
+/// An event dispatched by the runner.
+#[derive(Clone, Debug, Serialize)]
+struct EventRecord {
+    id: u64,
+    ancestor: Option<NonZeroU64>,
+    correlation_id: Option<CorrelationId>,
+    description: &'static str,
+    queue: QueueKind,
+    dispatched_at: Timestamp,
+}
+
+/// The reactor state recorded while running, to be written out if the node crashes.
+struct FlightRecorder {
+    /// The type name of the reactor.
+    reactor: &'static str,
+    /// Returns the current number of events in each of the reactor's queues.
+    queue_depths: Box<dyn Fn() -> HashMap<QueueKind, usize> + Send>,
+    /// The most recently dispatched events, by event type.
+    recent_events: HashMap<&'static str, VecDeque<EventRecord>>,
+    /// The event being dispatched, or last dispatched.
+    current_event: Option<EventRecord>,
+    /// The last recorded reactor state and when it was recorded.
+    reactor_state: Option<(Timestamp, ReactorState)>,
+}
+
+impl FlightRecorder {
+    fn record_event(&mut self, record: EventRecord) {
+        let recent = self.recent_events.entry(record.description).or_default();
+        if recent.len() == RECENT_EVENTS_PER_TYPE {
+            recent.pop_front();
+        }
+        recent.push_back(record.clone());
+        self.current_event = Some(record);
+    }
+}
+
+/// The contents of a crash dump file.
+#[derive(Serialize)]
+struct CrashDump<'a> {
+    reason: &'a str,
+    timestamp: Timestamp,
+    reactor: &'static str,
+    current_event: Option<&'a EventRecord>,
+    queue_depths: BTreeMap<QueueKind, usize>,
+    /// The reactor state at `reactor_state_timestamp`.
+    reactor_state: Option<&'a ReactorState>,
+    reactor_state_timestamp: Option<Timestamp>,
+    recent_events: BTreeMap<&'static str, &'a VecDeque<EventRecord>>,
+}
+
+/// Enables crash dumps, written to `dir`, and installs a panic hook writing one on panics.
+///
+/// The panic hook runs before any previously installed one.  Calling this more than once has no
+/// effect.
+pub(crate) fn enable(dir: PathBuf) {
+    if CRASH_DUMP_DIR.set(dir).is_err() {
+        return;
+    }
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        on_panic(info);
+        previous_hook(info)
+    }));
+}
+
+fn is_enabled() -> bool {
+    CRASH_DUMP_DIR.get().is_some()
+}
+
+/// Starts recording the state of a newly created reactor, replacing that of any previous one.
+pub(crate) fn start_recording<F>(reactor: &'static str, queue_depths: F)
+where
+    F: Fn() -> HashMap<QueueKind, usize> + Send + 'static,
+{
+    if !is_enabled() {
+        return;
+    }
+    let recorder = FlightRecorder {
+        reactor,
+        queue_depths: Box::new(queue_depths),
+        recent_events: HashMap::new(),
+        current_event: None,
+        reactor_state: None,
+    };
+    *lock_recorder() = Some(recorder);
+}
+
+/// Records that an event is about to be dispatched.
+pub(crate) fn record_event(
+    id: u64,
+    ancestor: Option<NonZeroU64>,
+    correlation_id: Option<CorrelationId>,
+    description: &'static str,
+    queue: QueueKind,
+) {
+    if !is_enabled() {
+        return;
+    }
+    if let Some(recorder) = lock_recorder().as_mut() {
+        recorder.record_event(EventRecord {
+            id,
+            ancestor,
+            correlation_id,
+            description,
+            queue,
+            dispatched_at: Timestamp::now(),
+        });
+    }
+}
+
+/// Returns whether the reactor state should be recorded, i.e. whether crash dumps are enabled.
+pub(crate) fn should_record_reactor_state() -> bool {
+    is_enabled()
+}
+
+/// Records a summary of the reactor's state, to be included in a crash dump on panic.
+pub(crate) fn record_reactor_state(reactor_state: ReactorState) {
+    if let Some(recorder) = lock_recorder().as_mut() {
+        recorder.reactor_state = Some((Timestamp::now(), reactor_state));
+    }
+}
+
+/// Writes a crash dump for a fatal error, including the reactor's current state.
+///
+/// Returns the path of the crash dump, or `None` if crash dumps are not enabled.
+pub(crate) fn write_fatal_error(
+    reason: &str,
+    reactor_state: ReactorState,
+) -> io::Result<Option<PathBuf>> {
+    let dir = match CRASH_DUMP_DIR.get() {
+        Some(dir) => dir,
+        None => return Ok(None),
+    };
+    record_reactor_state(reactor_state);
+    match lock_recorder().as_ref() {
+        Some(recorder) => write(dir, reason, recorder).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Writes a crash dump using the last recorded reactor state, for use where the reactor itself
+/// cannot be accessed.
+///
+/// Returns the path of the crash dump, or `None` if crash dumps are not enabled.
+pub(crate) fn write_recorded(reason: &str) -> io::Result<Option<PathBuf>> {
+    let dir = match CRASH_DUMP_DIR.get() {
+        Some(dir) => dir,
+        None => return Ok(None),
+    };
+    match lock_recorder().as_ref() {
+        Some(recorder) => write(dir, reason, recorder).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Writes a crash dump from within the panic hook, using the last recorded reactor state.
+fn on_panic(info: &PanicInfo) {
+    let dir = match CRASH_DUMP_DIR.get() {
+        Some(dir) => dir,
+        None => return,
+    };
+    // The panicking thread may hold the lock already, in which case we must not wait for it.
+    let guard = match RECORDER.try_lock() {
+        Ok(guard) => guard,
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+        Err(TryLockError::WouldBlock) => {
+            eprintln!("crash dump state is locked, not writing a crash dump");
+            return;
+        }
+    };
+    if let Some(recorder) = guard.as_ref() {
+        match write(dir, &format!("panic: {}", info), recorder) {
+            Ok(path) => eprintln!("wrote crash dump to {}", path.display()),
+            Err(error) => eprintln!("failed to write crash dump: {}", error),
+        }
+    }
+}
+
+fn lock_recorder() -> std::sync::MutexGuard<'static, Option<FlightRecorder>> {
+    // Recording never panics while holding the lock, but a poisoned lock is harmless anyway.
+    RECORDER
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Writes a crash dump to a new file in `dir`, returning its path.
+fn write(dir: &Path, reason: &str, recorder: &FlightRecorder) -> io::Result<PathBuf> {
+    let timestamp = Timestamp::now();
+    let crash_dump = CrashDump {
+        reason,
+        timestamp,
+        reactor: recorder.reactor,
+        current_event: recorder.current_event.as_ref(),
+        queue_depths: (recorder.queue_depths)().into_iter().collect(),
+        reactor_state: recorder.reactor_state.as_ref().map(|(_, state)| state),
+        reactor_state_timestamp: recorder
+            .reactor_state
+            .as_ref()
+            .map(|(timestamp, _)| *timestamp),
+        recent_events: recorder
+            .recent_events
+            .iter()
+            .map(|(description, events)| (*description, events))
+            .collect(),
+    };
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("crash-dump-{}.json", timestamp.millis()));
+    let mut writer = BufWriter::new(File::create(&path)?);
+    serde_json::to_writer_pretty(&mut writer, &crash_dump)?;
+    writer.flush()?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn should_write_recent_events_and_reactor_state() {
+        let dir = tempdir().unwrap();
+        let mut recorder = FlightRecorder {
+            reactor: "TestReactor",
+            queue_depths: Box::new(|| [(QueueKind::Regular, 3)].iter().copied().collect()),
+            recent_events: HashMap::new(),
+            current_event: None,
+            reactor_state: None,
+        };
+        for id in 1..=(RECENT_EVENTS_PER_TYPE as u64 + 2) {
+            recorder.record_event(EventRecord {
+                id,
+                ancestor: NonZeroU64::new(id - 1),
+                correlation_id: NonZeroU64::new(1).map(CorrelationId::new),
+                description: "Consensus",
+                queue: QueueKind::Consensus,
+                dispatched_at: Timestamp::now(),
+            });
+        }
+        recorder.reactor_state = Some((
+            Timestamp::now(),
+            ReactorState {
+                consensus: Some(ConsensusState {
+                    current_era: EraId::new(7),
+                    open_eras: vec![EraId::new(6), EraId::new(7)],
+                    next_round_length: None,
+                }),
+                connections: None,
+            },
+        ));
+
+        let path = write(dir.path(), "test", &recorder).unwrap();
+        let dump: serde_json::Value = serde_json::from_reader(File::open(path).unwrap()).unwrap();
+
+        assert_eq!(dump["reason"], "test");
+        assert_eq!(dump["queue_depths"]["Regular"], 3);
+        assert_eq!(
+            dump["current_event"]["id"],
+            RECENT_EVENTS_PER_TYPE as u64 + 2
+        );
+        assert_eq!(dump["current_event"]["correlation_id"], 1);
+        assert_eq!(dump["reactor_state"]["consensus"]["current_era"], 7);
+        let recent = dump["recent_events"]["Consensus"].as_array().unwrap();
+        assert_eq!(recent.len(), RECENT_EVENTS_PER_TYPE);
+        assert_eq!(recent[0]["id"], 3);
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use prometheus::{self, IntGauge, Registry};
+use tracing::error;
+
+use crate::{types::HealthReport, unregister_metric};
+
+/// Metrics for the health of the reactor's components.
+///
+/// Each gauge is 0 while healthy, 1 while degraded and 2 once failed.
+#[derive(Debug)]
+pub(super) struct HealthMetrics {
+    /// The overall health of the node.
+    health_status: IntGauge,
+    /// Per component gauges, by component name.
+    component_gauges: HashMap<&'static str, IntGauge>,
+    /// Instance of registry to unregister from when being dropped.
+    registry: Registry,
+}
+
+impl HealthMetrics {
+    /// Initializes the health metrics for the given components.
+    pub(super) fn new(
+        registry: Registry,
+        components: &[&'static str],
+    ) -> Result<Self, prometheus::Error> {
+        let health_status = IntGauge::new(
+            "health_status",
+            "overall health of the node: 0 healthy, 1 degraded, 2 failed",
+        )?;
+        registry.register(Box::new(health_status.clone()))?;
+
+        let mut component_gauges = HashMap::new();
+        for component in components {
+            let gauge = IntGauge::new(
+                format!("health_{}_status", component),
+                format!(
+                    "health of the {} component: 0 healthy, 1 degraded, 2 failed",
+                    component
+                ),
+            )?;
+            registry.register(Box::new(gauge.clone()))?;
+            let result = component_gauges.insert(*component, gauge);
+            assert!(result.is_none(), "Map keys should not be overwritten.");
+        }
+
+        Ok(HealthMetrics {
+            health_status,
+            component_gauges,
+            registry,
+        })
+    }
+
+    /// Updates the gauges from a health report.
+    pub(super) fn record(&self, report: &HealthReport) {
+        self.health_status.set(report.status.as_metric());
+        for (component, health) in &report.components {
+            if let Some(gauge) = self.component_gauges.get(component.as_str()) {
+                gauge.set(health.status.as_metric());
+            }
+        }
+    }
+}
+
+impl Drop for HealthMetrics {
+    fn drop(&mut self) {
+        unregister_metric!(self.registry, self.health_status);
+        self.component_gauges.iter().for_each(|(key, gauge)| {
+            self.registry
+                .unregister(Box::new(gauge.clone()))
+                .unwrap_or_else(|_| error!("unregistering {} failed: was not registered", key))
+        });
+    }
+}
@@ -26,12 +26,12 @@ use crate::{
         },
         requests::{
             ChainspecLoaderRequest, ContractRuntimeRequest, MarkBlockCompletedRequest,
-            NetworkRequest, StorageRequest,
+            NetworkRequest, StateStoreRequest, StorageRequest,
         },
         EffectBuilder, Effects,
     },
     protocol::Message,
-    reactor::{self, participating, EventQueueHandle, ReactorExit},
+    reactor::{self, participating, shutdown::ShutdownStage, EventQueueHandle, ReactorExit},
     types::chainspec,
     utils::WithDir,
     NodeRng,
@@ -77,6 +77,10 @@ pub(crate) enum Event {
     #[from]
     MarkBlockCompletedRequest(MarkBlockCompletedRequest),
 
+    /// State store request.
+    #[from]
+    StateStoreRequest(StateStoreRequest),
+
     /// Contract runtime request.
     #[from]
     ContractRuntimeRequest(ContractRuntimeRequest),
@@ -111,6 +115,7 @@ impl ReactorEvent for Event {
             Event::ControlAnnouncement(_) => "ControlAnnouncement",
             Event::StorageRequest(_) => "StorageRequest",
             Event::MarkBlockCompletedRequest(_) => "MarkBlockCompletedRequest",
+            Event::StateStoreRequest(_) => "StateStoreRequest",
             Event::ContractRuntime(_) => "ContractRuntime",
             Event::ChainspecLoaderAnnouncement(_) => "ChainspecLoaderAnnouncement",
             Event::ContractRuntimeAnnouncement(_) => "ContractRuntimeAnnouncement",
@@ -133,6 +138,7 @@ impl Display for Event {
             Event::MarkBlockCompletedRequest(req) => {
                 write!(formatter, "mark block completed request: {}", req)
             }
+            Event::StateStoreRequest(req) => write!(formatter, "state store request: {}", req),
             Event::ContractRuntime(event) => write!(formatter, "contract runtime event: {}", event),
             Event::ChainspecLoaderAnnouncement(ann) => {
                 write!(formatter, "chainspec loader announcement: {}", ann)
@@ -219,6 +225,7 @@ impl Reactor {
             chainspec_loader.chainspec().protocol_config.version,
             storage.root_path(),
             &config.value().contract_runtime,
+            config.value().node.sync_mode,
             chainspec_loader.chainspec().wasm_config,
             chainspec_loader.chainspec().system_costs_config,
             chainspec_loader.chainspec().core_config.max_associated_keys,
@@ -239,6 +246,11 @@ impl Reactor {
                 .chainspec()
                 .protocol_config
                 .verifiable_chunked_hash_activation,
+            chainspec_loader
+                .chainspec()
+                .highway_config
+                .min_round_length(),
+            chainspec_loader.chainspec().pricing_config,
         )?;
 
         let effects = reactor::wrap_effects(Event::Chainspec, chainspec_effects);
@@ -283,8 +295,12 @@ impl reactor::Reactor for Reactor {
         let effect_builder = EffectBuilder::new(event_queue);
 
         // Construct the `ChainspecLoader` first so we fail fast if the chainspec is invalid.
+        let chainspec_dir = match config.value().node.chainspec_dir {
+            Some(ref chainspec_dir) => config.with_dir(chainspec_dir.clone()),
+            None => config.dir().to_path_buf(),
+        };
         let (chainspec_loader, chainspec_effects) =
-            ChainspecLoader::new(config.dir(), effect_builder)?;
+            ChainspecLoader::new(chainspec_dir, effect_builder)?;
         Self::new_with_chainspec_loader(config, registry, chainspec_loader, chainspec_effects)
     }
 
@@ -330,6 +346,10 @@ impl reactor::Reactor for Reactor {
                 Event::Storage,
                 self.storage.handle_event(effect_builder, rng, req.into()),
             ),
+            Event::StateStoreRequest(req) => reactor::wrap_effects(
+                Event::Storage,
+                self.storage.handle_event(effect_builder, rng, req.into()),
+            ),
             Event::ControlAnnouncement(ann) => {
                 error!(%ann, "control announcement dispatched in initializer");
                 Effects::new()
@@ -359,6 +379,23 @@ impl reactor::Reactor for Reactor {
     fn maybe_exit(&self) -> Option<ReactorExit> {
         self.chainspec_loader.reactor_exit()
     }
+
+    fn reload_config(
+        &mut self,
+        _effect_builder: EffectBuilder<Self::Event>,
+        config: &participating::Config,
+    ) -> Effects<Self::Event> {
+        self.config.value_mut().update_reloadable(config);
+        Effects::new()
+    }
+
+    fn begin_shutdown_stage(&mut self, stage: ShutdownStage) {
+        if stage == ShutdownStage::SyncStorage {
+            if let Err(error) = self.storage.sync() {
+                error!(%error, "failed to sync storage");
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -36,7 +36,7 @@ use crate::{
         rest_server::{self, RestServer},
         small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
         storage::{self, Storage},
-        Component,
+        Component, HealthCheck,
     },
     contract_runtime,
     effect::{
@@ -45,7 +45,7 @@ use crate::{
             ContractRuntimeAnnouncement, ControlAnnouncement, DeployAcceptorAnnouncement,
             GossiperAnnouncement, LinearChainAnnouncement,
         },
-        diagnostics_port::DumpConsensusStateRequest,
+        diagnostics_port::{DumpConsensusStateRequest, DumpMemoryRequest},
         incoming::{
             ConsensusMessageIncoming, FinalitySignatureIncoming, GossiperIncoming,
             NetRequestIncoming, NetResponseIncoming, TrieDemand, TrieRequestIncoming,
@@ -53,22 +53,28 @@ use crate::{
         },
         requests::{
             BeginGossipRequest, ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest,
-            FetcherRequest, MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest,
-            NetworkRequest, NodeStateRequest, RestRequest, StorageRequest,
+            FetcherRequest, HealthRequest, MarkBlockCompletedRequest, MetricsRequest,
+            NetworkInfoRequest, NetworkRequest, NodeStateRequest, RestRequest, StateStoreRequest,
+            StorageRequest, SyncStatisticsRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
     protocol::Message,
     reactor::{
         self,
+        crash_dump::{ConnectionSummary, ReactorState},
         event_queue_metrics::EventQueueMetrics,
+        health_metrics::HealthMetrics,
         initializer,
         participating::{self, Error, ParticipatingInitConfig},
+        shutdown::{self, ShutdownStage},
+        watchdog::Liveness,
         EventQueueHandle, Finalize, ReactorExit,
     },
     types::{
         Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
         BlockSignatures, BlockWithMetadata, Deploy, ExitCode, FinalizedApprovalsWithId,
+        HealthReport,
     },
     utils::WithDir,
     NodeRng,
@@ -76,6 +82,9 @@ use crate::{
 #[cfg(test)]
 use crate::{testing::network::NetworkedReactor, types::NodeId};
 
+/// The components included in the health report, by the name they are reported under.
+const HEALTH_CHECKED_COMPONENTS: [&str; 3] = ["network", "storage", "contract_runtime"];
+
 /// Top-level event for the reactor.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, From, Serialize)]
@@ -100,6 +109,8 @@ pub(crate) enum JoinerEvent {
     #[from]
     ChainSynchronizerRequest(#[serde(skip_serializing)] NodeStateRequest),
     #[from]
+    SyncStatisticsRequest(#[serde(skip_serializing)] SyncStatisticsRequest),
+    #[from]
     NetworkInfoRequest(#[serde(skip_serializing)] NetworkInfoRequest),
     #[from]
     BlockFetcher(#[serde(skip_serializing)] fetcher::Event<Block>),
@@ -132,6 +143,8 @@ pub(crate) enum JoinerEvent {
     #[from]
     MarkBlockCompletedRequest(MarkBlockCompletedRequest),
     #[from]
+    StateStoreRequest(StateStoreRequest),
+    #[from]
     DiagnosticsPort(diagnostics_port::Event),
     #[from]
     ContractRuntime(contract_runtime::Event),
@@ -203,6 +216,10 @@ pub(crate) enum JoinerEvent {
     FinalitySignatureIncoming(FinalitySignatureIncoming),
     #[from]
     DumpConsensusStateRequest(DumpConsensusStateRequest),
+    #[from]
+    DumpMemoryRequest(DumpMemoryRequest),
+    #[from]
+    HealthRequest(#[serde(skip_serializing)] HealthRequest),
 }
 
 impl ReactorEvent for JoinerEvent {
@@ -233,6 +250,7 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::ChainspecLoader(_) => "ChainspecLoader",
             JoinerEvent::ChainspecLoaderRequest(_) => "ChainspecLoaderRequest",
             JoinerEvent::ChainSynchronizerRequest(_) => "ChainSynchronizerRequest",
+            JoinerEvent::SyncStatisticsRequest(_) => "SyncStatisticsRequest",
             JoinerEvent::NetworkInfoRequest(_) => "NetworkInfoRequest",
             JoinerEvent::BlockFetcher(_) => "BlockFetcher",
             JoinerEvent::BlockByHeightFetcher(_) => "BlockByHeightFetcher",
@@ -251,6 +269,8 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::FinalizedApprovalsFetcherRequest(_) => "FinalizedApprovalsFetcherRequest",
             JoinerEvent::TrieOrChunkFetcherRequest(_) => "TrieOrChunkFetcherRequest",
             JoinerEvent::DumpConsensusStateRequest(_) => "DumpConsensusStateRequest",
+            JoinerEvent::DumpMemoryRequest(_) => "DumpMemoryRequest",
+            JoinerEvent::HealthRequest(_) => "HealthRequest",
             JoinerEvent::ControlAnnouncement(_) => "ControlAnnouncement",
             JoinerEvent::ContractRuntimeAnnouncement(_) => "ContractRuntimeAnnouncement",
             JoinerEvent::AddressGossiperAnnouncement(_) => "AddressGossiperAnnouncement",
@@ -269,6 +289,7 @@ impl ReactorEvent for JoinerEvent {
             JoinerEvent::BlocklistAnnouncement(_) => "BlocklistAnnouncement",
             JoinerEvent::StorageRequest(_) => "StorageRequest",
             JoinerEvent::MarkBlockCompletedRequest(_) => "MarkBlockCompletedRequest",
+            JoinerEvent::StateStoreRequest(_) => "StateStoreRequest",
             JoinerEvent::BeginAddressGossipRequest(_) => "BeginAddressGossipRequest",
             JoinerEvent::ConsensusMessageIncoming(_) => "ConsensusMessageIncoming",
             JoinerEvent::DeployGossiperIncoming(_) => "DeployGossiperIncoming",
@@ -338,10 +359,14 @@ impl Display for JoinerEvent {
             JoinerEvent::ChainSynchronizerRequest(req) => {
                 write!(f, "chain synchronizer request: {}", req)
             }
+            JoinerEvent::SyncStatisticsRequest(req) => {
+                write!(f, "sync statistics request: {}", req)
+            }
             JoinerEvent::StorageRequest(req) => write!(f, "storage request: {}", req),
             JoinerEvent::MarkBlockCompletedRequest(req) => {
                 write!(f, "mark block as completed request: {}", req)
             }
+            JoinerEvent::StateStoreRequest(req) => write!(f, "state store request: {}", req),
             JoinerEvent::NetworkInfoRequest(req) => write!(f, "network info request: {}", req),
             JoinerEvent::BlockFetcherRequest(request) => {
                 write!(f, "block fetcher request: {}", request)
@@ -424,6 +449,8 @@ impl Display for JoinerEvent {
             JoinerEvent::DumpConsensusStateRequest(req) => {
                 write!(f, "consensus dump request: {}", req)
             }
+            JoinerEvent::DumpMemoryRequest(req) => write!(f, "{}", req),
+            JoinerEvent::HealthRequest(req) => write!(f, "{}", req),
             JoinerEvent::DeployGossiper(event) => write!(f, "deploy gossiper: {}", event),
             JoinerEvent::DeployGossiperAnnouncement(ann) => {
                 write!(f, "deploy gossiper announcement: {}", ann)
@@ -487,6 +514,8 @@ pub(crate) struct Reactor {
     // Attach memory metrics for the joiner.
     #[data_size(skip)] // Never allocates data on the heap.
     memory_metrics: MemoryMetrics,
+    #[data_size(skip)]
+    health_metrics: HealthMetrics,
     node_startup_instant: Instant,
     deploy_gossiper: Gossiper<Deploy, JoinerEvent>,
 }
@@ -525,6 +554,8 @@ impl reactor::Reactor for Reactor {
 
         let event_queue_metrics = EventQueueMetrics::new(registry.clone(), event_queue)?;
 
+        let health_metrics = HealthMetrics::new(registry.clone(), &HEALTH_CHECKED_COMPONENTS)?;
+
         let metrics = Metrics::new(registry.clone());
 
         let chainspec = chainspec_loader.chainspec().as_ref();
@@ -540,6 +571,7 @@ impl reactor::Reactor for Reactor {
             registry,
             small_network_identity,
             chainspec,
+            config.node.sync_mode,
         )?;
 
         let mut effects = reactor::wrap_effects(JoinerEvent::SmallNetwork, small_network_effects);
@@ -639,6 +671,7 @@ impl reactor::Reactor for Reactor {
                 rest_server,
                 event_stream_server,
                 memory_metrics,
+                health_metrics,
                 node_startup_instant,
                 diagnostics_port,
                 deploy_gossiper,
@@ -779,6 +812,10 @@ impl reactor::Reactor for Reactor {
                 JoinerEvent::Storage,
                 self.storage.handle_event(effect_builder, rng, req.into()),
             ),
+            JoinerEvent::StateStoreRequest(req) => reactor::wrap_effects(
+                JoinerEvent::Storage,
+                self.storage.handle_event(effect_builder, rng, req.into()),
+            ),
             JoinerEvent::BeginAddressGossipRequest(req) => reactor::wrap_effects(
                 JoinerEvent::AddressGossiper,
                 self.address_gossiper
@@ -896,6 +933,11 @@ impl reactor::Reactor for Reactor {
                 rng,
                 JoinerEvent::ChainSynchronizer(req.into()),
             ),
+            JoinerEvent::SyncStatisticsRequest(req) => self.dispatch_event(
+                effect_builder,
+                rng,
+                JoinerEvent::ChainSynchronizer(req.into()),
+            ),
             JoinerEvent::NetworkInfoRequest(req) => {
                 let event = JoinerEvent::SmallNetwork(small_network::Event::from(req));
                 self.dispatch_event(effect_builder, rng, event)
@@ -1003,6 +1045,12 @@ impl reactor::Reactor for Reactor {
                 req.answer(Err(Cow::Borrowed("node is joining, no running consensus")))
                     .ignore()
             }
+            JoinerEvent::DumpMemoryRequest(DumpMemoryRequest { responder }) => responder
+                .respond(memory_metrics::memory_report(self))
+                .ignore(),
+            JoinerEvent::HealthRequest(HealthRequest(responder)) => {
+                responder.respond(self.health_report()).ignore()
+            }
             JoinerEvent::DeployGossiper(event) => reactor::wrap_effects(
                 JoinerEvent::DeployGossiper,
                 self.deploy_gossiper
@@ -1050,10 +1098,81 @@ impl reactor::Reactor for Reactor {
         self.memory_metrics.estimate(self);
         self.event_queue_metrics
             .record_event_queue_counts(&event_queue_handle);
+        self.health_metrics.record(&self.health_report());
+    }
+
+    fn crash_dump_state(&self) -> ReactorState {
+        let peers = self.small_network.peers();
+        ReactorState {
+            consensus: None,
+            connections: Some(ConnectionSummary {
+                connected_peers: peers.len(),
+                peers,
+            }),
+        }
+    }
+
+    fn reload_config(
+        &mut self,
+        _effect_builder: EffectBuilder<Self::Event>,
+        config: &participating::Config,
+    ) -> Effects<Self::Event> {
+        self.config.update_reloadable(config);
+        self.deploy_gossiper.update_config(config.gossip);
+        self.address_gossiper.update_config(config.gossip);
+        reactor::wrap_effects(
+            JoinerEvent::SmallNetwork,
+            self.small_network
+                .update_known_addresses(config.network.known_addresses.clone()),
+        )
+    }
+
+    fn liveness(&self) -> Liveness {
+        Liveness {
+            next_block_height: None,
+            peers: Some(self.small_network.peers().len()),
+        }
+    }
+
+    fn begin_shutdown_stage(&mut self, stage: ShutdownStage) {
+        if stage == ShutdownStage::SyncStorage {
+            if let Err(error) = self.storage.sync() {
+                error!(%error, "failed to sync storage");
+            }
+        }
+    }
+
+    fn is_shutdown_stage_complete(
+        &self,
+        stage: ShutdownStage,
+        event_queue_handle: EventQueueHandle<Self::Event>,
+    ) -> bool {
+        match stage {
+            // The joiner neither participates in consensus nor executes blocks as they are
+            // finalized, so there is only the network to drain.
+            ShutdownStage::DrainNetwork => shutdown::is_network_drained(
+                event_queue_handle,
+                self.small_network.queued_messages(),
+            ),
+            ShutdownStage::StopProposing
+            | ShutdownStage::FlushConsensus
+            | ShutdownStage::FinishExecution
+            | ShutdownStage::SyncStorage => true,
+        }
     }
 }
 
 impl Reactor {
+    /// Checks the health of the reactor's components.
+    fn health_report(&self) -> HealthReport {
+        let [network, storage, contract_runtime] = HEALTH_CHECKED_COMPONENTS;
+        let mut report = HealthReport::default();
+        report.add(network, self.small_network.health());
+        report.add(storage, self.storage.health());
+        report.add(contract_runtime, self.contract_runtime.health());
+        report
+    }
+
     /// Deconstructs the reactor into config useful for creating a Validator reactor. Shuts down
     /// the network, closing all incoming and outgoing connections, and frees up the listening
     /// socket.
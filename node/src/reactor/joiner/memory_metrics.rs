@@ -3,7 +3,7 @@ use prometheus::{self, Histogram, HistogramOpts, IntGauge, Registry};
 use tracing::debug;
 
 use super::Reactor;
-use crate::unregister_metric;
+use crate::{effect::diagnostics_port::MemoryReport, unregister_metric};
 
 /// Metrics for estimated heap memory usage for the joiner reactor.
 #[derive(Debug)]
@@ -145,6 +145,55 @@ impl MemoryMetrics {
     }
 }
 
+/// Estimates the memory usage of each component, broken down by their major collections.
+pub(super) fn memory_report(reactor: &Reactor) -> MemoryReport {
+    let mut report = MemoryReport::default();
+    report.add_component("metrics", &reactor.metrics);
+    reactor
+        .small_network
+        .report_memory(report.add_component("small_network", &reactor.small_network));
+    reactor
+        .address_gossiper
+        .report_memory(report.add_component("address_gossiper", &reactor.address_gossiper));
+    report.add_component("config", &reactor.config);
+    report.add_component("chainspec_loader", &reactor.chainspec_loader);
+    report.add_component("storage", &reactor.storage);
+    report.add_component("contract_runtime", &reactor.contract_runtime);
+    report.add_component("chain_synchronizer", &reactor.chain_synchronizer);
+    report.add_component("deploy_fetcher", &reactor.deploy_fetcher);
+    reactor
+        .deploy_gossiper
+        .report_memory(report.add_component("deploy_gossiper", &reactor.deploy_gossiper));
+    report.add_component(
+        "finalized_approvals_fetcher",
+        &reactor.finalized_approvals_fetcher,
+    );
+    report.add_component("block_by_hash_fetcher", &reactor.block_by_hash_fetcher);
+    report.add_component("block_by_height_fetcher", &reactor.block_by_height_fetcher);
+    report.add_component(
+        "block_header_and_finality_signatures_by_height_fetcher",
+        &reactor.block_header_and_finality_signatures_by_height_fetcher,
+    );
+    report.add_component(
+        "block_and_deploys_fetcher",
+        &reactor.block_and_deploys_fetcher,
+    );
+    report.add_component("trie_or_chunk_fetcher", &reactor.trie_or_chunk_fetcher);
+    report.add_component(
+        "block_header_by_hash_fetcher",
+        &reactor.block_header_by_hash_fetcher,
+    );
+    report.add_component(
+        "block_headers_batch_fetcher",
+        &reactor.block_headers_batch_fetcher,
+    );
+    report.add_component(
+        "finality_signatures_fetcher",
+        &reactor.finality_signatures_fetcher,
+    );
+    report
+}
+
 impl Drop for MemoryMetrics {
     fn drop(&mut self) {
         unregister_metric!(self.registry, self.mem_total);
@@ -43,7 +43,7 @@ use crate::{
         rpc_server::{self, RpcServer},
         small_network::{self, GossipedAddress, SmallNetwork, SmallNetworkIdentity},
         storage::{self, Storage},
-        Component,
+        Component, HealthCheck,
     },
     contract_runtime,
     effect::{
@@ -53,7 +53,7 @@ use crate::{
             ControlAnnouncement, DeployAcceptorAnnouncement, GossiperAnnouncement,
             LinearChainAnnouncement, RpcServerAnnouncement,
         },
-        diagnostics_port::DumpConsensusStateRequest,
+        diagnostics_port::{DumpConsensusStateRequest, DumpMemoryRequest},
         incoming::{
             ConsensusMessageIncoming, FinalitySignatureIncoming, GossiperIncoming,
             NetRequestIncoming, NetResponseIncoming, TrieDemand, TrieRequestIncoming,
@@ -62,17 +62,26 @@ use crate::{
         requests::{
             BeginGossipRequest, BlockProposerRequest, BlockValidationRequest,
             ChainspecLoaderRequest, ConsensusRequest, ContractRuntimeRequest, FetcherRequest,
-            MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest, NetworkRequest,
-            NodeStateRequest, RestRequest, RpcRequest, StateStoreRequest, StorageRequest,
+            HealthRequest, MarkBlockCompletedRequest, MetricsRequest, NetworkInfoRequest,
+            NetworkRequest, NodeStateRequest, RestRequest, RpcRequest, StateStoreRequest,
+            StorageRequest, SyncStatisticsRequest,
         },
         EffectBuilder, EffectExt, Effects,
     },
     protocol::Message,
-    reactor::{self, event_queue_metrics::EventQueueMetrics, EventQueueHandle, ReactorExit},
+    reactor::{
+        self,
+        crash_dump::{ConnectionSummary, ConsensusState, ReactorState},
+        event_queue_metrics::EventQueueMetrics,
+        health_metrics::HealthMetrics,
+        shutdown::{self, ShutdownStage},
+        watchdog::Liveness,
+        EventQueueHandle, QueueKind, ReactorExit,
+    },
     types::{
         Block, BlockAndDeploys, BlockHeader, BlockHeaderWithMetadata, BlockHeadersBatch,
         BlockSignatures, BlockWithMetadata, Deploy, ExitCode, FinalitySignature,
-        FinalizedApprovalsWithId,
+        FinalizedApprovalsWithId, HealthReport,
     },
     utils::{Source, WithDir},
     NodeRng,
@@ -85,6 +94,10 @@ use memory_metrics::MemoryMetrics;
 
 const DELAY_FOR_SIGNING_IMMEDIATE_SWITCH_BLOCK: Duration = Duration::from_secs(10);
 
+/// The components included in the health report, by the name they are reported under.
+const HEALTH_CHECKED_COMPONENTS: [&str; 4] =
+    ["network", "storage", "consensus", "contract_runtime"];
+
 /// Top-level event for the reactor.
 #[derive(Debug, From, Serialize)]
 #[must_use]
@@ -148,6 +161,8 @@ pub(crate) enum ParticipatingEvent {
     #[from]
     ChainSynchronizerRequest(#[serde(skip_serializing)] NodeStateRequest),
     #[from]
+    SyncStatisticsRequest(#[serde(skip_serializing)] SyncStatisticsRequest),
+    #[from]
     ContractRuntimeRequest(ContractRuntimeRequest),
     #[from]
     NetworkRequest(#[serde(skip_serializing)] NetworkRequest<Message>),
@@ -195,6 +210,10 @@ pub(crate) enum ParticipatingEvent {
     StateStoreRequest(StateStoreRequest),
     #[from]
     DumpConsensusStateRequest(DumpConsensusStateRequest),
+    #[from]
+    DumpMemoryRequest(DumpMemoryRequest),
+    #[from]
+    HealthRequest(#[serde(skip_serializing)] HealthRequest),
 
     // Announcements
     #[from]
@@ -278,6 +297,7 @@ impl ReactorEvent for ParticipatingEvent {
             ParticipatingEvent::LinearChain(_) => "LinearChain",
             ParticipatingEvent::ContractRuntimeRequest(_) => "ContractRuntimeRequest",
             ParticipatingEvent::ChainSynchronizerRequest(_) => "ChainSynchronizerRequest",
+            ParticipatingEvent::SyncStatisticsRequest(_) => "SyncStatisticsRequest",
             ParticipatingEvent::BlockFetcher(_) => "BlockFetcher",
             ParticipatingEvent::BlockHeaderFetcher(_) => "BlockHeaderFetcher",
             ParticipatingEvent::TrieOrChunkFetcher(_) => "TrieOrChunkFetcher",
@@ -316,6 +336,8 @@ impl ReactorEvent for ParticipatingEvent {
             ParticipatingEvent::MarkBlockCompletedRequest(_) => "MarkBlockCompletedRequest",
             ParticipatingEvent::StateStoreRequest(_) => "StateStoreRequest",
             ParticipatingEvent::DumpConsensusStateRequest(_) => "DumpConsensusStateRequest",
+            ParticipatingEvent::DumpMemoryRequest(_) => "DumpMemoryRequest",
+            ParticipatingEvent::HealthRequest(_) => "HealthRequest",
             ParticipatingEvent::ControlAnnouncement(_) => "ControlAnnouncement",
             ParticipatingEvent::RpcServerAnnouncement(_) => "RpcServerAnnouncement",
             ParticipatingEvent::DeployAcceptorAnnouncement(_) => "DeployAcceptorAnnouncement",
@@ -341,6 +363,16 @@ impl ReactorEvent for ParticipatingEvent {
             ParticipatingEvent::ChainSynchronizerAnnouncement(_) => "ChainSynchronizerAnnouncement",
         }
     }
+
+    fn queue_kind(&self) -> QueueKind {
+        match self {
+            ParticipatingEvent::Consensus(_) | ParticipatingEvent::ConsensusMessageIncoming(_) => {
+                QueueKind::Consensus
+            }
+            ParticipatingEvent::ControlAnnouncement(_) => QueueKind::Control,
+            _ => QueueKind::Regular,
+        }
+    }
 }
 
 impl From<RpcRequest> for ParticipatingEvent {
@@ -433,6 +465,9 @@ impl Display for ParticipatingEvent {
             ParticipatingEvent::ChainSynchronizerRequest(req) => {
                 write!(f, "chain synchronizer request: {}", req)
             }
+            ParticipatingEvent::SyncStatisticsRequest(req) => {
+                write!(f, "sync statistics request: {}", req)
+            }
             ParticipatingEvent::NetworkRequest(req) => write!(f, "network request: {}", req),
             ParticipatingEvent::NetworkInfoRequest(req) => {
                 write!(f, "network info request: {}", req)
@@ -489,6 +524,8 @@ impl Display for ParticipatingEvent {
             ParticipatingEvent::DumpConsensusStateRequest(req) => {
                 write!(f, "dump consensus state: {}", req)
             }
+            ParticipatingEvent::DumpMemoryRequest(req) => write!(f, "{}", req),
+            ParticipatingEvent::HealthRequest(req) => write!(f, "{}", req),
             ParticipatingEvent::RpcServerAnnouncement(ann) => {
                 write!(f, "api server announcement: {}", ann)
             }
@@ -605,6 +642,21 @@ pub(crate) struct Reactor {
     memory_metrics: MemoryMetrics,
     #[data_size(skip)]
     event_queue_metrics: EventQueueMetrics,
+    #[data_size(skip)]
+    health_metrics: HealthMetrics,
+}
+
+impl Reactor {
+    /// Checks the health of the reactor's components.
+    fn health_report(&self) -> HealthReport {
+        let [network, storage, consensus, contract_runtime] = HEALTH_CHECKED_COMPONENTS;
+        let mut report = HealthReport::default();
+        report.add(network, self.small_network.health());
+        report.add(storage, self.storage.health());
+        report.add(consensus, self.consensus.health());
+        report.add(contract_runtime, self.contract_runtime.health());
+        report
+    }
 }
 
 #[cfg(test)]
@@ -709,27 +761,31 @@ impl reactor::Reactor for Reactor {
 
                 // We're responsible for signing the new block if we're in the provided list.
                 if validators_to_sign_immediate_switch_block.contains(&our_public_key) {
-                    let signature = FinalitySignature::new(
+                    match FinalitySignature::create(
                         *block.hash(),
                         current_era_id,
                         &our_secret_key,
                         our_public_key.clone(),
-                    );
-                    effects.extend(
-                        async move {
-                            effect_builder
-                                .announce_created_finality_signature(signature.clone())
-                                .await;
-                            // Allow a short period for peers to establish connections.  This delay
-                            // can be removed once we move to a single reactor model.
-                            effect_builder
-                                .set_timeout(DELAY_FOR_SIGNING_IMMEDIATE_SWITCH_BLOCK)
-                                .await;
-                            let message = Message::FinalitySignature(Box::new(signature));
-                            effect_builder.broadcast_message(message).await
+                    ) {
+                        Ok(signature) => effects.extend(
+                            async move {
+                                effect_builder
+                                    .announce_created_finality_signature(signature.clone())
+                                    .await;
+                                // Allow a short period for peers to establish connections.  This
+                                // delay can be removed once we move to a single reactor model.
+                                effect_builder
+                                    .set_timeout(DELAY_FOR_SIGNING_IMMEDIATE_SWITCH_BLOCK)
+                                    .await;
+                                let message = Message::FinalitySignature(Box::new(signature));
+                                effect_builder.broadcast_message(message).await
+                            }
+                            .ignore(),
+                        ),
+                        Err(error) => {
+                            error!(%error, "failed to sign immediate switch block");
                         }
-                        .ignore(),
-                    );
+                    }
                 }
 
                 highest_block_header
@@ -740,6 +796,8 @@ impl reactor::Reactor for Reactor {
 
         let event_queue_metrics = EventQueueMetrics::new(registry.clone(), event_queue)?;
 
+        let health_metrics = HealthMetrics::new(registry.clone(), &HEALTH_CHECKED_COMPONENTS)?;
+
         let metrics = Metrics::new(registry.clone());
 
         let (diagnostics_port, diagnostics_port_effects) = DiagnosticsPort::new(
@@ -803,6 +861,7 @@ impl reactor::Reactor for Reactor {
             registry,
             small_network_identity,
             chainspec.as_ref(),
+            config.node.sync_mode,
         )?;
 
         effects.extend(reactor::wrap_effects(
@@ -911,6 +970,7 @@ impl reactor::Reactor for Reactor {
                 diagnostics_port,
                 memory_metrics,
                 event_queue_metrics,
+                health_metrics,
             },
             effects,
         ))
@@ -1052,6 +1112,11 @@ impl reactor::Reactor for Reactor {
                 self.chain_synchronizer
                     .handle_event(effect_builder, rng, request.into()),
             ),
+            ParticipatingEvent::SyncStatisticsRequest(request) => reactor::wrap_effects(
+                ParticipatingEvent::ChainSynchronizer,
+                self.chain_synchronizer
+                    .handle_event(effect_builder, rng, request.into()),
+            ),
             ParticipatingEvent::NetworkRequest(req) => {
                 let event = ParticipatingEvent::SmallNetwork(small_network::Event::from(req));
                 self.dispatch_event(effect_builder, rng, event)
@@ -1152,6 +1217,12 @@ impl reactor::Reactor for Reactor {
                 ParticipatingEvent::Consensus,
                 self.consensus.handle_event(effect_builder, rng, req.into()),
             ),
+            ParticipatingEvent::DumpMemoryRequest(DumpMemoryRequest { responder }) => responder
+                .respond(memory_metrics::memory_report(self))
+                .ignore(),
+            ParticipatingEvent::HealthRequest(HealthRequest(responder)) => {
+                responder.respond(self.health_report()).ignore()
+            }
 
             // Announcements:
             ParticipatingEvent::ControlAnnouncement(ctrl_ann) => {
@@ -1259,6 +1330,12 @@ impl reactor::Reactor for Reactor {
                         );
                         self.dispatch_event(effect_builder, rng, reactor_event)
                     }
+                    ConsensusAnnouncement::FellBehind { .. } => {
+                        let reactor_event = ParticipatingEvent::ChainSynchronizer(
+                            chain_synchronizer::Event::CatchUp,
+                        );
+                        self.dispatch_event(effect_builder, rng, reactor_event)
+                    }
                 }
             }
             ParticipatingEvent::ContractRuntimeAnnouncement(
@@ -1488,7 +1565,41 @@ impl reactor::Reactor for Reactor {
     fn update_metrics(&mut self, event_queue_handle: EventQueueHandle<Self::Event>) {
         self.memory_metrics.estimate(self);
         self.event_queue_metrics
-            .record_event_queue_counts(&event_queue_handle)
+            .record_event_queue_counts(&event_queue_handle);
+        self.health_metrics.record(&self.health_report());
+    }
+
+    fn reload_config(
+        &mut self,
+        _effect_builder: EffectBuilder<Self::Event>,
+        config: &Config,
+    ) -> Effects<Self::Event> {
+        self.deploy_gossiper.update_config(config.gossip);
+        self.address_gossiper.update_config(config.gossip);
+        self.rpc_server
+            .update_config(&config.rpc_server, &config.speculative_exec_server);
+        reactor::wrap_effects(
+            ParticipatingEvent::SmallNetwork,
+            self.small_network
+                .update_known_addresses(config.network.known_addresses.clone()),
+        )
+    }
+
+    fn crash_dump_state(&self) -> ReactorState {
+        let mut open_eras: Vec<_> = self.consensus.open_eras().keys().copied().collect();
+        open_eras.sort();
+        let peers = self.small_network.peers();
+        ReactorState {
+            consensus: Some(ConsensusState {
+                current_era: self.consensus.current_era(),
+                open_eras,
+                next_round_length: self.consensus.next_round_length(),
+            }),
+            connections: Some(ConnectionSummary {
+                connected_peers: peers.len(),
+                peers,
+            }),
+        }
     }
 
     fn maybe_exit(&self) -> Option<ReactorExit> {
@@ -1496,6 +1607,45 @@ impl reactor::Reactor for Reactor {
             .stop_for_upgrade()
             .then(|| ReactorExit::ProcessShouldExit(ExitCode::Success))
     }
+
+    fn liveness(&self) -> Liveness {
+        Liveness {
+            next_block_height: Some(self.consensus.next_block_height()),
+            peers: Some(self.small_network.peers().len()),
+        }
+    }
+
+    fn begin_shutdown_stage(&mut self, stage: ShutdownStage) {
+        match stage {
+            ShutdownStage::StopProposing => self.consensus.stop_proposing(),
+            ShutdownStage::SyncStorage => {
+                if let Err(error) = self.storage.sync() {
+                    error!(%error, "failed to sync storage");
+                }
+            }
+            ShutdownStage::FlushConsensus
+            | ShutdownStage::DrainNetwork
+            | ShutdownStage::FinishExecution => {}
+        }
+    }
+
+    fn is_shutdown_stage_complete(
+        &self,
+        stage: ShutdownStage,
+        event_queue_handle: EventQueueHandle<Self::Event>,
+    ) -> bool {
+        match stage {
+            ShutdownStage::StopProposing | ShutdownStage::SyncStorage => true,
+            ShutdownStage::FlushConsensus => {
+                event_queue_handle.event_queue_count(QueueKind::Consensus) == 0
+            }
+            ShutdownStage::DrainNetwork => shutdown::is_network_drained(
+                event_queue_handle,
+                self.small_network.queued_messages(),
+            ),
+            ShutdownStage::FinishExecution => self.consensus.is_execution_complete(),
+        }
+    }
 }
 
 #[cfg(test)]
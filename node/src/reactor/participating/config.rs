@@ -43,3 +43,18 @@ pub(crate) struct Config {
     /// Diagnostics port configuration.
     pub(crate) diagnostics_port: DiagnosticsPortConfig,
 }
+
+impl Config {
+    /// Updates the options which can be changed without restarting the node from a reloaded
+    /// config.
+    ///
+    /// Used by reactors which hand their config on to the next reactor, so that a reload while
+    /// they are running is not lost.  The log filter is not included, since it is applied to the
+    /// global logger directly.
+    pub(crate) fn update_reloadable(&mut self, reloaded: &Config) {
+        self.gossip = reloaded.gossip;
+        self.rpc_server.qps_limit = reloaded.rpc_server.qps_limit;
+        self.speculative_exec_server.qps_limit = reloaded.speculative_exec_server.qps_limit;
+        self.network.known_addresses = reloaded.network.known_addresses.clone();
+    }
+}
@@ -33,7 +33,7 @@ use crate::{
     testing::{self, filter_reactor::FilterReactor, network::Network, ConditionCheckReactor},
     types::{
         chainspec::{AccountConfig, AccountsConfig, ValidatorConfig},
-        ActivationPoint, BlockHeader, Chainspec, ChainspecRawBytes, Deploy, ExitCode,
+        ActivationPoint, BlockHeader, Chainspec, ChainspecRawBytes, Deploy, ExitCode, SyncMode,
     },
     utils::{External, Loadable, Source, WithDir, RESOURCES_PATH},
     NodeRng,
@@ -156,6 +156,8 @@ impl TestChain {
         cfg.storage = storage_cfg;
 
         cfg.block_proposer.deploy_delay = "5sec".parse().unwrap();
+        // All nodes start at genesis, so there is no history to sync.
+        cfg.node.sync_mode = SyncMode::Recent;
 
         cfg
     }
@@ -163,10 +165,29 @@ impl TestChain {
     async fn create_initialized_network(
         &mut self,
         rng: &mut NodeRng,
+    ) -> anyhow::Result<Network<FilterReactor<participating::Reactor>>> {
+        self.add_initialized_nodes(Network::new(), rng).await
+    }
+
+    /// Like `create_initialized_network`, but the network runs on simulated time once all nodes
+    /// have joined.
+    async fn create_initialized_network_with_simulated_time(
+        &mut self,
+        rng: &mut NodeRng,
+    ) -> anyhow::Result<Network<FilterReactor<participating::Reactor>>> {
+        let mut network = self.add_initialized_nodes(Network::new(), rng).await?;
+        network.simulate_time();
+        Ok(network)
+    }
+
+    /// Initializes a node for each validator and adds it to `network`.
+    async fn add_initialized_nodes(
+        &mut self,
+        mut network: Network<FilterReactor<participating::Reactor>>,
+        rng: &mut NodeRng,
     ) -> anyhow::Result<Network<FilterReactor<participating::Reactor>>> {
         let root = RESOURCES_PATH.join("local");
 
-        let mut network: Network<FilterReactor<participating::Reactor>> = Network::new();
         let first_node_port = testing::unused_port_on_localhost();
 
         for idx in 0..self.keys.len() {
@@ -316,6 +337,72 @@ async fn run_participating_network() {
     .await;
 }
 
+#[tokio::test]
+async fn should_run_eras_and_upgrade_in_simulated_time() {
+    testing::init_logging();
+
+    let mut rng = crate::new_rng();
+
+    const NETWORK_SIZE: usize = 3;
+    let mut chain = TestChain::new(&mut rng, NETWORK_SIZE);
+
+    // The wait for genesis and the eras themselves pass in simulated time, so this doesn't take
+    // any longer than the nodes need to process their events.
+    let mut net = chain
+        .create_initialized_network_with_simulated_time(&mut rng)
+        .await
+        .expect("network initialization failed");
+
+    // An upgrade is scheduled for era 3.
+    for runner in net.runners_mut() {
+        runner
+            .process_injected_effects(|effect_builder| {
+                let upgrade = NextUpgrade::new(
+                    ActivationPoint::EraId(3.into()),
+                    ProtocolVersion::from_parts(999, 0, 0),
+                );
+                effect_builder
+                    .announce_upgrade_activation_point_read(upgrade)
+                    .ignore()
+            })
+            .await;
+    }
+
+    net.settle_on(
+        &mut rng,
+        is_in_era(EraId::from(2)),
+        Duration::from_secs(300),
+    )
+    .await;
+
+    // Run until the nodes shut down for the upgrade.
+    net.settle_on(
+        &mut rng,
+        |nodes| {
+            nodes
+                .values()
+                .all(|runner| runner.participating().maybe_exit().is_some())
+        },
+        Duration::from_secs(300),
+    )
+    .await;
+
+    // All nodes agree on the switch blocks of the eras before the upgrade, and exited for it.
+    let switch_blocks = SwitchBlocks::collect(net.nodes(), 3);
+    for header in &switch_blocks.headers {
+        assert_eq!(
+            chain.chainspec.protocol_version(),
+            header.protocol_version()
+        );
+    }
+    for runner in net.nodes().values() {
+        assert_eq!(
+            Some(ReactorExit::ProcessShouldExit(ExitCode::Success)),
+            runner.participating().maybe_exit()
+        );
+    }
+}
+
 #[tokio::test]
 async fn run_equivocator_network() {
     testing::init_logging();
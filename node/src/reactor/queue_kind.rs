@@ -3,6 +3,10 @@
 //! The reactor's event queue uses different queues to group events by priority and polls them in a
 //! round-robin manner. This way, events are only competing for time within one queue, non-congested
 //! queues can always assume to be speedily processed.
+//!
+//! Events resulting from effects are queued according to
+//! [`ReactorEvent::queue_kind`](crate::reactor::ReactorEvent::queue_kind), so each reactor decides
+//! which of its events warrant a queue of their own.
 
 use std::{fmt::Display, num::NonZeroUsize};
 
@@ -30,6 +34,11 @@ pub enum QueueKind {
     ///
     /// This is the default queue.
     Regular,
+    /// Events of the consensus component, including its timers.
+    ///
+    /// These are kept apart from other local events, so that a backlog of those cannot delay
+    /// consensus past its round deadlines.
+    Consensus,
     /// Reporting events on the local node.
     ///
     /// Metric events take precedence over most other events since missing a request for metrics
@@ -46,6 +55,7 @@ impl Display for QueueKind {
             QueueKind::NetworkDemand => "NetworkDemand",
             QueueKind::Network => "Network",
             QueueKind::Regular => "Regular",
+            QueueKind::Consensus => "Consensus",
             QueueKind::Api => "Api",
         };
         write!(f, "{}", str_value)
@@ -72,6 +82,7 @@ impl QueueKind {
             QueueKind::NetworkDemand => 2,
             QueueKind::Network => 4,
             QueueKind::Regular => 8,
+            QueueKind::Consensus => 16,
             QueueKind::Api => 16,
         })
         .expect("weight must be positive")
@@ -92,7 +103,37 @@ impl QueueKind {
             QueueKind::NetworkLowPriority => "network_low_priority",
             QueueKind::Network => "network",
             QueueKind::Regular => "regular",
+            QueueKind::Consensus => "consensus",
             QueueKind::Api => "api",
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::WeightedRoundRobin;
+
+    #[tokio::test]
+    async fn backlog_should_not_delay_consensus_events() {
+        let scheduler = WeightedRoundRobin::<usize, QueueKind>::new(QueueKind::weights());
+        for index in 0..1_000 {
+            scheduler.push(index, QueueKind::Api).await;
+            scheduler.push(index, QueueKind::NetworkIncoming).await;
+            scheduler.push(index, QueueKind::Regular).await;
+        }
+        scheduler.push(0, QueueKind::Consensus).await;
+
+        // The consensus event must be handled within a single round over all queues.
+        let round_length: usize = QueueKind::weights()
+            .iter()
+            .map(|(_, weight)| weight.get())
+            .sum();
+        for _ in 0..round_length {
+            if scheduler.pop().await.1 == QueueKind::Consensus {
+                return;
+            }
+        }
+        panic!("consensus event was not scheduled within one round");
+    }
+}
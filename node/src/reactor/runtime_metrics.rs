@@ -0,0 +1,184 @@
+#[cfg(tokio_unstable)]
+use prometheus::Gauge;
+use prometheus::{self, IntGauge, Registry};
+
+use crate::{components::contract_runtime, unregister_metric};
+
+/// Metrics for the async runtime, to tell an overloaded reactor apart from a starved runtime.
+///
+/// Most of tokio's own statistics are only available if it is compiled with `--cfg tokio_unstable`,
+/// as set in the workspace's `.cargo/config.toml`.  Without it only the task counts tracked by the
+/// node itself are exported.
+#[derive(Debug)]
+pub(super) struct RuntimeMetrics {
+    /// Number of spawned effect tasks which have not completed yet.
+    effect_tasks: IntGauge,
+    /// Number of resource intensive tasks waiting to be run on the blocking thread pool.
+    intensive_tasks_waiting: IntGauge,
+    /// Statistics reported by tokio itself.
+    #[cfg(tokio_unstable)]
+    tokio: TokioMetrics,
+    /// Instance of registry to unregister from when being dropped.
+    registry: Registry,
+}
+
+impl RuntimeMetrics {
+    /// Creates and registers the runtime metrics.
+    ///
+    /// Must be called from within the tokio runtime.
+    pub(super) fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let effect_tasks = IntGauge::new(
+            "runtime_effect_tasks",
+            "number of spawned effect tasks which have not completed yet",
+        )?;
+        let intensive_tasks_waiting = IntGauge::new(
+            "runtime_intensive_tasks_waiting",
+            "number of resource intensive tasks waiting to be run on the blocking thread pool",
+        )?;
+        registry.register(Box::new(effect_tasks.clone()))?;
+        registry.register(Box::new(intensive_tasks_waiting.clone()))?;
+
+        Ok(RuntimeMetrics {
+            effect_tasks,
+            intensive_tasks_waiting,
+            #[cfg(tokio_unstable)]
+            tokio: TokioMetrics::new(registry)?,
+            registry: registry.clone(),
+        })
+    }
+
+    /// Updates the metrics from the current state of the runtime.
+    pub(super) fn update(&self) {
+        self.effect_tasks.set(super::active_effect_tasks() as i64);
+        self.intensive_tasks_waiting
+            .set(contract_runtime::waiting_intensive_tasks() as i64);
+        #[cfg(tokio_unstable)]
+        self.tokio.update();
+    }
+}
+
+impl Drop for RuntimeMetrics {
+    fn drop(&mut self) {
+        unregister_metric!(self.registry, self.effect_tasks);
+        unregister_metric!(self.registry, self.intensive_tasks_waiting);
+        #[cfg(tokio_unstable)]
+        self.tokio.unregister(&self.registry);
+    }
+}
+
+/// The statistics tokio reports about its runtime, summed over all worker threads.
+#[cfg(tokio_unstable)]
+#[derive(Debug)]
+struct TokioMetrics {
+    runtime_metrics: tokio::runtime::RuntimeMetrics,
+    /// Number of worker threads.
+    workers: IntGauge,
+    /// Total number of tasks scheduled from outside the runtime's worker threads.
+    remote_schedules: IntGauge,
+    /// Total number of tasks scheduled by the worker threads themselves.
+    local_schedules: IntGauge,
+    /// Total number of task polls.
+    polls: IntGauge,
+    /// Total number of tasks stolen by idle worker threads from busy ones.
+    steals: IntGauge,
+    /// Total number of times worker threads parked for lack of tasks.
+    parks: IntGauge,
+    /// Total time the worker threads spent busy, in seconds.
+    busy_seconds: Gauge,
+    /// Number of tasks waiting in the runtime's global queue.
+    injection_queue_depth: IntGauge,
+    /// Number of tasks waiting in the worker threads' local queues.
+    local_queue_depth: IntGauge,
+}
+
+#[cfg(tokio_unstable)]
+impl TokioMetrics {
+    fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
+        let register = |gauge: IntGauge| -> Result<IntGauge, prometheus::Error> {
+            registry.register(Box::new(gauge.clone()))?;
+            Ok(gauge)
+        };
+        let busy_seconds = Gauge::new(
+            "runtime_busy_seconds",
+            "running total of the time the async runtime's worker threads spent busy, in seconds",
+        )?;
+        registry.register(Box::new(busy_seconds.clone()))?;
+
+        Ok(TokioMetrics {
+            runtime_metrics: tokio::runtime::Handle::current().metrics(),
+            workers: register(IntGauge::new(
+                "runtime_workers",
+                "number of worker threads of the async runtime",
+            )?)?,
+            remote_schedules: register(IntGauge::new(
+                "runtime_remote_schedule_count",
+                "running total of tasks scheduled from outside the async runtime's worker threads",
+            )?)?,
+            local_schedules: register(IntGauge::new(
+                "runtime_local_schedule_count",
+                "running total of tasks scheduled by the async runtime's worker threads",
+            )?)?,
+            polls: register(IntGauge::new(
+                "runtime_poll_count",
+                "running total of task polls by the async runtime",
+            )?)?,
+            steals: register(IntGauge::new(
+                "runtime_steal_count",
+                "running total of tasks stolen between the async runtime's worker threads",
+            )?)?,
+            parks: register(IntGauge::new(
+                "runtime_park_count",
+                "running total of times the async runtime's worker threads ran out of tasks",
+            )?)?,
+            busy_seconds,
+            injection_queue_depth: register(IntGauge::new(
+                "runtime_injection_queue_depth",
+                "number of tasks waiting in the async runtime's global queue",
+            )?)?,
+            local_queue_depth: register(IntGauge::new(
+                "runtime_local_queue_depth",
+                "number of tasks waiting in the async runtime's per worker queues",
+            )?)?,
+        })
+    }
+
+    fn update(&self) {
+        let metrics = &self.runtime_metrics;
+        let workers = metrics.num_workers();
+        let sum = |count: &dyn Fn(usize) -> u64| (0..workers).map(count).sum::<u64>() as i64;
+
+        self.workers.set(workers as i64);
+        self.remote_schedules
+            .set(metrics.remote_schedule_count() as i64);
+        self.local_schedules
+            .set(sum(&|worker| metrics.worker_local_schedule_count(worker)));
+        self.polls
+            .set(sum(&|worker| metrics.worker_poll_count(worker)));
+        self.steals
+            .set(sum(&|worker| metrics.worker_steal_count(worker)));
+        self.parks
+            .set(sum(&|worker| metrics.worker_park_count(worker)));
+        self.busy_seconds.set(
+            (0..workers)
+                .map(|worker| metrics.worker_total_busy_duration(worker).as_secs_f64())
+                .sum(),
+        );
+        self.injection_queue_depth
+            .set(metrics.injection_queue_depth() as i64);
+        self.local_queue_depth.set(sum(&|worker| {
+            metrics.worker_local_queue_depth(worker) as u64
+        }));
+    }
+
+    fn unregister(&self, registry: &Registry) {
+        unregister_metric!(registry, self.workers);
+        unregister_metric!(registry, self.remote_schedules);
+        unregister_metric!(registry, self.local_schedules);
+        unregister_metric!(registry, self.polls);
+        unregister_metric!(registry, self.steals);
+        unregister_metric!(registry, self.parks);
+        unregister_metric!(registry, self.busy_seconds);
+        unregister_metric!(registry, self.injection_queue_depth);
+        unregister_metric!(registry, self.local_queue_depth);
+    }
+}
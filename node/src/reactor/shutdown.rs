@@ -0,0 +1,121 @@
+//! Graceful shutdown.
+//!
+//! When the node is asked to terminate via `SIGINT` or `SIGTERM`, the runner does not stop
+//! dispatching events right away, as that could lose work which is only held in memory, such as a
+//! unit the node has just created but not yet sent to its peers.  Instead it runs through the
+//! [`ShutdownStage`]s in order, keeping on dispatching events until the reactor reports a stage as
+//! complete or the stage's timeout elapses.
+//!
+//! If a stage times out, the remaining stages are still run, but the process exits with
+//! [`ExitCode::ShutdownTimedOut`](crate::types::ExitCode::ShutdownTimedOut) rather than the code
+//! for the signal.  A second termination signal received during the shutdown skips all remaining
+//! stages.
+
+use std::fmt::{self, Display, Formatter};
+
+use tokio::time::Duration;
+
+use super::{EventQueueHandle, QueueKind};
+use crate::types::ShutdownConfig;
+
+/// A stage of the graceful shutdown, in the order they are run.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ShutdownStage {
+    /// Stop creating new consensus units and block proposals.
+    StopProposing,
+    /// Handle all pending consensus events, handing any resulting messages to the network.
+    FlushConsensus,
+    /// Send all messages queued for our peers.
+    DrainNetwork,
+    /// Finish executing all blocks which have been finalized.
+    FinishExecution,
+    /// Flush the storage database to disk.
+    SyncStorage,
+}
+
+impl ShutdownStage {
+    /// All stages, in the order they are run.
+    pub(crate) const ALL: [ShutdownStage; 5] = [
+        ShutdownStage::StopProposing,
+        ShutdownStage::FlushConsensus,
+        ShutdownStage::DrainNetwork,
+        ShutdownStage::FinishExecution,
+        ShutdownStage::SyncStorage,
+    ];
+
+    /// Returns how long the stage may take before the shutdown moves on to the next one.
+    ///
+    /// Stopping proposals and syncing storage complete immediately, so they have no timeout.
+    pub(crate) fn timeout(self, config: &ShutdownConfig) -> Duration {
+        match self {
+            ShutdownStage::StopProposing | ShutdownStage::SyncStorage => Duration::ZERO,
+            ShutdownStage::FlushConsensus => config.flush_consensus_timeout.into(),
+            ShutdownStage::DrainNetwork => config.drain_network_timeout.into(),
+            ShutdownStage::FinishExecution => config.finish_execution_timeout.into(),
+        }
+    }
+}
+
+impl Display for ShutdownStage {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ShutdownStage::StopProposing => write!(formatter, "stop proposing"),
+            ShutdownStage::FlushConsensus => write!(formatter, "flush consensus"),
+            ShutdownStage::DrainNetwork => write!(formatter, "drain network"),
+            ShutdownStage::FinishExecution => write!(formatter, "finish execution"),
+            ShutdownStage::SyncStorage => write!(formatter, "sync storage"),
+        }
+    }
+}
+
+/// Returns whether all outgoing messages have been sent, given the number of messages still queued
+/// by the network component.
+///
+/// Requests to send or broadcast a message are scheduled on the network queue, and events which
+/// may still result in such requests on the regular queue, so both have to be empty as well.
+pub(super) fn is_network_drained<REv>(
+    event_queue: EventQueueHandle<REv>,
+    queued_messages: i64,
+) -> bool {
+    event_queue.event_queue_count(QueueKind::Regular) == 0
+        && event_queue.event_queue_count(QueueKind::Network) == 0
+        && queued_messages == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use derive_more::From;
+
+    use super::*;
+    use crate::{
+        effect::{requests::NetworkRequest, EffectBuilder},
+        reactor::Scheduler,
+        utils,
+    };
+
+    #[derive(Debug, From)]
+    struct Event(NetworkRequest<String>);
+
+    #[tokio::test]
+    async fn should_not_be_drained_with_pending_broadcast() {
+        let scheduler = utils::leak(Scheduler::<Event>::new(QueueKind::weights()));
+        let event_queue = EventQueueHandle::without_shutdown(scheduler);
+        let effect_builder = EffectBuilder::new(event_queue);
+        assert!(is_network_drained(event_queue, 0));
+
+        // A broadcast requested just before shutting down is still waiting on the network queue.
+        let broadcast = tokio::spawn(effect_builder.broadcast_message("unit".to_string()));
+        while event_queue.event_queue_count(QueueKind::Network) == 0 {
+            tokio::task::yield_now().await;
+        }
+        assert!(!is_network_drained(event_queue, 0));
+
+        // Once the network component has taken the request, its queued messages are what count.
+        let ((_origin, Event(request)), queue_kind) = scheduler.pop().await;
+        assert_eq!(queue_kind, QueueKind::Network);
+        assert!(!is_network_drained(event_queue, 1));
+        drop(request);
+        broadcast.await.unwrap();
+        assert!(is_network_drained(event_queue, 0));
+    }
+}
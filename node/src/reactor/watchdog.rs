@@ -0,0 +1,293 @@
+//! Watchdog.
+//!
+//! While the watchdog is enabled, the runner records a few liveness signals of the running reactor:
+//! when it last dispatched an event, the height of the next block to be finalized and the number of
+//! connected peers.  A separate thread checks these periodically, so that it keeps working even
+//! if the reactor is wedged.  If a signal has not shown any progress for longer than its configured
+//! threshold, the watchdog logs the signals, writes a crash dump if crash dumps are enabled, and
+//! exits the process with an exit code specific to the failure, so that a supervisor can restart
+//! the node and keep count of why it had to.
+//!
+//! The watchdog is only enabled by the node's binary, never in tests.
+
+use std::{
+    process,
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::{Lazy, OnceCell};
+use tracing::{error, info};
+
+use super::crash_dump;
+use crate::types::{ExitCode, WatchdogConfig};
+
+/// How often the runner records the reactor's liveness, at most.
+pub(crate) const LIVENESS_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The configuration of the watchdog, set once it is started.
+static CONFIG: OnceCell<WatchdogConfig> = OnceCell::new();
+
+/// The liveness signals of the currently running reactor.
+static SIGNALS: Lazy<Mutex<Option<LivenessSignals>>> = Lazy::new(|| Mutex::new(None));
+
+/// The liveness of a reactor, as reported by the reactor itself.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Liveness {
+    /// The height of the next block to be finalized, if the reactor finalizes blocks.
+    pub(crate) next_block_height: Option<u64>,
+    /// The number of connected peers, if the reactor connects to any.
+    pub(crate) peers: Option<usize>,
+}
+
+/// The liveness signals recorded for the running reactor.
+struct LivenessSignals {
+    /// The type name of the reactor.
+    reactor: &'static str,
+    /// Returns the current number of events waiting to be dispatched.
+    queued_events: Box<dyn Fn() -> usize + Send>,
+    /// When the reactor last dispatched an event.
+    last_dispatch: Instant,
+    /// The last reported liveness.
+    liveness: Liveness,
+    /// When the next block height last increased, i.e. a block was finalized.
+    last_finalization: Instant,
+    /// When the reactor last reported having at least one peer.
+    last_connected: Instant,
+}
+
+impl LivenessSignals {
+    fn new(reactor: &'static str, queued_events: Box<dyn Fn() -> usize + Send>) -> Self {
+        let now = Instant::now();
+        LivenessSignals {
+            reactor,
+            queued_events,
+            last_dispatch: now,
+            liveness: Liveness::default(),
+            last_finalization: now,
+            last_connected: now,
+        }
+    }
+
+    fn record_liveness(&mut self, liveness: Liveness, now: Instant) {
+        if liveness.next_block_height > self.liveness.next_block_height {
+            self.last_finalization = now;
+        }
+        if liveness.peers.map_or(true, |peers| peers > 0) {
+            self.last_connected = now;
+        }
+        self.liveness = liveness;
+    }
+
+    /// Returns the exit code and a description of the failure if any signal has stalled for too
+    /// long.
+    fn check(
+        &self,
+        config: &WatchdogConfig,
+        queued_events: usize,
+        now: Instant,
+    ) -> Option<(ExitCode, String)> {
+        let stalled_for = |since: Instant| now.saturating_duration_since(since);
+
+        let events_stall = stalled_for(self.last_dispatch);
+        if queued_events > 0 && events_stall > Duration::from(config.max_event_stall) {
+            return Some((
+                ExitCode::WatchdogEventsStalled,
+                format!(
+                    "no event dispatched for {:?} with {} events queued",
+                    events_stall, queued_events
+                ),
+            ));
+        }
+
+        if let Some(next_block_height) = self.liveness.next_block_height {
+            let finalization_stall = stalled_for(self.last_finalization);
+            if finalization_stall > Duration::from(config.max_finalization_stall) {
+                return Some((
+                    ExitCode::WatchdogFinalizationStalled,
+                    format!(
+                        "no block finalized for {:?}, next block height {}",
+                        finalization_stall, next_block_height
+                    ),
+                ));
+            }
+        }
+
+        let isolation = stalled_for(self.last_connected);
+        if isolation > Duration::from(config.max_isolation) {
+            return Some((
+                ExitCode::WatchdogIsolated,
+                format!("no connected peers for {:?}", isolation),
+            ));
+        }
+
+        None
+    }
+}
+
+/// Starts the watchdog thread.  Calling this more than once has no effect.
+pub(crate) fn start(config: WatchdogConfig) {
+    if CONFIG.set(config).is_err() {
+        return;
+    }
+    let spawned = thread::Builder::new()
+        .name("watchdog".to_string())
+        .spawn(run);
+    if let Err(error) = spawned {
+        error!(%error, "failed to start watchdog thread");
+    }
+}
+
+fn is_enabled() -> bool {
+    CONFIG.get().is_some()
+}
+
+/// Starts watching a newly created reactor, replacing any previous one.
+pub(crate) fn watch_reactor<F>(reactor: &'static str, queued_events: F)
+where
+    F: Fn() -> usize + Send + 'static,
+{
+    if !is_enabled() {
+        return;
+    }
+    *lock_signals() = Some(LivenessSignals::new(reactor, Box::new(queued_events)));
+}
+
+/// Records that the reactor has dispatched an event.
+pub(crate) fn record_dispatch() {
+    if !is_enabled() {
+        return;
+    }
+    if let Some(signals) = lock_signals().as_mut() {
+        signals.last_dispatch = Instant::now();
+    }
+}
+
+/// Returns whether the reactor's liveness should be recorded, i.e. whether the watchdog runs.
+pub(crate) fn should_record_liveness() -> bool {
+    is_enabled()
+}
+
+/// Records the liveness reported by the reactor.
+pub(crate) fn record_liveness(liveness: Liveness) {
+    if let Some(signals) = lock_signals().as_mut() {
+        signals.record_liveness(liveness, Instant::now());
+    }
+}
+
+fn lock_signals() -> std::sync::MutexGuard<'static, Option<LivenessSignals>> {
+    // Recording never panics while holding the lock, but a poisoned lock is harmless anyway.
+    SIGNALS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The watchdog thread's main loop.  Only returns by exiting the process.
+fn run() {
+    let config = match CONFIG.get() {
+        Some(config) => config,
+        None => return,
+    };
+    info!(?config, "watchdog started");
+    loop {
+        thread::sleep(config.check_interval.into());
+
+        let failure = lock_signals().as_ref().and_then(|signals| {
+            let queued_events = (signals.queued_events)();
+            signals
+                .check(config, queued_events, Instant::now())
+                .map(|(exit_code, reason)| {
+                    error!(
+                        reactor = signals.reactor,
+                        queued_events,
+                        next_block_height = ?signals.liveness.next_block_height,
+                        peers = ?signals.liveness.peers,
+                        ?exit_code,
+                        %reason,
+                        "watchdog detected a stalled reactor, exiting"
+                    );
+                    (exit_code, reason)
+                })
+        });
+
+        if let Some((exit_code, reason)) = failure {
+            match crash_dump::write_recorded(&format!("watchdog: {}", reason)) {
+                Ok(Some(path)) => error!(path = %path.display(), "wrote crash dump"),
+                Ok(None) => {}
+                Err(error) => error!(%error, "failed to write crash dump"),
+            }
+            process::exit(exit_code as i32);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> WatchdogConfig {
+        WatchdogConfig {
+            enabled: true,
+            check_interval: "1sec".parse().unwrap(),
+            max_event_stall: "1min".parse().unwrap(),
+            max_finalization_stall: "10min".parse().unwrap(),
+            max_isolation: "5min".parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn should_detect_stalled_signals() {
+        let config = config();
+        let start = Instant::now();
+        let minutes = |minutes: u64| start + Duration::from_secs(minutes * 60);
+        let mut signals = LivenessSignals::new("TestReactor", Box::new(|| 0));
+        signals.last_dispatch = start;
+        signals.last_finalization = start;
+        signals.last_connected = start;
+        signals.record_liveness(
+            Liveness {
+                next_block_height: Some(10),
+                peers: Some(3),
+            },
+            start,
+        );
+
+        // An idle reactor is not stalled, only one which doesn't dispatch its queued events.
+        assert!(signals.check(&config, 0, minutes(2)).is_none());
+        let (exit_code, _) = signals.check(&config, 5, minutes(2)).unwrap();
+        assert_eq!(exit_code, ExitCode::WatchdogEventsStalled);
+
+        // Losing all peers only fails once the node has been isolated for long enough.
+        signals.last_dispatch = minutes(9);
+        signals.record_liveness(
+            Liveness {
+                next_block_height: Some(11),
+                peers: Some(2),
+            },
+            minutes(4),
+        );
+        signals.record_liveness(
+            Liveness {
+                next_block_height: Some(11),
+                peers: Some(0),
+            },
+            minutes(5),
+        );
+        assert!(signals.check(&config, 0, minutes(8)).is_none());
+        let (exit_code, _) = signals.check(&config, 0, minutes(10)).unwrap();
+        assert_eq!(exit_code, ExitCode::WatchdogIsolated);
+
+        // The block height not increasing counts as a stall, even if peers are connected.
+        signals.record_liveness(
+            Liveness {
+                next_block_height: Some(11),
+                peers: Some(2),
+            },
+            minutes(10),
+        );
+        assert!(signals.check(&config, 0, minutes(14)).is_none());
+        let (exit_code, _) = signals.check(&config, 0, minutes(15)).unwrap();
+        assert_eq!(exit_code, ExitCode::WatchdogFinalizationStalled);
+    }
+}
@@ -21,7 +21,7 @@ use crate::{
     },
     types::{
         chainspec::{AccountConfig, AccountsConfig, ValidatorConfig},
-        ActivationPoint, BlockHash, BlockHeader, Chainspec, ChainspecRawBytes, NodeId,
+        ActivationPoint, BlockHash, BlockHeader, Chainspec, ChainspecRawBytes, NodeId, SyncMode,
     },
     utils::{External, Loadable, WithDir},
     NodeRng,
@@ -150,20 +150,27 @@ impl TestChain {
             network,
         };
 
-        // Add the nodes to the chain
+        // Add the nodes to the chain.  They retain all history from genesis, so that joiners syncing
+        // to genesis can fetch it from them.
         test_chain
             .add_node(
                 true,
                 first_node_secret_key_with_stake.secret_key,
                 None,
-                false,
+                SyncMode::Archival,
                 rng,
             )
             .await;
 
         for secret_key_with_stake in other_secret_keys_with_stakes {
             test_chain
-                .add_node(false, secret_key_with_stake.secret_key, None, false, rng)
+                .add_node(
+                    false,
+                    secret_key_with_stake.secret_key,
+                    None,
+                    SyncMode::Archival,
+                    rng,
+                )
                 .await;
         }
 
@@ -176,7 +183,7 @@ impl TestChain {
         first_node: bool,
         secret_key: Arc<SecretKey>,
         trusted_hash: Option<BlockHash>,
-        sync_to_genesis: bool,
+        sync_mode: SyncMode,
         rng: &mut NodeRng,
     ) -> NodeId {
         // Set the network configuration.
@@ -192,7 +199,7 @@ impl TestChain {
             ..Default::default()
         };
 
-        participating_config.node.sync_to_genesis = sync_to_genesis;
+        participating_config.node.sync_mode = sync_mode;
 
         // Additionally set up storage in a temporary directory.
         let (storage_config, temp_dir) = storage::Config::default_for_tests();
@@ -438,7 +445,7 @@ async fn test_joiner_at_genesis() {
             false,
             joiner_node_secret_key,
             Some(trusted_hash),
-            false,
+            SyncMode::Recent,
             &mut rng,
         )
         .await;
@@ -518,7 +525,7 @@ async fn test_sync_to_genesis() {
             false,
             joiner_node_secret_key,
             Some(switch_block_hash),
-            true,
+            SyncMode::Archival,
             &mut rng,
         )
         .await;
@@ -636,7 +643,7 @@ async fn test_joiner() {
             false,
             joiner_node_secret_key,
             Some(switch_block_hash),
-            false,
+            SyncMode::Recent,
             &mut rng,
         )
         .await;
@@ -714,7 +721,7 @@ async fn test_joiner_network() {
             false,
             joiner_node_secret_key,
             Some(trusted_block_hash),
-            false,
+            SyncMode::Recent,
             &mut rng,
         )
         .await;
@@ -1,13 +1,21 @@
 //! A network of test reactors.
+//!
+//! # Determinism
+//!
+//! Nodes are always cranked in the order of their node IDs, so given the same seed for the
+//! `TestRng`, the same events are processed in the same order.  Together with the in-memory
+//! network and simulated time (see `Network::new_with_simulated_time`), this allows scenarios
+//! spanning long periods, like era transitions or network partitions, to be tested reliably: a
+//! failing run can be reproduced by rerunning it with the seed it printed.
 
 use std::{
     collections::{hash_map::Entry, HashMap},
     fmt::Debug,
-    mem,
+    mem, thread,
     time::Duration,
 };
 
-use casper_types::testing::TestRng;
+use casper_types::{testing::TestRng, TimeDiff, Timestamp};
 use fake_instant::FakeClock as Instant;
 use futures::future::{BoxFuture, FutureExt};
 use serde::Serialize;
@@ -49,6 +57,9 @@ pub(crate) trait NetworkedReactor: Sized {
 /// Time interval for which to poll an observed testing network when no events have occurred.
 const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
+/// How long to wait in real time before letting `POLL_INTERVAL` pass on simulated time.
+const SIMULATED_TIME_REAL_WAIT: Duration = Duration::from_millis(1);
+
 /// A network of multiple test reactors.
 ///
 /// Nodes themselves are not run in the background, rather manual cranking is required through
@@ -58,6 +69,9 @@ const POLL_INTERVAL: Duration = Duration::from_millis(10);
 pub(crate) struct Network<R: Reactor + NetworkedReactor> {
     /// Current network.
     nodes: HashMap<NodeId, Runner<ConditionCheckReactor<R>>>,
+    /// Whether tokio's clock is paused, i.e. time only passes while all nodes are idle, and
+    /// `Timestamp::now` follows it.
+    simulated_time: bool,
 }
 
 impl<R> Network<R>
@@ -102,9 +116,60 @@ where
     pub(crate) fn new() -> Self {
         Network {
             nodes: HashMap::new(),
+            simulated_time: false,
         }
     }
 
+    /// Creates a new network running on simulated time.
+    ///
+    /// Tokio's clock is paused, so rather than sleeping while all nodes are idle, time jumps
+    /// straight to the next timer due.  Timeouts like the `within` arguments are measured in
+    /// simulated time as well, hence hours of network activity can be run in seconds.
+    /// `Timestamp::now` follows the simulated time on the current thread until the network is
+    /// dropped, so timestamps like those of blocks advance with it.
+    ///
+    /// Time should only be moved forward using `tokio::time::advance`, since
+    /// `testing::advance_time` resumes the clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not called from within a current-thread runtime, as created by `#[tokio::test]`.
+    pub(crate) fn new_with_simulated_time() -> Self {
+        let mut network = Network::new();
+        network.simulate_time();
+        network
+    }
+
+    /// Switches the network to simulated time, see `Network::new_with_simulated_time`.
+    ///
+    /// Nodes which need to be set up on real time, e.g. by running their initializer and joiner
+    /// reactors, can be added to a network created by `Network::new` before switching it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not called from within a current-thread runtime, or if the network already runs
+    /// on simulated time.
+    pub(crate) fn simulate_time(&mut self) {
+        assert!(
+            !self.simulated_time,
+            "network already runs on simulated time"
+        );
+        time::pause();
+        let start_instant = time::Instant::now();
+        let start_timestamp = Timestamp::now();
+        Timestamp::set_mock_clock(move || {
+            start_timestamp + TimeDiff::from(start_instant.elapsed())
+        });
+        self.simulated_time = true;
+    }
+
+    /// Returns the IDs of all nodes, in the order they are cranked.
+    pub(crate) fn node_ids(&self) -> Vec<NodeId> {
+        let mut node_ids: Vec<_> = self.nodes.keys().copied().collect();
+        node_ids.sort();
+        node_ids
+    }
+
     /// Creates a new networking node on the network.
     ///
     /// # Panics
@@ -182,8 +247,7 @@ where
     async fn crank_and_check_indefinitely(&mut self, node_id: &NodeId, rng: &mut TestRng) {
         loop {
             if self.crank(node_id, rng).await == 0 {
-                Instant::advance_time(POLL_INTERVAL.as_millis() as u64);
-                time::sleep(POLL_INTERVAL).await;
+                self.wait_while_idle().await;
                 continue;
             }
 
@@ -200,11 +264,12 @@ where
         }
     }
 
-    /// Crank all runners once, returning the number of events processed.
+    /// Crank all runners once, in the order of their node IDs, returning the number of events
+    /// processed.
     pub(crate) async fn crank_all(&mut self, rng: &mut TestRng) -> usize {
         let mut event_count = 0;
-        for node in self.nodes.values_mut() {
-            let node_id = node.reactor().node_id();
+        for node_id in self.node_ids() {
+            let node = self.nodes.get_mut(&node_id).expect("should find node");
             event_count += if node
                 .try_crank(rng)
                 .instrument(error_span!("crank", node_id = %node_id))
@@ -286,12 +351,40 @@ where
 
             if self.crank_all(rng).await == 0 {
                 // No events processed, wait for a bit to avoid 100% cpu usage.
-                Instant::advance_time(POLL_INTERVAL.as_millis() as u64);
-                time::sleep(POLL_INTERVAL).await;
+                self.wait_while_idle().await;
             }
         }
     }
 
+    /// Runs the main loop of every reactor for `duration`.
+    ///
+    /// With simulated time, this processes all events which happen within `duration` of simulated
+    /// time, e.g. all rounds of consensus in that span.
+    pub(crate) async fn run_for(&mut self, rng: &mut TestRng, duration: Duration) {
+        let _ = time::timeout(duration, self.run_indefinitely(rng)).await;
+    }
+
+    async fn run_indefinitely(&mut self, rng: &mut TestRng) {
+        loop {
+            if self.crank_all(rng).await == 0 {
+                self.wait_while_idle().await;
+            }
+        }
+    }
+
+    /// Lets `POLL_INTERVAL` pass while no node has any events to process.
+    ///
+    /// On simulated time, the paused clock doesn't wait for work done outside of the runtime, such
+    /// as executing blocks on blocking threads, so a bit of real time is given to it first to
+    /// prevent the clock from racing ahead of it.
+    async fn wait_while_idle(&self) {
+        if self.simulated_time {
+            thread::sleep(SIMULATED_TIME_REAL_WAIT);
+        }
+        Instant::advance_time(POLL_INTERVAL.as_millis() as u64);
+        time::sleep(POLL_INTERVAL).await;
+    }
+
     /// Returns the internal map of nodes.
     pub(crate) fn nodes(&self) -> &HashMap<NodeId, Runner<ConditionCheckReactor<R>>> {
         &self.nodes
@@ -336,12 +429,12 @@ where
     R::Event: Serialize + Send + Sync,
     R::Error: From<prometheus::Error>,
 {
-    fn finalize(self) -> BoxFuture<'static, ()> {
+    fn finalize(mut self) -> BoxFuture<'static, ()> {
         // We support finalizing networks where the reactor itself can be finalized.
 
         async move {
             // Shutdown the sender of every reactor node to ensure the port is open again.
-            for (_, node) in self.nodes.into_iter() {
+            for (_, node) in mem::take(&mut self.nodes).into_iter() {
                 node.drain_into_inner().await.finalize().await;
             }
 
@@ -350,3 +443,11 @@ where
         .boxed()
     }
 }
+
+impl<R: Reactor + NetworkedReactor> Drop for Network<R> {
+    fn drop(&mut self) {
+        if self.simulated_time {
+            Timestamp::reset_mock_clock();
+        }
+    }
+}
@@ -7,6 +7,7 @@ pub mod chainspec;
 mod deploy;
 pub mod error;
 mod exit_code;
+mod health;
 mod item;
 pub mod json_compatibility;
 mod node_config;
@@ -14,6 +15,7 @@ mod node_id;
 /// Peers map.
 pub mod peers_map;
 mod shared_object;
+mod signing_key;
 mod status_feed;
 
 use rand::{CryptoRng, RngCore};
@@ -42,10 +44,12 @@ pub use deploy::{
 };
 pub use error::BlockValidationError;
 pub use exit_code::ExitCode;
+pub use health::{ComponentHealth, HealthReport, HealthStatus};
 pub(crate) use item::{Item, Tag};
-pub use node_config::NodeConfig;
+pub use node_config::{NodeConfig, ShutdownConfig, SyncMode, WatchdogConfig};
 pub(crate) use node_id::NodeId;
 pub use peers_map::PeersMap;
+pub(crate) use signing_key::{SigningError, SigningKey};
 pub use status_feed::{ChainspecInfo, GetStatusResult, NodeState, StatusFeed};
 
 /// An object-safe RNG trait that requires a cryptographically strong random number generator.
@@ -59,9 +59,11 @@ impl AppendableBlock {
         }
     }
 
-    /// Returns the total size of all deploys so far.
-    pub(crate) fn total_size(&self) -> usize {
-        self.total_size
+    /// Returns the number of bytes which can still be added to the block, or `None` if the block
+    /// size is unlimited.
+    pub(crate) fn remaining_size(&self) -> Option<usize> {
+        self.max_block_size()
+            .map(|max_block_size| max_block_size.saturating_sub(self.total_size))
     }
 
     /// Attempts to add a transfer to the block; returns an error if that would violate a validity
@@ -127,7 +129,10 @@ impl AppendableBlock {
         let new_total_size = self
             .total_size
             .checked_add(deploy_info.size)
-            .filter(|size| *size <= self.deploy_config.max_block_size as usize)
+            .filter(|size| {
+                self.max_block_size()
+                    .map_or(true, |max_block_size| *size <= max_block_size)
+            })
             .ok_or(AddError::BlockSize)?;
         let payment_amount = deploy_info.payment_amount;
         let gas_price = deploy_info.header.gas_price();
@@ -157,16 +162,25 @@ impl AppendableBlock {
         BlockPayload::new(deploys, transfers, accusations, random_bit)
     }
 
+    /// Returns the maximum block size in bytes, or `None` if the chainspec sets it to 0, meaning
+    /// unlimited.
+    fn max_block_size(&self) -> Option<usize> {
+        match self.deploy_config.max_block_size {
+            0 => None,
+            max_block_size => Some(max_block_size as usize),
+        }
+    }
+
     /// Returns `true` if the number of transfers is already the maximum allowed count, i.e. no
     /// more transfers can be added to this block.
     fn has_max_transfer_count(&self) -> bool {
-        self.transfers.len() == self.deploy_config.block_max_transfer_count as usize
+        self.transfers.len() >= self.deploy_config.block_max_transfer_count as usize
     }
 
     /// Returns `true` if the number of deploys is already the maximum allowed count, i.e. no more
     /// deploys can be added to this block.
     fn has_max_deploy_count(&self) -> bool {
-        self.deploys.len() == self.deploy_config.block_max_deploy_count as usize
+        self.deploys.len() >= self.deploy_config.block_max_deploy_count as usize
     }
 
     /// Returns `true` if adding the deploy with 'additional_approvals` approvals would exceed the
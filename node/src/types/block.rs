@@ -39,7 +39,7 @@ use crate::{
     types::{
         error::{BlockCreationError, BlockValidationError},
         Approval, Deploy, DeployHash, DeployOrTransferHash, DeployWithApprovals, JsonBlock,
-        JsonBlockHeader,
+        JsonBlockHeader, SigningError, SigningKey,
     },
     utils::DisplayIter,
 };
@@ -2529,9 +2529,11 @@ impl FinalitySignature {
         secret_key: &SecretKey,
         public_key: PublicKey,
     ) -> Self {
-        let mut bytes = block_hash.inner().into_vec();
-        bytes.extend_from_slice(&era_id.to_le_bytes());
-        let signature = crypto::sign(bytes, secret_key, &public_key);
+        let signature = crypto::sign(
+            Self::signed_bytes(&block_hash, era_id),
+            secret_key,
+            &public_key,
+        );
         FinalitySignature {
             block_hash,
             era_id,
@@ -2540,12 +2542,36 @@ impl FinalitySignature {
         }
     }
 
+    /// Create an instance of `FinalitySignature`, signed with a validator's signing key.
+    pub(crate) fn create(
+        block_hash: BlockHash,
+        era_id: EraId,
+        signing_key: &SigningKey,
+        public_key: PublicKey,
+    ) -> Result<Self, SigningError> {
+        let signature = signing_key.sign(Self::signed_bytes(&block_hash, era_id), &public_key)?;
+        Ok(FinalitySignature {
+            block_hash,
+            era_id,
+            signature,
+            public_key,
+        })
+    }
+
+    /// Returns the bytes covered by the signature.
+    fn signed_bytes(block_hash: &BlockHash, era_id: EraId) -> Vec<u8> {
+        let mut bytes = block_hash.inner().into_vec();
+        bytes.extend_from_slice(&era_id.to_le_bytes());
+        bytes
+    }
+
     /// Verifies whether the signature is correct.
     pub fn verify(&self) -> Result<(), crypto::Error> {
-        // NOTE: This needs to be in sync with the `new` constructor.
-        let mut bytes = self.block_hash.inner().into_vec();
-        bytes.extend_from_slice(&self.era_id.to_le_bytes());
-        crypto::verify(bytes, &self.signature, &self.public_key)
+        crypto::verify(
+            Self::signed_bytes(&self.block_hash, self.era_id),
+            &self.signature,
+            &self.public_key,
+        )
     }
 
     /// Returns a random `FinalitySignature` for the provided `block_hash` and `era_id`.
@@ -11,6 +11,7 @@ mod global_state_update;
 mod highway_config;
 mod network_config;
 mod parse_toml;
+mod pricing_config;
 mod protocol_config;
 
 use std::{fmt::Debug, path::Path};
@@ -40,7 +41,7 @@ pub(crate) use self::{
     accounts_config::AccountsConfig, activation_point::ActivationPoint,
     chainspec_raw_bytes::ChainspecRawBytes, core_config::CoreConfig, deploy_config::DeployConfig,
     global_state_update::GlobalStateUpdate, highway_config::HighwayConfig,
-    network_config::NetworkConfig, protocol_config::ProtocolConfig,
+    network_config::NetworkConfig, pricing_config::PricingConfig, protocol_config::ProtocolConfig,
 };
 use crate::utils::Loadable;
 
@@ -65,6 +66,8 @@ pub struct Chainspec {
     pub(crate) wasm_config: WasmConfig,
     #[serde(rename = "system_costs")]
     pub(crate) system_costs_config: SystemConfig,
+    #[serde(rename = "pricing")]
+    pub(crate) pricing_config: PricingConfig,
 }
 
 impl Chainspec {
@@ -90,7 +93,9 @@ impl Chainspec {
             warn!("era duration is less than minimum era height * round length!");
         }
 
-        self.protocol_config.is_valid() && self.highway_config.is_valid()
+        self.protocol_config.is_valid()
+            && self.highway_config.is_valid()
+            && self.pricing_config.is_valid()
     }
 
     /// Serializes `self` and hashes the resulting bytes.
@@ -119,6 +124,7 @@ impl Chainspec {
         let deploy_config = DeployConfig::random(rng);
         let wasm_costs_config = rng.gen();
         let system_costs_config = rng.gen();
+        let pricing_config = PricingConfig::random(rng);
 
         Chainspec {
             protocol_config,
@@ -128,6 +134,7 @@ impl Chainspec {
             deploy_config,
             wasm_config: wasm_costs_config,
             system_costs_config,
+            pricing_config,
         }
     }
 }
@@ -142,6 +149,7 @@ impl ToBytes for Chainspec {
         buffer.extend(self.deploy_config.to_bytes()?);
         buffer.extend(self.wasm_config.to_bytes()?);
         buffer.extend(self.system_costs_config.to_bytes()?);
+        buffer.extend(self.pricing_config.to_bytes()?);
         Ok(buffer)
     }
 
@@ -153,6 +161,7 @@ impl ToBytes for Chainspec {
             + self.deploy_config.serialized_length()
             + self.wasm_config.serialized_length()
             + self.system_costs_config.serialized_length()
+            + self.pricing_config.serialized_length()
     }
 }
 
@@ -165,6 +174,7 @@ impl FromBytes for Chainspec {
         let (deploy_config, remainder) = DeployConfig::from_bytes(remainder)?;
         let (wasm_config, remainder) = WasmConfig::from_bytes(remainder)?;
         let (system_costs_config, remainder) = SystemConfig::from_bytes(remainder)?;
+        let (pricing_config, remainder) = PricingConfig::from_bytes(remainder)?;
         let chainspec = Chainspec {
             protocol_config,
             network_config,
@@ -173,6 +183,7 @@ impl FromBytes for Chainspec {
             deploy_config,
             wasm_config,
             system_costs_config,
+            pricing_config,
         };
         Ok((chainspec, remainder))
     }
@@ -330,6 +341,7 @@ mod tests {
                 );
             }
             assert!(spec.protocol_config.last_emergency_restart.is_none());
+            assert_eq!(spec.pricing_config, PricingConfig::default());
         } else {
             assert_eq!(
                 spec.protocol_config.version,
@@ -348,6 +360,9 @@ mod tests {
                 spec.protocol_config.last_emergency_restart,
                 Some(EraId::new(99))
             );
+            assert_eq!(spec.pricing_config.min_gas_price, 2);
+            assert_eq!(spec.pricing_config.max_gas_price, 1000);
+            assert_eq!(spec.pricing_config.min_payment_amount, 3);
         }
 
         assert_eq!(spec.network_config.name, "test-chain");
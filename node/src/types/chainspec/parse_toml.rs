@@ -10,13 +10,16 @@ use std::{convert::TryFrom, path::Path};
 
 use serde::{Deserialize, Serialize};
 
-use casper_execution_engine::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
+use casper_execution_engine::{
+    core::engine_state::StateMigration,
+    shared::{system_config::SystemConfig, wasm_config::WasmConfig},
+};
 use casper_types::{bytesrepr::Bytes, file_utils, EraId, ProtocolVersion};
 
 use super::{
     accounts_config::AccountsConfig, global_state_update::GlobalStateUpdateConfig, ActivationPoint,
     Chainspec, ChainspecRawBytes, CoreConfig, DeployConfig, Error, GlobalStateUpdate,
-    HighwayConfig, NetworkConfig, ProtocolConfig,
+    HighwayConfig, NetworkConfig, PricingConfig, ProtocolConfig,
 };
 
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -36,6 +39,8 @@ struct TomlProtocol {
     activation_point: ActivationPoint,
     last_emergency_restart: Option<EraId>,
     verifiable_chunked_hash_activation: EraId,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    state_migrations: Vec<StateMigration>,
 }
 
 /// A chainspec configuration as laid out in the TOML-encoded configuration file.
@@ -50,6 +55,8 @@ pub(super) struct TomlChainspec {
     highway: HighwayConfig,
     wasm: WasmConfig,
     system_costs: SystemConfig,
+    #[serde(default)]
+    pricing: PricingConfig,
 }
 
 impl From<&Chainspec> for TomlChainspec {
@@ -62,6 +69,7 @@ impl From<&Chainspec> for TomlChainspec {
             verifiable_chunked_hash_activation: chainspec
                 .protocol_config
                 .verifiable_chunked_hash_activation,
+            state_migrations: chainspec.protocol_config.state_migrations.clone(),
         };
         let network = TomlNetwork {
             name: chainspec.network_config.name.clone(),
@@ -72,6 +80,7 @@ impl From<&Chainspec> for TomlChainspec {
         let highway = chainspec.highway_config;
         let wasm = chainspec.wasm_config;
         let system_costs = chainspec.system_costs_config;
+        let pricing = chainspec.pricing_config;
 
         TomlChainspec {
             protocol,
@@ -81,6 +90,7 @@ impl From<&Chainspec> for TomlChainspec {
             highway,
             wasm,
             system_costs,
+            pricing,
         }
     }
 }
@@ -122,6 +132,7 @@ pub(super) fn parse_toml<P: AsRef<Path>>(
         verifiable_chunked_hash_activation: toml_chainspec
             .protocol
             .verifiable_chunked_hash_activation,
+        state_migrations: toml_chainspec.protocol.state_migrations,
     };
 
     let chainspec = Chainspec {
@@ -132,6 +143,7 @@ pub(super) fn parse_toml<P: AsRef<Path>>(
         highway_config: toml_chainspec.highway,
         wasm_config: toml_chainspec.wasm,
         system_costs_config: toml_chainspec.system_costs,
+        pricing_config: toml_chainspec.pricing,
     };
     let chainspec_raw_bytes = ChainspecRawBytes::new(
         Bytes::from(chainspec_bytes),
@@ -0,0 +1,193 @@
+use datasize::DataSize;
+#[cfg(test)]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+#[cfg(test)]
+use casper_types::testing::TestRng;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    U512,
+};
+
+use crate::types::DeployConfigurationFailure;
+
+/// Fee and gas pricing rules for deploys.
+///
+/// Like the rest of the chainspec these may be changed at any protocol upgrade.  Chainspecs
+/// without a `[pricing]` section use the defaults, which impose no restrictions beyond requiring a
+/// non-zero gas price.
+#[derive(Copy, Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct PricingConfig {
+    /// The lowest gas price a deploy may offer.
+    pub(crate) min_gas_price: u64,
+    /// The highest gas price a deploy may offer, or 0 for no upper limit.
+    pub(crate) max_gas_price: u64,
+    /// The minimum payment amount in motes for deploys other than native transfers.
+    pub(crate) min_payment_amount: u64,
+}
+
+impl PricingConfig {
+    /// Checks a deploy's gas price and, unless it is a native transfer, its payment amount.
+    ///
+    /// `payment_amount` should be `None` for native transfers, which have a fixed cost.
+    pub(crate) fn check(
+        &self,
+        gas_price: u64,
+        payment_amount: Option<U512>,
+    ) -> Result<(), DeployConfigurationFailure> {
+        if gas_price < self.min_gas_price {
+            return Err(DeployConfigurationFailure::GasPriceTooLow {
+                min_gas_price: self.min_gas_price,
+                got: gas_price,
+            });
+        }
+        if self.max_gas_price != 0 && gas_price > self.max_gas_price {
+            return Err(DeployConfigurationFailure::GasPriceTooHigh {
+                max_gas_price: self.max_gas_price,
+                got: gas_price,
+            });
+        }
+        if let Some(payment_amount) = payment_amount {
+            let minimum = U512::from(self.min_payment_amount);
+            if payment_amount < minimum {
+                return Err(DeployConfigurationFailure::InsufficientPaymentAmount {
+                    minimum,
+                    attempted: payment_amount,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `false` and logs errors if the values set in the config don't make sense.
+    pub(super) fn is_valid(&self) -> bool {
+        if self.min_gas_price == 0 {
+            error!("minimum gas price must be at least 1");
+            return false;
+        }
+        if self.max_gas_price != 0 && self.max_gas_price < self.min_gas_price {
+            error!(
+                min = %self.min_gas_price,
+                max = %self.max_gas_price,
+                "minimum gas price is greater than the maximum gas price",
+            );
+            return false;
+        }
+        true
+    }
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        PricingConfig {
+            min_gas_price: 1,
+            max_gas_price: 0,
+            min_payment_amount: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+impl PricingConfig {
+    /// Generates a random instance using a `TestRng`.
+    pub fn random(rng: &mut TestRng) -> Self {
+        let min_gas_price = rng.gen_range(1..10);
+        let max_gas_price = rng.gen_range(min_gas_price..1_000);
+        let min_payment_amount = rng.gen_range(0..1_000_000_000);
+
+        PricingConfig {
+            min_gas_price,
+            max_gas_price,
+            min_payment_amount,
+        }
+    }
+}
+
+impl ToBytes for PricingConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.min_gas_price.to_bytes()?);
+        buffer.extend(self.max_gas_price.to_bytes()?);
+        buffer.extend(self.min_payment_amount.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.min_gas_price.serialized_length()
+            + self.max_gas_price.serialized_length()
+            + self.min_payment_amount.serialized_length()
+    }
+}
+
+impl FromBytes for PricingConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (min_gas_price, remainder) = u64::from_bytes(bytes)?;
+        let (max_gas_price, remainder) = u64::from_bytes(remainder)?;
+        let (min_payment_amount, remainder) = u64::from_bytes(remainder)?;
+        let config = PricingConfig {
+            min_gas_price,
+            max_gas_price,
+            min_payment_amount,
+        };
+        Ok((config, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = PricingConfig::random(&mut rng);
+        bytesrepr::test_serialization_roundtrip(&config);
+    }
+
+    #[test]
+    fn toml_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = PricingConfig::random(&mut rng);
+        let encoded = toml::to_string_pretty(&config).unwrap();
+        let decoded = toml::from_str(&encoded).unwrap();
+        assert_eq!(config, decoded);
+    }
+
+    #[test]
+    fn should_check_gas_price_and_payment_amount() {
+        let config = PricingConfig {
+            min_gas_price: 2,
+            max_gas_price: 10,
+            min_payment_amount: 1_000,
+        };
+        let payment = Some(U512::from(1_000));
+
+        assert!(config.check(2, payment).is_ok());
+        assert!(config.check(10, payment).is_ok());
+        assert!(matches!(
+            config.check(1, payment),
+            Err(DeployConfigurationFailure::GasPriceTooLow { .. })
+        ));
+        assert!(matches!(
+            config.check(11, payment),
+            Err(DeployConfigurationFailure::GasPriceTooHigh { .. })
+        ));
+        assert!(matches!(
+            config.check(2, Some(U512::from(999))),
+            Err(DeployConfigurationFailure::InsufficientPaymentAmount { .. })
+        ));
+        // Native transfers are not subject to the payment minimum.
+        assert!(config.check(2, None).is_ok());
+
+        // A maximum of 0 means there is no upper limit.
+        let unlimited = PricingConfig {
+            max_gas_price: 0,
+            ..config
+        };
+        assert!(unlimited.check(u64::MAX, payment).is_ok());
+    }
+}
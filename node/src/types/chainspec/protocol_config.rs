@@ -9,6 +9,7 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
+use casper_execution_engine::core::engine_state::StateMigration;
 #[cfg(test)]
 use casper_types::testing::TestRng;
 use casper_types::{
@@ -35,6 +36,9 @@ pub struct ProtocolConfig {
     pub(crate) last_emergency_restart: Option<EraId>,
     /// The era ID starting at which the new Merkle tree-based hashing scheme is applied.
     pub(crate) verifiable_chunked_hash_activation: EraId,
+    /// The migrations of global state to run, in order, at the start of the era specified in the
+    /// activation point.
+    pub(crate) state_migrations: Vec<StateMigration>,
 }
 
 impl ProtocolConfig {
@@ -79,6 +83,16 @@ impl ProtocolConfig {
             };
         }
 
+        // State migrations can only be run as part of an upgrade.
+        if !self.state_migrations.is_empty() && self.activation_point.genesis_timestamp().is_some()
+        {
+            error!(
+                state_migrations = ?self.state_migrations,
+                "[protocol.state_migrations] must be empty in a genesis chainspec."
+            );
+            return false;
+        }
+
         true
     }
 
@@ -93,6 +107,11 @@ impl ProtocolConfig {
         let activation_point = ActivationPoint::random(rng);
         let last_emergency_restart = rng.gen::<bool>().then(|| rng.gen());
         let verifiable_chunked_hash_activation = EraId::from(rng.gen_range(0..5));
+        let state_migrations = if activation_point.genesis_timestamp().is_none() && rng.gen() {
+            vec![StateMigration::WithdrawsToUnbonds]
+        } else {
+            vec![]
+        };
 
         ProtocolConfig {
             version: protocol_version,
@@ -101,6 +120,7 @@ impl ProtocolConfig {
             global_state_update: None,
             last_emergency_restart,
             verifiable_chunked_hash_activation,
+            state_migrations,
         }
     }
 }
@@ -114,6 +134,7 @@ impl ToBytes for ProtocolConfig {
         buffer.extend(self.global_state_update.to_bytes()?);
         buffer.extend(self.last_emergency_restart.to_bytes()?);
         buffer.extend(self.verifiable_chunked_hash_activation.to_bytes()?);
+        buffer.extend(self.state_migrations.to_bytes()?);
         Ok(buffer)
     }
 
@@ -124,6 +145,7 @@ impl ToBytes for ProtocolConfig {
             + self.global_state_update.serialized_length()
             + self.last_emergency_restart.serialized_length()
             + self.verifiable_chunked_hash_activation.serialized_length()
+            + self.state_migrations.serialized_length()
     }
 }
 
@@ -137,6 +159,7 @@ impl FromBytes for ProtocolConfig {
         let (global_state_update, remainder) = Option::<GlobalStateUpdate>::from_bytes(remainder)?;
         let (last_emergency_restart, remainder) = Option::<EraId>::from_bytes(remainder)?;
         let (verifiable_chunked_hash_activation, remainder) = EraId::from_bytes(remainder)?;
+        let (state_migrations, remainder) = Vec::<StateMigration>::from_bytes(remainder)?;
         let protocol_config = ProtocolConfig {
             version,
             hard_reset,
@@ -144,6 +167,7 @@ impl FromBytes for ProtocolConfig {
             global_state_update,
             last_emergency_restart,
             verifiable_chunked_hash_activation,
+            state_migrations,
         };
         Ok((protocol_config, remainder))
     }
@@ -151,6 +175,8 @@ impl FromBytes for ProtocolConfig {
 
 #[cfg(test)]
 mod tests {
+    use casper_types::Timestamp;
+
     use crate::types::Block;
 
     use super::*;
@@ -216,6 +242,20 @@ mod tests {
         assert!(!protocol_config.is_valid());
     }
 
+    #[test]
+    fn should_reject_state_migrations_at_genesis() {
+        let mut rng = crate::new_rng();
+        let mut protocol_config = ProtocolConfig::random(&mut rng);
+        protocol_config.last_emergency_restart = None;
+        protocol_config.state_migrations = vec![StateMigration::WithdrawsToUnbonds];
+
+        protocol_config.activation_point = ActivationPoint::EraId(EraId::new(1));
+        assert!(protocol_config.is_valid());
+
+        protocol_config.activation_point = ActivationPoint::Genesis(Timestamp::now());
+        assert!(!protocol_config.is_valid());
+    }
+
     #[test]
     fn should_recognize_blocks_before_activation_point() {
         let past_version = ProtocolVersion::from_parts(1, 0, 0);
@@ -235,6 +275,7 @@ mod tests {
             global_state_update: None,
             last_emergency_restart: None,
             verifiable_chunked_hash_activation,
+            state_migrations: vec![],
         };
 
         // The block before this protocol version: a switch block with previous era and version.
@@ -44,7 +44,7 @@ use super::{BlockHash, BlockHashAndHeight, Item, Tag};
 use crate::{
     components::block_proposer::DeployInfo,
     rpcs::docs::DocExample,
-    types::chainspec::DeployConfig,
+    types::chainspec::{DeployConfig, PricingConfig},
     utils::{ds, DisplayIter},
 };
 
@@ -206,6 +206,33 @@ pub enum DeployConfigurationFailure {
         /// The chainspec limit for max_associated_keys.
         max_associated_keys: u32,
     },
+
+    /// The gas price is below the chainspec minimum.
+    #[error("gas price of {got} is below the minimum of {min_gas_price}")]
+    GasPriceTooLow {
+        /// The chainspec minimum gas price.
+        min_gas_price: u64,
+        /// The gas price of the deploy.
+        got: u64,
+    },
+
+    /// The gas price exceeds the chainspec maximum.
+    #[error("gas price of {got} exceeds the maximum of {max_gas_price}")]
+    GasPriceTooHigh {
+        /// The chainspec maximum gas price.
+        max_gas_price: u64,
+        /// The gas price of the deploy.
+        got: u64,
+    },
+
+    /// Insufficient payment amount.
+    #[error("insufficient payment amount; minimum: {minimum} attempted: {attempted}")]
+    InsufficientPaymentAmount {
+        /// The minimum payment amount.
+        minimum: U512,
+        /// The attempted payment amount.
+        attempted: U512,
+    },
 }
 
 /// Error returned when a Deploy is too large.
@@ -1105,6 +1132,34 @@ impl Deploy {
 
         Ok(())
     }
+
+    /// Returns `Ok` if the gas price and, unless this is a native transfer, the payment amount
+    /// comply with the chainspec's pricing rules.
+    pub(crate) fn is_pricing_compliant(
+        &self,
+        pricing_config: &PricingConfig,
+    ) -> Result<(), DeployConfigurationFailure> {
+        let payment_amount = if self.session().is_transfer() {
+            None
+        } else {
+            let value = self
+                .payment()
+                .args()
+                .get(ARG_AMOUNT)
+                .ok_or(DeployConfigurationFailure::MissingPaymentAmount)?;
+            let payment_amount = value
+                .clone()
+                .into_t::<U512>()
+                .map_err(|_| DeployConfigurationFailure::FailedToParsePaymentAmount)?;
+            Some(payment_amount)
+        };
+        pricing_config
+            .check(self.header().gas_price(), payment_amount)
+            .map_err(|error| {
+                info!(deploy_hash = %self.id(), %error, "deploy does not comply with pricing");
+                error
+            })
+    }
 }
 
 /// A deploy combined with a potential set of finalized approvals.
@@ -2295,6 +2350,50 @@ mod tests {
         )
     }
 
+    #[test]
+    fn not_acceptable_due_to_pricing() {
+        let mut rng = crate::new_rng();
+        let deploy_config = DeployConfig::default();
+        let mut deploy = create_deploy(
+            &mut rng,
+            deploy_config.max_ttl,
+            deploy_config.max_dependencies as usize,
+            "net-1",
+        );
+        let pricing_config = PricingConfig {
+            min_gas_price: 2,
+            max_gas_price: 0,
+            min_payment_amount: 10,
+        };
+
+        assert_eq!(
+            Err(DeployConfigurationFailure::GasPriceTooLow {
+                min_gas_price: 2,
+                got: 1,
+            }),
+            deploy.is_pricing_compliant(&pricing_config)
+        );
+
+        // The payment minimum applies to deploys other than native transfers.
+        deploy.header.gas_price = 2;
+        assert_eq!(Ok(()), deploy.is_pricing_compliant(&pricing_config));
+        deploy.session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: RuntimeArgs::new(),
+        };
+        deploy.payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! { ARG_AMOUNT => U512::from(9) },
+        };
+        assert_eq!(
+            Err(DeployConfigurationFailure::InsufficientPaymentAmount {
+                minimum: U512::from(10),
+                attempted: U512::from(9),
+            }),
+            deploy.is_pricing_compliant(&pricing_config)
+        );
+    }
+
     #[test]
     fn not_acceptable_due_to_insufficient_transfer_amount() {
         let mut rng = crate::new_rng();
@@ -23,6 +23,19 @@ pub enum ExitCode {
     /// It is no longer used, but we keep it here to avoid it being reassigned to other features.
     #[doc(hidden)]
     DowngradeVersion = 102,
+    /// The process should exit with `103`.  A graceful shutdown was requested via a termination
+    /// signal, but at least one of its stages timed out, so some in-flight work may have been lost.
+    /// The launcher should not restart the node.
+    ShutdownTimedOut = 103,
+    /// The process should exit with `104`.  The watchdog found events waiting to be dispatched,
+    /// but none had been for too long.  The launcher should restart the node.
+    WatchdogEventsStalled = 104,
+    /// The process should exit with `105`.  The watchdog found that no block had been finalized
+    /// for too long.  The launcher should restart the node.
+    WatchdogFinalizationStalled = 105,
+    /// The process should exit with `106`.  The watchdog found that the node had no connected
+    /// peers for too long.  The launcher should restart the node.
+    WatchdogIsolated = 106,
     /// The exit code Rust uses by default when interrupted via an `INT` signal.
     SigInt = SIGNAL_OFFSET + SIGINT as u8,
     /// The exit code Rust uses by default when interrupted via a `QUIT` signal.
@@ -0,0 +1,138 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use std::collections::BTreeMap;
+
+use datasize::DataSize;
+use once_cell::sync::Lazy;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::components::rpc_server::rpcs::docs::DocExample;
+
+static HEALTH_REPORT: Lazy<HealthReport> = Lazy::new(|| {
+    let mut report = HealthReport::default();
+    for component in &["network", "storage", "consensus", "contract_runtime"] {
+        report.add(component, ComponentHealth::healthy());
+    }
+    report
+});
+
+/// The health of a component or of the node as a whole, ordered from best to worst.
+#[derive(
+    Copy, Clone, PartialEq, Eq, PartialOrd, Ord, DataSize, Debug, Deserialize, Serialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// Operating normally.
+    Healthy,
+    /// Operating, but impaired in a way which may need attention.
+    Degraded,
+    /// Not operating correctly.
+    Failed,
+}
+
+impl HealthStatus {
+    /// Returns the status as a number for use in metrics: 0 healthy, 1 degraded or 2 failed.
+    pub(crate) fn as_metric(self) -> i64 {
+        match self {
+            HealthStatus::Healthy => 0,
+            HealthStatus::Degraded => 1,
+            HealthStatus::Failed => 2,
+        }
+    }
+}
+
+/// The health of a single component.
+#[derive(Clone, PartialEq, Eq, DataSize, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentHealth {
+    /// The component's health status.
+    pub status: HealthStatus,
+    /// Why the component is not healthy, if it isn't.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ComponentHealth {
+    /// A healthy component.
+    pub(crate) fn healthy() -> Self {
+        ComponentHealth {
+            status: HealthStatus::Healthy,
+            reason: None,
+        }
+    }
+
+    /// A component which is operating, but impaired.
+    pub(crate) fn degraded<R: ToString>(reason: R) -> Self {
+        ComponentHealth {
+            status: HealthStatus::Degraded,
+            reason: Some(reason.to_string()),
+        }
+    }
+
+    /// A component which is not operating correctly.
+    pub(crate) fn failed<R: ToString>(reason: R) -> Self {
+        ComponentHealth {
+            status: HealthStatus::Failed,
+            reason: Some(reason.to_string()),
+        }
+    }
+}
+
+/// The health of the node's components and of the node as a whole.
+#[derive(Clone, PartialEq, Eq, DataSize, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HealthReport {
+    /// The overall health of the node, i.e. that of its least healthy component.
+    pub status: HealthStatus,
+    /// The health of each component, by component name.
+    pub components: BTreeMap<String, ComponentHealth>,
+}
+
+impl Default for HealthReport {
+    fn default() -> Self {
+        HealthReport {
+            status: HealthStatus::Healthy,
+            components: BTreeMap::new(),
+        }
+    }
+}
+
+impl DocExample for HealthReport {
+    fn doc_example() -> &'static Self {
+        &*HEALTH_REPORT
+    }
+}
+
+impl HealthReport {
+    /// Adds the health of the named component, updating the overall status.
+    pub(crate) fn add(&mut self, component: &str, health: ComponentHealth) {
+        self.status = self.status.max(health.status);
+        self.components.insert(component.to_string(), health);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overall_status_should_be_the_worst_component_status() {
+        let mut report = HealthReport::default();
+        assert_eq!(report.status, HealthStatus::Healthy);
+
+        report.add("network", ComponentHealth::healthy());
+        assert_eq!(report.status, HealthStatus::Healthy);
+
+        report.add("storage", ComponentHealth::failed("disk full"));
+        report.add("consensus", ComponentHealth::degraded("paused"));
+        assert_eq!(report.status, HealthStatus::Failed);
+        assert_eq!(report.components.len(), 3);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["components"]["storage"]["reason"], "disk full");
+        assert!(json["components"]["network"].get("reason").is_none());
+    }
+}
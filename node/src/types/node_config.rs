@@ -1,3 +1,8 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+};
+
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
@@ -12,6 +17,14 @@ const DEFAULT_MAX_PARALLEL_TRIE_FETCHES: u32 = 5000;
 const DEFAULT_MAX_PARALLEL_BLOCK_FETCHES: u32 = 50;
 const DEFAULT_PEER_REDEMPTION_INTERVAL: u32 = 10_000;
 const DEFAULT_RETRY_INTERVAL: &str = "100ms";
+const DEFAULT_SLOW_EVENT_THRESHOLD: &str = "1sec";
+const DEFAULT_FLUSH_CONSENSUS_TIMEOUT: &str = "5sec";
+const DEFAULT_DRAIN_NETWORK_TIMEOUT: &str = "5sec";
+const DEFAULT_FINISH_EXECUTION_TIMEOUT: &str = "30sec";
+const DEFAULT_WATCHDOG_CHECK_INTERVAL: &str = "10sec";
+const DEFAULT_MAX_EVENT_STALL: &str = "5min";
+const DEFAULT_MAX_FINALIZATION_STALL: &str = "30min";
+const DEFAULT_MAX_ISOLATION: &str = "10min";
 
 /// Node fast-sync configuration.
 #[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
@@ -36,9 +49,27 @@ pub struct NodeConfig {
     /// How many items to fetch before redeeming a random peer.
     pub sync_peer_redemption_interval: u32,
 
-    /// Whether to run in sync-to-genesis mode which captures all data (blocks, deploys
-    /// and global state) back to genesis.
-    pub sync_to_genesis: bool,
+    /// Which history and global state the node retains, and hence syncs and serves to peers.
+    pub sync_mode: SyncMode,
+
+    /// The directory containing the chainspec.  Relative paths are resolved from the config
+    /// file's directory, which is also the default.
+    pub chainspec_dir: Option<PathBuf>,
+
+    /// The directory to write crash dumps to if the node panics or exits due to a fatal error.
+    /// Relative paths are resolved from the config file's directory.  If unset, no crash dumps
+    /// are written.
+    pub crash_dump_dir: Option<PathBuf>,
+
+    /// Handling a single event taking longer than this is logged as a warning, and with debug
+    /// logging enabled, the event itself is logged as well.
+    pub slow_event_threshold: TimeDiff,
+
+    /// Timeouts for the stages of a graceful shutdown.
+    pub shutdown: ShutdownConfig,
+
+    /// Thresholds for the watchdog exiting the node if it appears to be stalled.
+    pub watchdog: WatchdogConfig,
 }
 
 impl Default for NodeConfig {
@@ -50,7 +81,95 @@ impl Default for NodeConfig {
             max_parallel_trie_fetches: DEFAULT_MAX_PARALLEL_TRIE_FETCHES,
             retry_interval: DEFAULT_RETRY_INTERVAL.parse().unwrap(),
             sync_peer_redemption_interval: DEFAULT_PEER_REDEMPTION_INTERVAL,
-            sync_to_genesis: false,
+            sync_mode: SyncMode::Archival,
+            chainspec_dir: None,
+            crash_dump_dir: None,
+            slow_event_threshold: DEFAULT_SLOW_EVENT_THRESHOLD.parse().unwrap(),
+            shutdown: ShutdownConfig::default(),
+            watchdog: WatchdogConfig::default(),
+        }
+    }
+}
+
+/// The history and global state retained by the node.
+#[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMode {
+    /// All blocks, deploys and global state back to genesis are synced and kept.
+    Archival,
+    /// Only blocks from the trusted block onwards are synced, and global state is pruned to that
+    /// of the most recent blocks.
+    Recent,
+}
+
+impl SyncMode {
+    /// Returns whether all history back to genesis is synced and retained.
+    pub fn is_archival(&self) -> bool {
+        *self == SyncMode::Archival
+    }
+}
+
+impl Display for SyncMode {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncMode::Archival => write!(formatter, "archival"),
+            SyncMode::Recent => write!(formatter, "recent"),
+        }
+    }
+}
+
+/// Graceful shutdown configuration.
+#[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShutdownConfig {
+    /// How long to wait for pending consensus events to be handled.
+    pub flush_consensus_timeout: TimeDiff,
+
+    /// How long to wait for queued outgoing messages to be sent.
+    pub drain_network_timeout: TimeDiff,
+
+    /// How long to wait for finalized blocks to be executed.
+    pub finish_execution_timeout: TimeDiff,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> ShutdownConfig {
+        ShutdownConfig {
+            flush_consensus_timeout: DEFAULT_FLUSH_CONSENSUS_TIMEOUT.parse().unwrap(),
+            drain_network_timeout: DEFAULT_DRAIN_NETWORK_TIMEOUT.parse().unwrap(),
+            finish_execution_timeout: DEFAULT_FINISH_EXECUTION_TIMEOUT.parse().unwrap(),
+        }
+    }
+}
+
+/// Watchdog configuration.
+#[derive(DataSize, Debug, Deserialize, Serialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatchdogConfig {
+    /// Whether the watchdog is enabled.
+    pub enabled: bool,
+
+    /// How often the watchdog checks the node's liveness.
+    pub check_interval: TimeDiff,
+
+    /// How long events may be waiting without any being dispatched.
+    pub max_event_stall: TimeDiff,
+
+    /// How long the node may go without a block being finalized.
+    pub max_finalization_stall: TimeDiff,
+
+    /// How long the node may go without any connected peers.
+    pub max_isolation: TimeDiff,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> WatchdogConfig {
+        WatchdogConfig {
+            enabled: false,
+            check_interval: DEFAULT_WATCHDOG_CHECK_INTERVAL.parse().unwrap(),
+            max_event_stall: DEFAULT_MAX_EVENT_STALL.parse().unwrap(),
+            max_finalization_stall: DEFAULT_MAX_FINALIZATION_STALL.parse().unwrap(),
+            max_isolation: DEFAULT_MAX_ISOLATION.parse().unwrap(),
         }
     }
 }
@@ -0,0 +1,74 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use datasize::DataSize;
+
+use casper_types::{crypto, PublicKey, SecretKey, Signature};
+
+#[cfg(feature = "pkcs11")]
+use crate::pkcs11::{self, Pkcs11Key};
+
+/// The error signing with a [`SigningKey`] can fail with.
+#[cfg(feature = "pkcs11")]
+pub(crate) type SigningError = pkcs11::Error;
+
+/// The error signing with a [`SigningKey`] can fail with.
+///
+/// Without PKCS#11 support, all keys are held in memory and signing cannot fail.
+#[cfg(not(feature = "pkcs11"))]
+pub(crate) type SigningError = std::convert::Infallible;
+
+/// The secret key a validator signs with.
+#[derive(Clone, DataSize)]
+pub(crate) enum SigningKey {
+    /// A secret key held in memory, loaded from a file.
+    InMemory(Arc<SecretKey>),
+    /// A key held in a PKCS#11 token, which performs all signing.
+    #[cfg(feature = "pkcs11")]
+    Pkcs11(Arc<Pkcs11Key>),
+}
+
+impl SigningKey {
+    /// Returns the public key corresponding to this signing key.
+    pub(crate) fn public_key(&self) -> PublicKey {
+        match self {
+            SigningKey::InMemory(secret_key) => PublicKey::from(secret_key.as_ref()),
+            #[cfg(feature = "pkcs11")]
+            SigningKey::Pkcs11(key) => key.public_key().clone(),
+        }
+    }
+
+    /// Signs the given message.
+    ///
+    /// Fails only if the key is held in a PKCS#11 token which fails to produce a valid signature,
+    /// e.g. because the device or session is temporarily unavailable.
+    pub(crate) fn sign<T: AsRef<[u8]>>(
+        &self,
+        message: T,
+        public_key: &PublicKey,
+    ) -> Result<Signature, SigningError> {
+        match self {
+            SigningKey::InMemory(secret_key) => Ok(crypto::sign(message, secret_key, public_key)),
+            #[cfg(feature = "pkcs11")]
+            SigningKey::Pkcs11(key) => key.sign(message.as_ref()),
+        }
+    }
+}
+
+impl From<Arc<SecretKey>> for SigningKey {
+    fn from(secret_key: Arc<SecretKey>) -> Self {
+        SigningKey::InMemory(secret_key)
+    }
+}
+
+impl Debug for SigningKey {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SigningKey::InMemory(_) => formatter.write_str("SigningKey::InMemory(..)"),
+            #[cfg(feature = "pkcs11")]
+            SigningKey::Pkcs11(key) => write!(formatter, "SigningKey::Pkcs11({:?})", key),
+        }
+    }
+}
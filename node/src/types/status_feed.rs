@@ -17,11 +17,11 @@ use casper_types::{EraId, ProtocolVersion, PublicKey, TimeDiff, Timestamp};
 
 use crate::{
     components::{
-        chain_synchronizer::Progress,
+        chain_synchronizer::{Progress, SyncStatistics},
         chainspec_loader::NextUpgrade,
         rpc_server::rpcs::docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     },
-    types::{ActivationPoint, Block, BlockHash, NodeId, PeersMap},
+    types::{ActivationPoint, Block, BlockHash, HealthReport, NodeId, PeersMap},
 };
 
 static CHAINSPEC_INFO: Lazy<ChainspecInfo> = Lazy::new(|| {
@@ -49,6 +49,8 @@ static GET_STATUS_RESULT: Lazy<GetStatusResult> = Lazy::new(|| {
         version: crate::VERSION_STRING.as_str(),
         node_uptime: Duration::from_secs(13),
         node_state: NodeState::Participating,
+        sync_statistics: None,
+        health: HealthReport::doc_example().clone(),
     };
     GetStatusResult::new(status_feed, DOCS_EXAMPLE_PROTOCOL_VERSION)
 });
@@ -110,9 +112,14 @@ pub struct StatusFeed {
     pub node_uptime: Duration,
     /// The current state of node.
     pub node_state: NodeState,
+    /// Statistics about the blocks synced so far, if the node is syncing blocks.
+    pub sync_statistics: Option<SyncStatistics>,
+    /// The health of the node's components.
+    pub health: HealthReport,
 }
 
 impl StatusFeed {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         last_added_block: Option<Block>,
         peers: BTreeMap<NodeId, String>,
@@ -120,6 +127,8 @@ impl StatusFeed {
         consensus_status: Option<(PublicKey, Option<TimeDiff>)>,
         node_uptime: Duration,
         node_state: NodeState,
+        sync_statistics: Option<SyncStatistics>,
+        health: HealthReport,
     ) -> Self {
         let (our_public_signing_key, round_length) = match consensus_status {
             Some((public_key, round_length)) => (Some(public_key), round_length),
@@ -134,6 +143,8 @@ impl StatusFeed {
             version: crate::VERSION_STRING.as_str(),
             node_uptime,
             node_state,
+            sync_statistics,
+            health,
         }
     }
 }
@@ -164,7 +175,7 @@ impl From<Block> for MinimalBlockInfo {
 }
 
 /// Result for "info_get_status" RPC response.
-#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[derive(PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct GetStatusResult {
     /// The RPC API version.
@@ -191,6 +202,10 @@ pub struct GetStatusResult {
     pub uptime: TimeDiff,
     /// The current state of node.
     pub node_state: NodeState,
+    /// Statistics about the blocks synced so far, if the node is syncing blocks.
+    pub sync_statistics: Option<SyncStatistics>,
+    /// The health of the node's components.
+    pub health: HealthReport,
 }
 
 impl GetStatusResult {
@@ -207,6 +222,8 @@ impl GetStatusResult {
             next_upgrade: status_feed.chainspec_info.next_upgrade,
             uptime: status_feed.node_uptime.into(),
             node_state: status_feed.node_state,
+            sync_statistics: status_feed.sync_statistics,
+            health: status_feed.health,
             #[cfg(not(test))]
             build_version: crate::VERSION_STRING.clone(),
 
@@ -1,6 +1,7 @@
 //! Various functions that are not limited to a particular module, but are too small to warrant
 //! being factored out into standalone crates.
 
+pub(crate) mod byte_size;
 mod display_error;
 pub(crate) mod ds;
 mod external;
@@ -267,6 +268,11 @@ impl<T> WithDir<T> {
         &self.value
     }
 
+    /// Get a mutable reference to the inner value.
+    pub(crate) fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
     /// Adds `self.dir` as a parent if `path` is relative, otherwise returns `path` unchanged.
     pub fn with_dir(&self, path: PathBuf) -> PathBuf {
         if path.is_relative() {
@@ -365,6 +371,42 @@ pub(crate) fn xor(lhs: &mut [u8], rhs: &[u8]) {
         .for_each(|(sb, &cb)| sb.bitxor_assign(cb));
 }
 
+/// Returns the `Debug` representation of `value`, truncated to at most `max_len` bytes.
+///
+/// Formatting stops once the limit is reached, so this is cheap even for large values.
+pub(crate) fn truncated_debug<T: Debug>(value: &T, max_len: usize) -> String {
+    struct LimitedWriter {
+        buffer: String,
+        max_len: usize,
+    }
+
+    impl fmt::Write for LimitedWriter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let remaining = self.max_len - self.buffer.len();
+            if s.len() <= remaining {
+                self.buffer.push_str(s);
+                return Ok(());
+            }
+            let mut end = remaining;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.buffer.push_str(&s[..end]);
+            // Abort formatting, the rest would be discarded anyway.
+            Err(fmt::Error)
+        }
+    }
+
+    let mut writer = LimitedWriter {
+        buffer: String::new(),
+        max_len,
+    };
+    if fmt::write(&mut writer, format_args!("{:?}", value)).is_err() {
+        writer.buffer.push_str("...");
+    }
+    writer.buffer
+}
+
 /// Wait until all strong references for a particular arc have been dropped.
 ///
 /// Downgrades and immediately drops the `Arc`, keeping only a weak reference. The reference will
@@ -410,7 +452,7 @@ mod tests {
 
     use crate::utils::SharedFlag;
 
-    use super::{wait_for_arc_drop, xor};
+    use super::{truncated_debug, wait_for_arc_drop, xor};
 
     #[test]
     fn xor_works() {
@@ -467,6 +509,14 @@ mod tests {
         assert!(weak.upgrade().is_none());
     }
 
+    #[test]
+    fn truncated_debug_limits_length() {
+        assert_eq!(truncated_debug(&"short", 16), "\"short\"");
+        assert_eq!(truncated_debug(&vec![1, 2, 3, 4], 6), "[1, 2,...");
+        // Multi-byte characters are never split.
+        assert_eq!(truncated_debug(&"ää", 4), "\"ä...");
+    }
+
     #[test]
     fn shared_flag_sanity_check() {
         let flag = SharedFlag::new();
@@ -0,0 +1,202 @@
+//! Human-readable byte sizes in config files.
+//!
+//! Config fields holding a number of bytes accept either a plain integer, or a string made up of
+//! an integer and a unit, e.g. `'512MiB'` or `'10 GB'`.  Both decimal (`kB`, `MB`, `GB`, `TB`) and
+//! binary (`KiB`, `MiB`, `GiB`, `TiB`) units are supported, while `B` or no unit denotes bytes.
+//!
+//! Fields opt in via `#[serde(deserialize_with = "byte_size::deserialize")]`, or
+//! `deserialize_option` for optional fields.  Serialization is unaffected, so sizes are always
+//! written out as plain integers.
+
+use std::{any, convert::TryFrom, fmt};
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use thiserror::Error;
+
+/// The recognized units and their sizes in bytes.
+const UNITS: [(&str, u64); 11] = [
+    ("B", 1),
+    ("kB", 1_000),
+    ("KB", 1_000),
+    ("MB", 1_000_000),
+    ("GB", 1_000_000_000),
+    ("TB", 1_000_000_000_000),
+    ("KiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+    ("TiB", 1 << 40),
+    ("", 1),
+];
+
+/// An error parsing a byte size.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum ParseByteSizeError {
+    /// The input was empty.
+    #[error("empty byte size")]
+    Empty,
+    /// The input didn't start with an integer.
+    #[error("byte size '{0}' does not start with an integer")]
+    InvalidNumber(String),
+    /// The unit following the integer isn't one we know.
+    #[error(
+        "unknown unit '{unit}' in byte size '{input}', expected one of B, kB, KiB, MB, MiB, GB, \
+        GiB, TB or TiB"
+    )]
+    UnknownUnit { input: String, unit: String },
+    /// The size doesn't fit into 64 bits.
+    #[error("byte size '{0}' is too large")]
+    Overflow(String),
+}
+
+/// Parses a byte size such as `512MiB` into a number of bytes.
+///
+/// Digits may be separated by underscores as in TOML integers, and whitespace is permitted between
+/// the number and the unit.  Fractions are not supported.
+pub(crate) fn parse(input: &str) -> Result<u64, ParseByteSizeError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(ParseByteSizeError::Empty);
+    }
+
+    let unit_start = trimmed
+        .find(|c: char| !c.is_ascii_digit() && c != '_')
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(unit_start);
+    let digits = number.replace('_', "");
+    if digits.is_empty() || number.starts_with('_') || number.ends_with('_') {
+        return Err(ParseByteSizeError::InvalidNumber(input.to_string()));
+    }
+    let value: u64 = digits
+        .parse()
+        .map_err(|_| ParseByteSizeError::Overflow(input.to_string()))?;
+
+    let unit = unit.trim_start();
+    let multiplier = UNITS
+        .iter()
+        .find(|(name, _)| *name == unit)
+        .map(|(_, multiplier)| *multiplier)
+        .ok_or_else(|| ParseByteSizeError::UnknownUnit {
+            input: input.to_string(),
+            unit: unit.to_string(),
+        })?;
+
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| ParseByteSizeError::Overflow(input.to_string()))
+}
+
+/// Deserializes a byte size given either as an integer or as a string with a unit.
+pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<u64>,
+{
+    let bytes = deserializer.deserialize_any(ByteSizeVisitor)?;
+    T::try_from(bytes).map_err(|_| {
+        de::Error::custom(format!(
+            "byte size {} does not fit into {}",
+            bytes,
+            any::type_name::<T>()
+        ))
+    })
+}
+
+/// Deserializes an optional byte size, see [`deserialize`].
+///
+/// Fields using this must also be `#[serde(default)]`, as a missing value is handled by serde
+/// before the deserializer is called.
+pub(crate) fn deserialize_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<u64>,
+{
+    deserialize(deserializer).map(Some)
+}
+
+struct ByteSizeVisitor;
+
+impl<'de> Visitor<'de> for ByteSizeVisitor {
+    type Value = u64;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a number of bytes, or a string such as '512MiB'")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+        parse(value).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_sizes_with_units() {
+        assert_eq!(parse("0"), Ok(0));
+        assert_eq!(parse("2_621_440"), Ok(2_621_440));
+        assert_eq!(parse("100B"), Ok(100));
+        assert_eq!(parse("2kB"), Ok(2_000));
+        assert_eq!(parse("512MiB"), Ok(512 * 1024 * 1024));
+        assert_eq!(parse(" 10 GB "), Ok(10_000_000_000));
+        assert_eq!(parse("450GiB"), Ok(483_183_820_800));
+        assert_eq!(parse("1TiB"), Ok(1 << 40));
+    }
+
+    #[test]
+    fn should_reject_invalid_sizes() {
+        assert_eq!(parse(""), Err(ParseByteSizeError::Empty));
+        assert!(matches!(
+            parse("MiB"),
+            Err(ParseByteSizeError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            parse("-1"),
+            Err(ParseByteSizeError::InvalidNumber(_))
+        ));
+        assert!(matches!(
+            parse("1.5GiB"),
+            Err(ParseByteSizeError::UnknownUnit { .. })
+        ));
+        assert!(matches!(
+            parse("512mib"),
+            Err(ParseByteSizeError::UnknownUnit { .. })
+        ));
+        assert!(matches!(
+            parse("16_777_216TiB"),
+            Err(ParseByteSizeError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn should_deserialize_integers_and_strings() {
+        #[derive(Deserialize)]
+        struct Sizes {
+            #[serde(deserialize_with = "deserialize")]
+            small: u32,
+            #[serde(default, deserialize_with = "deserialize_option")]
+            large: Option<usize>,
+        }
+
+        let sizes: Sizes = toml::from_str("small = 1024\nlarge = '2GiB'").unwrap();
+        assert_eq!(sizes.small, 1024);
+        assert_eq!(sizes.large, Some(1 << 31));
+
+        let sizes: Sizes = toml::from_str("small = '2MiB'").unwrap();
+        assert_eq!(sizes.small, 2 << 20);
+        assert_eq!(sizes.large, None);
+
+        assert!(toml::from_str::<Sizes>("small = '8GiB'").is_err());
+        assert!(toml::from_str::<Sizes>("small = -1").is_err());
+    }
+}
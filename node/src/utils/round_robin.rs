@@ -10,6 +10,7 @@ use std::{
     hash::Hash,
     num::NonZeroUsize,
     sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
 };
 
 use enum_iterator::IntoEnumIterator;
@@ -52,7 +53,14 @@ struct QueueState<I> {
     ///
     /// Do not modify this unless you are holding the `queue` lock.
     event_count: AtomicUsize,
-    queue: Mutex<VecDeque<I>>,
+    queue: Mutex<VecDeque<QueuedItem<I>>>,
+}
+
+/// An item in a queue, along with the time it was pushed.
+#[derive(Debug)]
+struct QueuedItem<I> {
+    item: I,
+    pushed_at: Instant,
 }
 
 impl<I> QueueState<I> {
@@ -66,14 +74,17 @@ impl<I> QueueState<I> {
     /// Remove all events from a queue.
     async fn drain(&self) -> Vec<I> {
         let mut guard = self.queue.lock().await;
-        let events: Vec<I> = guard.drain(..).collect();
+        let events: Vec<I> = guard.drain(..).map(|queued| queued.item).collect();
         self.event_count.fetch_sub(events.len(), Ordering::SeqCst);
         events
     }
 
     #[inline]
     async fn push_back(&self, element: I) {
-        self.queue.lock().await.push_back(element);
+        self.queue.lock().await.push_back(QueuedItem {
+            item: element,
+            pushed_at: Instant::now(),
+        });
         self.event_count.fetch_add(1, Ordering::SeqCst);
     }
 
@@ -123,7 +134,7 @@ where
     ///
     /// A `BTreeMap` is used to make the ordering constant, it will be in the natural order defined
     /// by `Ord` on `K`.
-    queues: BTreeMap<K, &'a VecDeque<I>>,
+    queues: BTreeMap<K, Vec<&'a I>>,
 }
 
 impl<I, K> WeightedRoundRobin<I, K>
@@ -139,7 +150,7 @@ where
         let locks = self.lock_queues().await;
         let mut queues = BTreeMap::new();
         for (kind, guard) in &locks {
-            let queue = &**guard;
+            let queue = guard.iter().map(|queued| &queued.item).collect();
             queues.insert(*kind, queue);
         }
 
@@ -148,7 +159,7 @@ where
     }
 
     /// Lock all queues in a well-defined order to avoid deadlocks conditions.
-    async fn lock_queues(&self) -> Vec<(K, MutexGuard<'_, VecDeque<I>>)> {
+    async fn lock_queues(&self) -> Vec<(K, MutexGuard<'_, VecDeque<QueuedItem<I>>>)> {
         let mut locks = Vec::new();
         for kind in K::into_enum_iter() {
             let queue_guard = self
@@ -227,6 +238,14 @@ where
     ///
     /// Asynchronously waits until a queue is non-empty or panics if an internal error occurred.
     pub(crate) async fn pop(&self) -> (I, K) {
+        let (item, queue, _) = self.pop_timed().await;
+        (item, queue)
+    }
+
+    /// Returns the next item from queue, along with the time it spent queued.
+    ///
+    /// See [`pop`](Self::pop) for details.
+    pub(crate) async fn pop_timed(&self) -> (I, K, Duration) {
         // Safe to `expect` here as the only way for acquiring a permit to fail would be if the
         // `self.total` semaphore were closed.
         self.total.acquire().await.expect("should acquire").forget();
@@ -253,12 +272,16 @@ where
             // We have hit a queue that is not empty. Decrease tickets and pop.
             inner.active_slot.tickets -= 1;
 
-            let item = current_queue
+            let queued = current_queue
                 .pop_front()
                 // We hold the queue's lock and checked `is_empty` earlier.
                 .expect("item disappeared. this should not happen");
             queue_state.dec_count();
-            break (item, inner.active_slot.key);
+            break (
+                queued.item,
+                inner.active_slot.key,
+                queued.pushed_at.elapsed(),
+            );
         }
     }
 
@@ -300,7 +323,6 @@ where
     }
 
     /// Returns the number of events currently in the queue.
-    #[cfg(test)]
     pub(crate) fn item_count(&self) -> usize {
         self.total.available_permits()
     }
@@ -312,6 +334,13 @@ where
             .map(|(key, queue)| (*key, queue.event_count()))
             .collect()
     }
+
+    /// Returns the number of events in the given queue.
+    pub(crate) fn event_queue_count(&self, queue: K) -> usize {
+        self.queues
+            .get(&queue)
+            .map_or(0, |queue_state| queue_state.event_count())
+    }
 }
 
 #[cfg(test)]
@@ -384,4 +413,16 @@ mod tests {
         assert_eq!(scheduler.item_count(), 0);
         assert!(scheduler.drain_queues().await.is_empty());
     }
+
+    #[tokio::test]
+    async fn should_report_time_spent_queued() {
+        let scheduler = WeightedRoundRobin::<char, QueueKind>::new(weights());
+        scheduler.push('a', QueueKind::One).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        scheduler.push('b', QueueKind::Two).await;
+
+        let (item, queue, queued_for) = scheduler.pop_timed().await;
+        assert_eq!(('a', QueueKind::One), (item, queue));
+        assert!(queued_for >= Duration::from_millis(10));
+    }
 }
@@ -6,6 +6,8 @@ use core::{
     ops::{Add, AddAssign, Div, Mul, Rem, Shl, Shr, Sub, SubAssign},
     time::Duration,
 };
+#[cfg(any(feature = "testing", test))]
+use std::cell::RefCell;
 #[cfg(any(feature = "std", test))]
 use std::{
     fmt::{self, Display, Formatter},
@@ -29,6 +31,12 @@ use crate::bytesrepr::{self, FromBytes, ToBytes};
 #[cfg(any(feature = "testing", test))]
 use crate::testing::TestRng;
 
+#[cfg(any(feature = "testing", test))]
+thread_local! {
+    /// The clock overriding the system time in `Timestamp::now` on this thread, if any.
+    static MOCK_CLOCK: RefCell<Option<Box<dyn Fn() -> Timestamp>>> = RefCell::new(None);
+}
+
 /// A timestamp type, representing a concrete moment in time.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
@@ -42,7 +50,16 @@ pub struct Timestamp(u64);
 impl Timestamp {
     #[cfg(any(feature = "std", test))]
     /// Returns the timestamp of the current moment.
+    ///
+    /// In tests, the system time can be replaced on the current thread using
+    /// `Timestamp::set_mock_clock`.
     pub fn now() -> Self {
+        #[cfg(any(feature = "testing", test))]
+        {
+            if let Some(now) = MOCK_CLOCK.with(|clock| clock.borrow().as_ref().map(|now| now())) {
+                return now;
+            }
+        }
         let millis = SystemTime::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64;
         Timestamp(millis)
     }
@@ -98,6 +115,17 @@ impl Timestamp {
     pub fn checked_sub(self, other: TimeDiff) -> Option<Timestamp> {
         self.0.checked_sub(other.0).map(Timestamp)
     }
+
+    /// Makes `Timestamp::now` return the result of `clock` on the current thread, e.g. to let it
+    /// follow a paused tokio clock, until `Timestamp::reset_mock_clock` is called.
+    pub fn set_mock_clock<F: Fn() -> Timestamp + 'static>(clock: F) {
+        MOCK_CLOCK.with(|mock_clock| *mock_clock.borrow_mut() = Some(Box::new(clock)));
+    }
+
+    /// Makes `Timestamp::now` return the system time again on the current thread.
+    pub fn reset_mock_clock() {
+        MOCK_CLOCK.with(|mock_clock| *mock_clock.borrow_mut() = None);
+    }
 }
 
 #[cfg(any(feature = "std", test))]
@@ -404,6 +432,17 @@ mod tests {
         bytesrepr::test_serialization_roundtrip(&timestamp);
     }
 
+    #[test]
+    fn should_use_mock_clock() {
+        let timestamp = Timestamp(1_000);
+        Timestamp::set_mock_clock(move || timestamp);
+        assert_eq!(Timestamp::now(), timestamp);
+        assert_eq!(Timestamp(500).elapsed(), TimeDiff(500));
+
+        Timestamp::reset_mock_clock();
+        assert!(Timestamp::now() > timestamp);
+    }
+
     #[test]
     fn timediff_serialization_roundtrip() {
         let mut rng = TestRng::new();
@@ -144,6 +144,7 @@ async fn main() -> Result<(), anyhow::Error> {
             deploys,
             transfers,
             verifiable_chunked_hash_activation,
+            None,
         )?;
         let elapsed_micros = start.elapsed().as_micros() as u64;
         execution_time_hist
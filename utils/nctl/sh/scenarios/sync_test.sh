@@ -37,12 +37,12 @@ function main() {
     do_send_transfers
     # 6. Wait until they're all included in the chain.
     do_await_deploy_inclusion
-    # 7. Start the node in sync_to_genesis mode using hash from 4)
-    do_start_new_node "$SYNC_TO_GENESIS_NODE_ID" 'true'
-    # 8. Wait until sync_to_genesis node is synchronized.
+    # 7. Start the node in archival sync mode using hash from 4)
+    do_start_new_node "$SYNC_TO_GENESIS_NODE_ID" 'archival'
+    # 8. Wait until archival node is synchronized.
     do_await_full_synchronization "$SYNC_TO_GENESIS_NODE_ID"
     # 9. Start the node in fast-sync mode using hash from 4)
-    do_start_new_node "$FAST_SYNC_NODE_ID" 'false'
+    do_start_new_node "$FAST_SYNC_NODE_ID" 'recent'
     # 10. Wait until fast-sync node is synchronized.
     do_await_full_synchronization "$FAST_SYNC_NODE_ID"
     # 11. Run Closing Health Checks
@@ -114,7 +114,7 @@ function do_read_lfb_hash() {
 
 function do_start_new_node() {
     local NODE_ID=${1}
-    local SYNC_TO_GENESIS_MODE=${2}
+    local SYNC_MODE=${2}
     local CONFIG_PATH
 
     CONFIG_PATH="$(find $(get_path_to_node $NODE_ID) -name config.toml)"
@@ -122,10 +122,10 @@ function do_start_new_node() {
     log_step "starting new node-$NODE_ID. Syncing from hash=${LFB_HASH}"
     export RUST_LOG="info,casper_node::components::linear_chain_sync=trace"
 
-    if [ ! -z "$SYNC_TO_GENESIS_MODE" ]; then
-        sed -i "s/sync_to_genesis =.*/sync_to_genesis = $SYNC_TO_GENESIS_MODE/g" "$CONFIG_PATH"
+    if [ ! -z "$SYNC_MODE" ]; then
+        sed -i "s/sync_mode =.*/sync_mode = '$SYNC_MODE'/g" "$CONFIG_PATH"
     fi
-    log "Sync-to-genesis mode: $(cat $CONFIG_PATH | grep 'sync_to_genesis')"
+    log "Sync mode: $(cat $CONFIG_PATH | grep 'sync_mode')"
 
     # TODO: Do not hardcode.
     do_node_start "$NODE_ID" "$LFB_HASH"